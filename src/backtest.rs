@@ -0,0 +1,502 @@
+use crate::config;
+use crate::error::DataError;
+use crate::events;
+use crate::model_params::{self, ModelParams};
+use crate::models::{BoardKind, Lineup, Player, Stone, Team};
+use crate::pipeline;
+use crate::records;
+use crate::season::{self, Season};
+use crate::utils;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// 모델에 요소를 하나씩 더해 가며 채점합니다. 뒤의 요소는 앞의 요소를 모두 포함합니다.
+const COMPONENTS: [&str; 7] = ["Elo", "+장고/속기/초속기·흑백·상대 가중치", "+컨디션(최근 경기력)", "+나이·레이팅 추세 전망", "+KB리그 성적", "+레이팅 편차", "+상대전적"];
+
+// 모델 상수를 맞출 때 한 번에 바꾸는 비율과 멈추는 비율, 최대 회차입니다.
+const FIT_INITIAL_STEP: f64 = 0.2;
+const FIT_MIN_STEP: f64 = 0.01;
+const FIT_MAX_ROUNDS: usize = 10;
+
+#[derive(Serialize, Deserialize)]
+struct BacktestFile {
+    season: String,
+    matches: Vec<BacktestMatch>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BacktestMatch {
+    date: String,
+    team1: String,
+    team2: String,
+    boards: Vec<BoardResult>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BoardResult {
+    board: BoardKind,
+    player1: String,
+    player2: String,
+    winner: String,
+    // black 또는 white
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    player1_color: Option<Stone>,
+}
+
+#[derive(Clone, Default)]
+struct Score {
+    count: usize,
+    brier: f64,
+    log_loss: f64,
+    // 예측 확률 10%p 구간별 (판 수, 예측 확률 합, 실제 승리 수)
+    buckets: [(usize, f64, f64); 10],
+}
+
+impl Score {
+    fn add(&mut self, probability: f64, outcome: f64) {
+        let clamped = probability.clamp(1e-6, 1.0 - 1e-6);
+        self.count += 1;
+        self.brier += (probability - outcome).powi(2);
+        self.log_loss -= outcome * clamped.ln() + (1.0 - outcome) * (1.0 - clamped).ln();
+        let bucket = &mut self.buckets[((probability * 10.0) as usize).min(9)];
+        bucket.0 += 1;
+        bucket.1 += probability;
+        bucket.2 += outcome;
+    }
+
+    fn merge(&mut self, other: &Score) {
+        self.count += other.count;
+        self.brier += other.brier;
+        self.log_loss += other.log_loss;
+        for (bucket, other_bucket) in self.buckets.iter_mut().zip(other.buckets.iter()) {
+            bucket.0 += other_bucket.0;
+            bucket.1 += other_bucket.1;
+            bucket.2 += other_bucket.2;
+        }
+    }
+
+    fn print_summary(&self, label: &str, unit: &str) {
+        if self.count == 0 {
+            println!("  {}: 채점할 {}이 없습니다.", label, unit);
+            return;
+        }
+        println!("  {}: {}{}, Brier {:.4}, log-loss {:.4}", label, self.count, unit, self.brier / self.count as f64, self.log_loss / self.count as f64);
+    }
+
+    fn print_calibration(&self) {
+        for (index, (count, predicted, observed)) in self.buckets.iter().enumerate().filter(|(_, bucket)| bucket.0 > 0) {
+            println!(
+                "    {:>3}~{:>3}%: {}판, 예측 {:.1}%, 실제 {:.1}%",
+                index * 10,
+                index * 10 + 10,
+                count,
+                predicted / *count as f64 * 100.0,
+                observed / *count as f64 * 100.0
+            );
+        }
+    }
+}
+
+fn backtest_path(season: &Season) -> String {
+    format!("{}/{}.json", config::get().backtest_dir, season.season())
+}
+
+fn load_backtest_file(path: &str) -> Result<BacktestFile, Box<dyn Error>> {
+    let text = fs::read_to_string(path).map_err(|e| format!("백테스트 경기 파일 {}을(를) 읽을 수 없습니다: {}", path, e))?;
+    let backtest_file: BacktestFile = serde_json::from_str(&text).map_err(|e| format!("백테스트 경기 파일 {}의 형식이 올바르지 않습니다: {}", path, e))?;
+    Ok(backtest_file)
+}
+
+// 팀 이름과 1~4국 기사입니다.
+pub type RecordedLineup = (String, [String; 4]);
+
+// 경기 파일에 적힌 팀별 1~4국 기사입니다. 장고 1판과 속기 3판이 모두 적힌 경기만 돌려주며, 경기 파일이 없으면 빈 목록입니다.
+pub fn recorded_lineups(season: &Season) -> Result<Vec<RecordedLineup>, Box<dyn Error>> {
+    let path = backtest_path(season);
+    if !Path::new(&path).exists() {
+        return Ok(Vec::new());
+    }
+    let mut lineups = Vec::new();
+    for backtest_match in load_backtest_file(&path)?.matches {
+        let rapid: Vec<&BoardResult> = backtest_match.boards.iter().filter(|board| board.board == BoardKind::Rapid).collect();
+        let blitz: Vec<&BoardResult> = backtest_match.boards.iter().filter(|board| board.board == BoardKind::Blitz).collect();
+        if rapid.len() != 1 || blitz.len() != 3 {
+            continue;
+        }
+        let boards = [rapid[0], blitz[0], blitz[1], blitz[2]];
+        lineups.push((backtest_match.team1.clone(), boards.map(|board| board.player1.clone())));
+        lineups.push((backtest_match.team2.clone(), boards.map(|board| board.player2.clone())));
+    }
+    Ok(lineups)
+}
+
+// 요소별 승률(0~1)을 COMPONENTS 순서로 돌려줍니다. 전망과 KB리그 성적은 라인업 계산과 같이 설정에서 켠 경우에만 더하므로,
+// 꺼져 있으면 앞 요소와 같은 값이 됩니다. 레이팅 편차를 반영한 값은 라인업 계산의 board_probability 그대로입니다.
+fn board_probabilities(player1: &Player, player2: &Player, board: &BoardResult, head_to_head: (u32, u32), params: &ModelParams) -> [f64; 7] {
+    let elo = utils::calculate_win_probability(player1.elo_rating(), player2.elo_rating());
+
+    // 흑백을 알면 라인업 계산과 같은 배율로 흑백 가중치를 더합니다.
+    let player2_color = board.player1_color.map(|stone| stone.opposite());
+    let player1_rating = player1.elo_rating() + utils::board_weight(player1, board.board, board.player1_color, params) + utils::relative_weight(player1, player2);
+    let player2_rating = player2.elo_rating() + utils::board_weight(player2, board.board, player2_color, params) + utils::relative_weight(player2, player1);
+    let weighted = utils::calculate_win_probability(player1_rating, player2_rating);
+
+    let player1_rating = player1_rating + player1.condition_weight();
+    let player2_rating = player2_rating + player2.condition_weight();
+    let with_condition = utils::calculate_win_probability(player1_rating, player2_rating);
+
+    let player1_rating = player1_rating + player1.projection_weight();
+    let player2_rating = player2_rating + player2.projection_weight();
+    let with_projection = utils::calculate_win_probability(player1_rating, player2_rating);
+
+    let player1_rating = player1_rating + player1.team_league_weight();
+    let player2_rating = player2_rating + player2.team_league_weight();
+    let with_team_league = utils::calculate_win_probability(player1_rating, player2_rating);
+
+    let with_deviation = utils::board_probability(player1, player2, board.board, board.player1_color, params);
+
+    let with_head_to_head = utils::shrink_head_to_head_with_prior(with_deviation, head_to_head.0, head_to_head.1, config::get().head_to_head_prior_games);
+
+    [elo, weighted, with_condition, with_projection, with_team_league, with_deviation, with_head_to_head]
+}
+
+fn find_player<'a>(teams: &'a [Team], name: &str) -> Option<&'a Player> {
+    teams.iter().flat_map(|team| team.players()).find(|player| player.korean_name() == name || player.aliases().iter().any(|alias| alias == name))
+}
+
+struct Replay {
+    board_scores: Vec<HashMap<usize, Score>>,
+    match_scores: Vec<Score>,
+    // 라인업 계산(calculate_match_result)이 경기 전에 내놓았을 팀 승리확률의 점수입니다.
+    lineup_score: Score,
+}
+
+impl Replay {
+    // 모델 상수를 맞출 때 줄이려는 값입니다. 모든 요소를 반영한 판별 승률과 라인업 계산의 팀 승리확률을 함께 봅니다.
+    fn objective(&self) -> f64 {
+        let mut total = Score::default();
+        for score in self.board_scores[COMPONENTS.len() - 1].values() {
+            total.merge(score);
+        }
+        total.merge(&self.lineup_score);
+        if total.count == 0 { f64::INFINITY } else { total.log_loss / total.count as f64 }
+    }
+}
+
+// 경기마다 전날까지의 상태로 판별 승률과 팀 승리확률을 다시 계산하여 채점합니다.
+fn replay(season: &Season, backtest_file: &BacktestFile, sync_failures: &HashMap<String, DataError>) -> Result<Replay, Box<dyn Error>> {
+    let params = model_params::get();
+    let mut result = Replay {
+        board_scores: vec![HashMap::new(); COMPONENTS.len()],
+        match_scores: vec![Score::default(); COMPONENTS.len()],
+        lineup_score: Score::default(),
+    };
+
+    let progress = pipeline::progress_bar(backtest_file.matches.len() as u64);
+    for (match_index, backtest_match) in backtest_file.matches.iter().enumerate() {
+        progress.inc(1);
+        let date = NaiveDate::parse_from_str(&backtest_match.date, "%Y-%m-%d")
+            .map_err(|_| format!("{}번째 경기의 날짜 '{}'을(를) 해석할 수 없습니다. (YYYY-MM-DD)", match_index + 1, backtest_match.date))?;
+        let find_team = |name: &str| {
+            season.teams().iter().find(|team| team.team_name() == name).cloned().ok_or_else(|| format!("{}번째 경기의 팀 '{}'이(가) 시즌 파일에 없습니다.", match_index + 1, name))
+        };
+        let mut teams = vec![find_team(&backtest_match.team1)?, find_team(&backtest_match.team2)?];
+
+        progress.set_message(format!("{} {} vs {}", backtest_match.date, backtest_match.team1, backtest_match.team2));
+        let as_of = date - Duration::try_days(1).unwrap();
+        if let Err(e) = utils::update_team_elo_ratings_as_of(&mut teams, as_of, sync_failures) {
+            progress.println(format!("경고: {}번째 경기의 레이팅을 계산하지 못해 건너뜁니다: {}", match_index + 1, e));
+            continue;
+        }
+
+        // 1~4국의 승률과 에이스 결정전의 승률을 요소별로 모아 팀 승패 예측에 사용합니다.
+        let mut regular_probabilities: Vec<Vec<f64>> = vec![Vec::new(); COMPONENTS.len()];
+        let mut ace_probabilities: Vec<Option<f64>> = vec![None; COMPONENTS.len()];
+        let mut regular_players: Vec<(Player, Player)> = Vec::new();
+        let mut team1_board_wins = 0;
+        let mut team2_board_wins = 0;
+
+        for board in &backtest_match.boards {
+            let (player1, player2) = match (find_player(&teams[..1], &board.player1), find_player(&teams[1..], &board.player2)) {
+                (Some(player1), Some(player2)) => (player1, player2),
+                _ => {
+                    progress.println(format!("경고: {} vs {} 대국의 기사를 팀 명단에서 찾지 못해 건너뜁니다.", board.player1, board.player2));
+                    continue;
+                }
+            };
+            if player1.elo_rating() == 0.0 || player2.elo_rating() == 0.0 {
+                progress.println(format!("경고: {} vs {} 대국은 레이팅이 없어 건너뜁니다.", board.player1, board.player2));
+                continue;
+            }
+            let outcome = if board.winner == board.player1 {
+                team1_board_wins += 1;
+                1.0
+            } else {
+                team2_board_wins += 1;
+                0.0
+            };
+
+            let record = utils::fetch_head_to_head_record(player1.korean_name(), player2.korean_name(), as_of).unwrap_or_default();
+            let head_to_head = (*record.get(player1.korean_name()).unwrap_or(&0), *record.get(player2.korean_name()).unwrap_or(&0));
+            let probabilities = board_probabilities(player1, player2, board, head_to_head, &params);
+
+            let board_index = BoardKind::all().iter().position(|kind| *kind == board.board).unwrap();
+            for (component, probability) in probabilities.iter().enumerate() {
+                result.board_scores[component].entry(board_index).or_default().add(*probability, outcome);
+                if board.board == BoardKind::Bullet {
+                    ace_probabilities[component] = Some(*probability * 100.0);
+                } else {
+                    regular_probabilities[component].push(*probability * 100.0);
+                }
+            }
+            if board.board != BoardKind::Bullet {
+                regular_players.push((player1.clone(), player2.clone()));
+            }
+        }
+
+        if regular_probabilities[0].len() != 4 || team1_board_wins == team2_board_wins {
+            continue;
+        }
+        let outcome = if team1_board_wins > team2_board_wins { 1.0 } else { 0.0 };
+        for component in 0..COMPONENTS.len() {
+            let (all_win, three_win_one_lose, two_win_two_lose, _, _) = utils::board_outcome_probabilities(&regular_probabilities[component]);
+            // 에이스 결정전이 열리지 않은 경기는 출전 기사를 알 수 없으므로 반반으로 봅니다.
+            let ace_probability = ace_probabilities[component].unwrap_or(50.0) / 100.0;
+            result.match_scores[component].add(all_win + three_win_one_lose + two_win_two_lose * ace_probability, outcome);
+        }
+
+        // 라인업 계산은 에이스 결정전 출전 기사를 모르는 상태에서 예측하므로, 실제 1~4국 출전 기사만 넣어 계산합니다.
+        if let Ok(relativities) = utils::generate_player_relativities_as_of(&teams, None, as_of) {
+            let lineup = |players: Vec<Player>| Lineup::new(players[0].clone(), players[1].clone(), players[2].clone(), players[3].clone());
+            let team1_lineup = lineup(regular_players.iter().map(|pair| pair.0.clone()).collect());
+            let team2_lineup = lineup(regular_players.iter().map(|pair| pair.1.clone()).collect());
+            let match_result = utils::calculate_match_result(team1_lineup, team2_lineup, relativities);
+            result.lineup_score.add(match_result.total_win_probability() / 100.0, outcome);
+        }
+    }
+    progress.finish_and_clear();
+    Ok(result)
+}
+
+fn season_sync_failures(season: &Season) -> Result<HashMap<String, DataError>, Box<dyn Error>> {
+    pipeline::prefetch_team_data(season.teams())
+}
+
+fn run_backtest(season: &Season) -> Result<(), Box<dyn Error>> {
+    let path = backtest_path(season);
+    let backtest_file = load_backtest_file(&path)?;
+    let sync_failures = season_sync_failures(season)?;
+    let result = replay(season, &backtest_file, &sync_failures)?;
+
+    println!("\n{} {} 백테스트 결과 ({}, {})", season.league_name(), season.season(), path, model_params::get().describe());
+    for (component, label) in COMPONENTS.iter().enumerate() {
+        println!("\n[{}]", label);
+        let mut all_boards = Score::default();
+        for (board_index, board) in BoardKind::all().iter().enumerate() {
+            let score = result.board_scores[component].get(&board_index).cloned().unwrap_or_default();
+            score.print_summary(board.label(), "판");
+            all_boards.merge(&score);
+        }
+        all_boards.print_summary("전체 판", "판");
+        result.match_scores[component].print_summary("팀 승패", "경기");
+        println!("  전체 판 보정:");
+        all_boards.print_calibration();
+    }
+    println!();
+    result.lineup_score.print_summary("라인업 계산의 팀 승리확률", "경기");
+    Ok(())
+}
+
+// 상수를 하나씩 늘리거나 줄여 보고 log-loss가 줄어들면 받아들입니다. 한 바퀴 동안 나아지지 않으면 폭을 절반으로 줄입니다.
+fn fit_model_params(season: &Season) -> Result<(), Box<dyn Error>> {
+    let path = backtest_path(season);
+    let backtest_file = load_backtest_file(&path)?;
+    let sync_failures = season_sync_failures(season)?;
+
+    let trial = model_params::TrialParams::begin();
+    let original = trial.original().clone();
+    let evaluate = |params: &ModelParams| -> Result<f64, Box<dyn Error>> {
+        model_params::set(params.clone());
+        Ok(replay(season, &backtest_file, &sync_failures)?.objective())
+    };
+
+    let mut best = original.clone();
+    let mut best_loss = evaluate(&best)?;
+    let original_loss = best_loss;
+    println!("현재 상수({})의 log-loss: {:.4}", original.describe(), best_loss);
+
+    let target_count = best.fit_targets().len();
+    let mut step = FIT_INITIAL_STEP;
+    for round in 1..=FIT_MAX_ROUNDS {
+        let mut improved = false;
+        for index in 0..target_count {
+            for direction in [1.0, -1.0] {
+                let mut candidate = best.clone();
+                let (name, old_value, new_value) = {
+                    let (name, value, scale) = candidate.fit_targets().swap_remove(index);
+                    let old_value = *value;
+                    let factor = 1.0 + step * scale;
+                    *value = if direction > 0.0 { old_value * factor } else { old_value / factor };
+                    (name, old_value, *value)
+                };
+                let loss = evaluate(&candidate)?;
+                if loss < best_loss {
+                    println!("{}회차 {}: {} → {} (log-loss {:.4})", round, name, old_value, new_value, loss);
+                    best = candidate;
+                    best_loss = loss;
+                    improved = true;
+                    break;
+                }
+            }
+        }
+        if !improved {
+            step /= 2.0;
+            if step < FIT_MIN_STEP {
+                break;
+            }
+        }
+    }
+    drop(trial);
+
+    println!("\nlog-loss: {:.4} → {:.4}", original_loss, best_loss);
+    if best_loss >= original_loss {
+        println!("현재 상수보다 나은 값을 찾지 못했습니다.");
+        return Ok(());
+    }
+    println!("같은 경기로 맞추고 채점했으므로 실제 성능은 다른 시즌의 백테스트로 확인해주세요.");
+    println!("{}에 저장하시겠습니까? (y/n): ", config::get().model_params_file);
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).expect("입력을 읽는 데 실패했습니다.");
+    if answer.trim() != "y" {
+        return Ok(());
+    }
+
+    best.version = original.version + 1;
+    best.fitted_season = Some(season.season().clone());
+    best.fitted_at = Some(chrono::Utc::now().date_naive().to_string());
+    best.log_loss = Some(best_loss);
+    model_params::save(&best)?;
+    println!("{}을(를) 저장했습니다.", best.describe());
+    Ok(())
+}
+
+// 대국 기록 데이터베이스의 해당 시즌 KB리그 대국을 날짜와 두 팀으로 묶어 경기 파일 초안을 만듭니다.
+// 기록에는 판 순서가 없으므로 먼저 둔 대국부터 장고, 속기 3판, 초속기로 두고 사용자가 확인하도록 합니다.
+fn draft_backtest_file(season: &Season) -> Result<(), Box<dyn Error>> {
+    let path = backtest_path(season);
+    if Path::new(&path).exists() {
+        println!("{} 파일이 이미 있습니다. 덮어쓰시겠습니까? (y/n): ", path);
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer).expect("입력을 읽는 데 실패했습니다.");
+        if answer.trim() != "y" {
+            return Ok(());
+        }
+    }
+
+    let team_of: HashMap<&String, usize> = season
+        .teams()
+        .iter()
+        .enumerate()
+        .flat_map(|(index, team)| team.players().iter().flat_map(|player| std::iter::once(player.korean_name()).chain(player.aliases().iter())).map(move |name| (name, index)))
+        .collect();
+
+    let catalogue = events::catalogue();
+    let mut seen = HashSet::new();
+    let mut matches: Vec<BacktestMatch> = Vec::new();
+    for game in records::games_with_event_text(season.season())? {
+        if !catalogue.is_kb_league_season(game.event_name(), Some(season.season())) {
+            continue;
+        }
+        let winner = game.winner_name().clone();
+        let loser = game.opponent_of(&winner).clone();
+        if !seen.insert((game.date(), winner.clone(), loser.clone())) {
+            continue;
+        }
+        let (winner_team, loser_team) = match (team_of.get(&winner), team_of.get(&loser)) {
+            (Some(&winner_team), Some(&loser_team)) if winner_team != loser_team => (winner_team, loser_team),
+            _ => continue,
+        };
+
+        // 시즌 파일에서 앞에 있는 팀을 team1로 둡니다.
+        let (team1, team2) = (winner_team.min(loser_team), winner_team.max(loser_team));
+        let (player1, player2) = if winner_team == team1 { (winner.clone(), loser) } else { (loser, winner.clone()) };
+        let winner_color = if game.winner_color().contains('흑') {
+            Some(Stone::Black)
+        } else if game.winner_color().contains('백') {
+            Some(Stone::White)
+        } else {
+            None
+        };
+        let player1_color = if winner_team == team1 { winner_color } else { winner_color.map(|stone| stone.opposite()) };
+        let date = game.date().to_string();
+        let team1_name = season.teams()[team1].team_name();
+        let team2_name = season.teams()[team2].team_name();
+        let index = match matches.iter().position(|m| m.date == date && &m.team1 == team1_name && &m.team2 == team2_name) {
+            Some(index) => index,
+            None => {
+                matches.push(BacktestMatch { date, team1: team1_name.clone(), team2: team2_name.clone(), boards: Vec::new() });
+                matches.len() - 1
+            }
+        };
+        let board = match matches[index].boards.len() {
+            0 => BoardKind::Rapid,
+            1..=3 => BoardKind::Blitz,
+            _ => BoardKind::Bullet,
+        };
+        matches[index].boards.push(BoardResult { board, player1, player2, winner, player1_color });
+    }
+
+    if matches.is_empty() {
+        println!("대국 기록 데이터베이스에 {} 시즌 KB리그 대국이 없습니다. 먼저 대국 기록을 동기화해주세요.", season.season());
+        return Ok(());
+    }
+
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let board_count: usize = matches.iter().map(|m| m.boards.len()).sum();
+    fs::write(&path, serde_json::to_string_pretty(&BacktestFile { season: season.season().clone(), matches })?)?;
+    println!("{}에 {}판의 초안을 저장했습니다. 판 종류(rapid, blitz, bullet)와 순서를 확인해주세요.", path, board_count);
+    Ok(())
+}
+
+pub fn execute_backtest_menu() {
+    loop {
+        let season = match season::load_current_season() {
+            Ok(season) => season,
+            Err(e) => {
+                println!("{}", e);
+                return;
+            }
+        };
+
+        println!("\n선택할 옵션:");
+        println!("1. 백테스트 실행 ({})", backtest_path(&season));
+        println!("2. 대국 기록으로 경기 파일 초안 만들기");
+        println!("3. 모델 상수 맞추기 ({})", model_params::get().describe());
+        println!("exit. 돌아가기");
+
+        let mut option = String::new();
+        io::stdin().read_line(&mut option).expect("입력을 읽는 데 실패했습니다.");
+
+        let result = match option.trim() {
+            "1" => run_backtest(&season),
+            "2" => draft_backtest_file(&season),
+            "3" => fit_model_params(&season),
+            "exit" => break,
+            _ => {
+                println!("잘못된 입력입니다. 다시 입력해주세요.");
+                Ok(())
+            }
+        };
+
+        if let Err(e) = result {
+            println!("오류가 발생했습니다: {}", e);
+        }
+    }
+}
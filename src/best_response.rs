@@ -0,0 +1,245 @@
+use crate::constraints::LineupConstraints;
+use crate::models::{Lineup, MatchResult};
+use crate::objective::Objective;
+
+// 상대 라인업(라인업 메트릭스의 열)에 거는 가중치입니다.
+pub enum OpponentWeighting {
+    // 모든 라인업을 같은 확률로 봅니다.
+    Uniform,
+    // 메트릭스의 열은 상대의 평균승률이 높은 순서이므로, 앞쪽 N개 라인업만 같은 확률로 봅니다.
+    Top(usize),
+    // 열마다 직접 정한 가중치입니다. 합이 1이 아니어도 되며, 0인 열은 나오지 않는 라인업입니다.
+    Weights(Vec<f64>),
+    // 상대의 라인업 조건을 만족하지 않는 라인업을 빼고 안쪽 가중치를 겁니다. Option<bool>은 상대 1국이 흑번인지입니다.
+    Constrained(Box<OpponentWeighting>, LineupConstraints, Option<bool>),
+}
+
+pub enum Aggregate {
+    // 가중치로 평균합니다.
+    Expected,
+    // 가중치가 있는 라인업 중 평가 기준으로 가장 불리한 경우(상대의 카운터픽)를 봅니다.
+    WorstCase,
+}
+
+pub struct LineupResponse {
+    pub row: usize,
+    pub total_win_probability: f64,
+    pub four_zero_probability: f64,
+    pub three_one_probability: f64,
+    pub two_two_probability: f64,
+    pub board_win_probabilities: [f64; 4],
+    // 평가 기준으로 계산한 라인업의 값이며, 목록은 이 값이 큰 순서입니다.
+    pub objective_value: f64,
+    // 가장 불리한 경우를 볼 때 상대 라인업의 열입니다.
+    pub worst_column: Option<usize>,
+}
+
+impl LineupResponse {
+    // 2:2 없이 이기는 확률(4-0 + 3-1)입니다.
+    pub fn outright_win_probability(&self) -> f64 {
+        self.four_zero_probability + self.three_one_probability
+    }
+}
+
+fn opponent_names(result: &MatchResult) -> [&String; 4] {
+    [result.first_rapid().player2().korean_name(), result.second_blitz().player2().korean_name(), result.third_blitz().player2().korean_name(), result.forth_blitz().player2().korean_name()]
+}
+
+impl OpponentWeighting {
+    pub fn column_weights(&self, matrix: &[Vec<MatchResult>]) -> Vec<f64> {
+        let columns = matrix.first().map_or(0, |row| row.len());
+        self.weights_over(matrix, vec![true; columns])
+    }
+
+    // allowed가 false인 열은 가중치가 0이며, Top은 허용된 열 가운데 앞쪽 N개를 고릅니다.
+    fn weights_over(&self, matrix: &[Vec<MatchResult>], allowed: Vec<bool>) -> Vec<f64> {
+        match self {
+            OpponentWeighting::Uniform => allowed.iter().map(|&allowed| if allowed { 1.0 } else { 0.0 }).collect(),
+            OpponentWeighting::Top(count) => {
+                let mut remaining = *count;
+                allowed
+                    .iter()
+                    .map(|&allowed| {
+                        if allowed && remaining > 0 {
+                            remaining -= 1;
+                            1.0
+                        } else {
+                            0.0
+                        }
+                    })
+                    .collect()
+            }
+            OpponentWeighting::Weights(weights) => weights.iter().zip(&allowed).map(|(weight, &allowed)| if allowed { *weight } else { 0.0 }).collect(),
+            OpponentWeighting::Constrained(inner, constraints, first_board_black) => {
+                let allowed = matrix.first().into_iter().flatten().zip(allowed).map(|(result, allowed)| allowed && constraints.allows(&opponent_names(result).map(|name| name.as_str()), *first_board_black)).collect();
+                inner.weights_over(matrix, allowed)
+            }
+        }
+    }
+}
+
+// 라인업 메트릭스에서 해당 라인업의 행입니다.
+pub fn row_of(matrix: &[Vec<MatchResult>], lineup: &Lineup) -> Option<usize> {
+    matrix.iter().position(|results| {
+        results.first().is_some_and(|result| {
+            result.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name()
+                && result.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name()
+                && result.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name()
+                && result.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name()
+        })
+    })
+}
+
+fn response_from(row: usize, result: &MatchResult, objective_value: f64, worst_column: Option<usize>) -> LineupResponse {
+    LineupResponse {
+        row,
+        total_win_probability: result.total_win_probability(),
+        four_zero_probability: result.four_zero_probability(),
+        three_one_probability: result.three_one_probability(),
+        two_two_probability: result.two_two_probability(),
+        board_win_probabilities: [result.first_rapid_win_probability(), result.second_blitz_win_probability(), result.third_blitz_win_probability(), result.forth_blitz_win_probability()],
+        objective_value,
+        worst_column,
+    }
+}
+
+// 후보 라인업마다 상대 라인업 가중치에 대한 결과를 계산하고, 평가 기준의 값이 큰 순서로 최대 top_k개를 돌려줍니다.
+// 가중치가 있는 상대 라인업이 없으면 빈 목록입니다.
+pub fn best_responses(matrix: &[Vec<MatchResult>], candidates: &[Lineup], weighting: &OpponentWeighting, aggregate: Aggregate, objective: &Objective, top_k: usize) -> Vec<LineupResponse> {
+    let weights = weighting.column_weights(matrix);
+    let total_weight: f64 = weights.iter().filter(|weight| **weight > 0.0).sum();
+    if total_weight <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut responses: Vec<LineupResponse> = candidates
+        .iter()
+        .filter_map(|lineup| row_of(matrix, lineup))
+        .map(|row| match aggregate {
+            Aggregate::Expected => {
+                let mut expected = response_from(row, &matrix[row][0], 0.0, None);
+                expected.total_win_probability = 0.0;
+                expected.four_zero_probability = 0.0;
+                expected.three_one_probability = 0.0;
+                expected.two_two_probability = 0.0;
+                expected.board_win_probabilities = [0.0; 4];
+                let (mut mean, mut square) = (0.0, 0.0);
+                for (result, weight) in matrix[row].iter().zip(&weights).filter(|(_, weight)| **weight > 0.0) {
+                    let share = weight / total_weight;
+                    let value = objective.value(result);
+                    mean += share * value;
+                    square += share * value * value;
+                    let response = response_from(row, result, value, None);
+                    expected.total_win_probability += share * response.total_win_probability;
+                    expected.four_zero_probability += share * response.four_zero_probability;
+                    expected.three_one_probability += share * response.three_one_probability;
+                    expected.two_two_probability += share * response.two_two_probability;
+                    for (board, probability) in expected.board_win_probabilities.iter_mut().zip(response.board_win_probabilities) {
+                        *board += share * probability;
+                    }
+                }
+                expected.objective_value = objective.risk_adjusted(mean, square - mean * mean);
+                expected
+            }
+            Aggregate::WorstCase => {
+                let (column, value) = (0..weights.len())
+                    .filter(|&column| weights[column] > 0.0)
+                    .map(|column| (column, objective.value(&matrix[row][column])))
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .unwrap();
+                response_from(row, &matrix[row][column], value, Some(column))
+            }
+        })
+        .collect();
+
+    responses.sort_by(|a, b| b.objective_value.total_cmp(&a.objective_value));
+    responses.truncate(top_k);
+    responses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints;
+    use crate::models::{Player, PlayerRelativity, Team};
+    use crate::objective::ObjectiveKind;
+
+    const TEAM1: [[&str; 4]; 2] = [["갑", "을", "병", "정"], ["을", "갑", "병", "정"]];
+    const TEAM2: [[&str; 4]; 3] = [["가", "나", "다", "라"], ["나", "가", "다", "라"], ["다", "나", "가", "라"]];
+
+    // 결과는 4-0 또는 0-4만 나오며, four_zero는 4-0 확률(%)입니다.
+    fn result(team1: [&str; 4], team2: [&str; 4], four_zero: f64) -> MatchResult {
+        let boards: Vec<PlayerRelativity> = team1.iter().zip(team2).map(|(player1, player2)| PlayerRelativity::new(Player::named(player1), Player::named(player2), 0, 0, four_zero, four_zero, four_zero, four_zero, four_zero)).collect();
+        let [first, second, third, forth]: [PlayerRelativity; 4] = boards.try_into().ok().unwrap();
+        MatchResult::new(first, second, third, forth, four_zero, four_zero, four_zero, four_zero, four_zero, 0.0, 0.0, 0.0, 100.0 - four_zero, four_zero, (four_zero, four_zero), Vec::new(), 0.0)
+    }
+
+    // 첫 라인업은 상대의 앞 두 라인업에 강하고 마지막 라인업에 약하며, 둘째 라인업은 언제나 중간입니다.
+    fn matrix() -> Vec<Vec<MatchResult>> {
+        let four_zero = [[90.0, 90.0, 10.0], [50.0, 50.0, 50.0]];
+        TEAM1.iter().zip(four_zero).map(|(team1, row)| TEAM2.iter().zip(row).map(|(team2, four_zero)| result(*team1, *team2, four_zero)).collect()).collect()
+    }
+
+    fn candidates() -> Vec<Lineup> {
+        TEAM1.iter().map(|names| {
+            let [first, second, third, forth] = names.map(Player::named);
+            Lineup::new(first, second, third, forth)
+        }).collect()
+    }
+
+    #[test]
+    fn weightings_pick_columns() {
+        let matrix = matrix();
+        assert_eq!(OpponentWeighting::Uniform.column_weights(&matrix), vec![1.0, 1.0, 1.0]);
+        assert_eq!(OpponentWeighting::Top(2).column_weights(&matrix), vec![1.0, 1.0, 0.0]);
+        assert_eq!(OpponentWeighting::Weights(vec![0.5, 0.0, 2.0]).column_weights(&matrix), vec![0.5, 0.0, 2.0]);
+    }
+
+    #[test]
+    fn constrained_weighting_drops_columns_before_the_inner_weighting() {
+        let matrix = matrix();
+        let team = Team::new("상대".to_string(), ["가", "나", "다", "라"].iter().map(|name| Player::named(name)).collect());
+        let constraints = constraints::parse(&team, 4, &["고정 2 1"]);
+        // 1국에 나를 고정하면 둘째 열만 남고, Top은 남은 열 가운데 앞쪽을 고릅니다.
+        let constrained = OpponentWeighting::Constrained(Box::new(OpponentWeighting::Top(1)), constraints.clone(), None);
+        assert_eq!(constrained.column_weights(&matrix), vec![0.0, 1.0, 0.0]);
+        let constrained = OpponentWeighting::Constrained(Box::new(OpponentWeighting::Weights(vec![3.0, 2.0, 1.0])), constraints, None);
+        assert_eq!(constrained.column_weights(&matrix), vec![0.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn expected_and_worst_case_disagree_on_a_counter_pick() {
+        let matrix = matrix();
+        let objective = Objective::new(ObjectiveKind::TotalWin, 0.0);
+
+        let expected = best_responses(&matrix, &candidates(), &OpponentWeighting::Uniform, Aggregate::Expected, &objective, 2);
+        assert_eq!(expected.iter().map(|response| response.row).collect::<Vec<_>>(), vec![0, 1]);
+        assert!((expected[0].objective_value - 190.0 / 300.0).abs() < 1e-9);
+        assert!((expected[0].total_win_probability - 190.0 / 3.0).abs() < 1e-9);
+        assert_eq!(expected[0].worst_column, None);
+
+        let worst_case = best_responses(&matrix, &candidates(), &OpponentWeighting::Uniform, Aggregate::WorstCase, &objective, 2);
+        assert_eq!(worst_case.iter().map(|response| response.row).collect::<Vec<_>>(), vec![1, 0]);
+        assert_eq!(worst_case[1].worst_column, Some(2));
+        assert!((worst_case[1].objective_value - 0.1).abs() < 1e-9);
+
+        // 상대의 마지막 라인업이 나오지 않으면 첫 라인업이 최악의 경우에도 낫습니다.
+        let worst_case = best_responses(&matrix, &candidates(), &OpponentWeighting::Top(2), Aggregate::WorstCase, &objective, 1);
+        assert_eq!(worst_case[0].row, 0);
+    }
+
+    #[test]
+    fn risk_aversion_penalises_the_swinging_lineup() {
+        let matrix = matrix();
+        let objective = Objective::new(ObjectiveKind::TotalWin, -1.0);
+        let responses = best_responses(&matrix, &candidates(), &OpponentWeighting::Uniform, Aggregate::Expected, &objective, 2);
+        assert_eq!(responses[0].row, 1);
+    }
+
+    #[test]
+    fn no_weighted_column_gives_no_response() {
+        let matrix = matrix();
+        let objective = Objective::new(ObjectiveKind::TotalWin, 0.0);
+        assert!(best_responses(&matrix, &candidates(), &OpponentWeighting::Weights(vec![0.0; 3]), Aggregate::Expected, &objective, 5).is_empty());
+    }
+}
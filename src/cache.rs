@@ -0,0 +1,318 @@
+use crate::config;
+use crate::error::DataError;
+use crate::fixtures;
+use chrono::{DateTime, Datelike, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Endpoint {
+    PlayerSearch,
+    Diary,
+    Ranking,
+    Goratings,
+    Other,
+}
+
+impl Endpoint {
+    pub fn from_url(url: &str) -> Endpoint {
+        if url.contains("search_pro.asp") {
+            Endpoint::PlayerSearch
+        } else if url.contains("diary_in.asp") {
+            Endpoint::Diary
+        } else if url.contains("rankingPlayer_in.asp") {
+            Endpoint::Ranking
+        } else if url.contains("goratings") {
+            Endpoint::Goratings
+        } else {
+            Endpoint::Other
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Endpoint::PlayerSearch => "search_pro.asp",
+            Endpoint::Diary => "diary_in.asp",
+            Endpoint::Ranking => "rankingPlayer_in.asp",
+            Endpoint::Goratings => "goratings.org",
+            Endpoint::Other => "기타",
+        }
+    }
+
+    fn all() -> [Endpoint; 5] {
+        [Endpoint::PlayerSearch, Endpoint::Diary, Endpoint::Ranking, Endpoint::Goratings, Endpoint::Other]
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    method: String,
+    url: String,
+    form: Vec<(String, String)>,
+    fetched_at: i64,
+    expires_at: Option<i64>,
+    body: String,
+}
+
+impl CacheEntry {
+    fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_none_or(|expires_at| now.timestamp() < expires_at)
+    }
+
+    // 파일명은 해시이므로 저장된 요청이 실제로 같은 요청인지 확인합니다.
+    fn matches(&self, method: &str, url: &str, form: &[(&str, &str)]) -> bool {
+        let mut stored_form: Vec<(&str, &str)> = self.form.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect();
+        let mut requested_form = form.to_vec();
+        stored_form.sort();
+        requested_form.sort();
+        self.method == method && self.url == url && stored_form == requested_form
+    }
+}
+
+pub fn cache_key(method: &str, url: &str, form: &[(&str, &str)]) -> String {
+    let mut sorted_form: Vec<(&str, &str)> = form.to_vec();
+    sorted_form.sort();
+    let mut key = format!("{} {}", method, url);
+    for (name, value) in sorted_form {
+        key.push_str(&format!("&{}={}", name, value));
+    }
+
+    // FNV-1a: 실행마다 같은 파일명을 얻기 위해 고정된 해시를 사용합니다.
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in key.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+fn entry_path(key: &str) -> PathBuf {
+    Path::new(&config::get().cache_dir).join(format!("{}.json", key))
+}
+
+fn expires_at(endpoint: Endpoint, form: &[(&str, &str)], now: DateTime<Utc>) -> Option<i64> {
+    let ttl = config::get().cache_ttl;
+    let hours = match endpoint {
+        Endpoint::PlayerSearch => ttl.search_hours,
+        Endpoint::Diary => ttl.diary_hours,
+        Endpoint::Goratings => ttl.goratings_hours,
+        Endpoint::Other => ttl.other_hours,
+        Endpoint::Ranking => {
+            let year = form.iter().find(|(name, _)| *name == "keyColumn").and_then(|(_, value)| value.parse::<i32>().ok());
+            let month = form.iter().find(|(name, _)| *name == "keyWord").and_then(|(_, value)| value.parse::<u32>().ok());
+            match (year, month) {
+                // 지난 달의 랭킹은 공표 이후 바뀌지 않습니다.
+                (Some(year), Some(month)) if (year, month) < (now.year(), now.month()) => return None,
+                _ => ttl.current_ranking_hours,
+            }
+        }
+    };
+    Some((now + Duration::try_hours(hours as i64).unwrap_or(Duration::zero())).timestamp())
+}
+
+fn read_entry(path: &Path) -> Option<CacheEntry> {
+    let text = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+// 재현 모드에서는 기록된 응답만 사용하도록 캐시를 거치지 않습니다.
+pub fn lookup(method: &str, url: &str, form: &[(&str, &str)]) -> Option<String> {
+    let config = config::get();
+    if !config.cache_enabled || config.fixture_replay {
+        return None;
+    }
+    let entry = read_entry(&entry_path(&cache_key(method, url, form)))?;
+    if entry.matches(method, url, form) && entry.is_fresh(Utc::now()) {
+        Some(entry.body)
+    } else {
+        None
+    }
+}
+
+pub fn store(method: &str, url: &str, form: &[(&str, &str)], body: &str) -> Result<(), DataError> {
+    let config = config::get();
+    if !config.cache_enabled || config.fixture_replay {
+        return Ok(());
+    }
+    let now = Utc::now();
+    let entry = CacheEntry {
+        method: method.to_string(),
+        url: url.to_string(),
+        form: form.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+        fetched_at: now.timestamp(),
+        expires_at: expires_at(Endpoint::from_url(url), form, now),
+        body: body.to_string(),
+    };
+    fs::create_dir_all(&config::get().cache_dir)?;
+    fs::write(entry_path(&cache_key(method, url, form)), serde_json::to_string(&entry)?)?;
+    Ok(())
+}
+
+// 응답을 캐시에 저장하고(fetched가 true일 때) 픽스처로 기록합니다. 저장은 받아 온 응답을 다시 쓰기 위한 것이므로,
+// 실패해도 응답은 그대로 쓰고 경고할 문구만 돌려줍니다.
+pub fn save_response(method: &str, url: &str, form: &[(&str, &str)], body: &str, fetched: bool) -> Option<String> {
+    let stored = if fetched { store(method, url, form, body) } else { Ok(()) };
+    let errors: Vec<String> = [stored, fixtures::record(method, url, form, body)].into_iter().filter_map(Result::err).map(|e| e.to_string()).collect();
+    (!errors.is_empty()).then(|| format!("경고: {}의 응답을 저장하지 못했습니다: {}", url, errors.join(", ")))
+}
+
+fn fetch(method: &str, url: &str, form: &[(&str, &str)]) -> Result<String, DataError> {
+    if let Some(body) = lookup(method, url, form) {
+        if let Some(warning) = save_response(method, url, form, &body, false) {
+            println!("{}", warning);
+        }
+        return Ok(body);
+    }
+
+    let client = reqwest::blocking::Client::new();
+    let request = if method == "POST" {
+        client.post(url).form(form)
+    } else {
+        client.get(url)
+    };
+    let body = request
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .map_err(|e| DataError::Network { url: url.to_string(), message: e.to_string() })?;
+
+    if let Some(warning) = save_response(method, url, form, &body, true) {
+        println!("{}", warning);
+    }
+    Ok(body)
+}
+
+pub fn get_text(url: &str) -> Result<String, DataError> {
+    fetch("GET", url, &[])
+}
+
+pub fn post_form_text(url: &str, form: &[(&str, &str)]) -> Result<String, DataError> {
+    fetch("POST", url, form)
+}
+
+struct EndpointStats {
+    entries: usize,
+    expired: usize,
+    bytes: u64,
+}
+
+fn cache_files() -> Vec<PathBuf> {
+    match fs::read_dir(&config::get().cache_dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn collect_stats() -> BTreeMap<Endpoint, EndpointStats> {
+    let now = Utc::now();
+    let mut stats: BTreeMap<Endpoint, EndpointStats> = BTreeMap::new();
+    for path in cache_files() {
+        let bytes = fs::metadata(&path).map(|metadata| metadata.len()).unwrap_or(0);
+        if let Some(entry) = read_entry(&path) {
+            let endpoint_stats = stats.entry(Endpoint::from_url(&entry.url)).or_insert(EndpointStats { entries: 0, expired: 0, bytes: 0 });
+            endpoint_stats.entries += 1;
+            endpoint_stats.bytes += bytes;
+            if !entry.is_fresh(now) {
+                endpoint_stats.expired += 1;
+            }
+        }
+    }
+    stats
+}
+
+pub fn purge<F: Fn(&CacheEntryInfo) -> bool>(predicate: F) -> usize {
+    let now = Utc::now();
+    let mut removed = 0;
+    for path in cache_files() {
+        let remove = match read_entry(&path) {
+            Some(entry) => predicate(&CacheEntryInfo {
+                endpoint: Endpoint::from_url(&entry.url),
+                expired: !entry.is_fresh(now),
+            }),
+            None => true,
+        };
+        if remove && fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+pub struct CacheEntryInfo {
+    pub endpoint: Endpoint,
+    pub expired: bool,
+}
+
+pub fn print_cache_stats() {
+    let stats = collect_stats();
+    println!("\n캐시 위치: {}", config::get().cache_dir);
+    if stats.is_empty() {
+        println!("저장된 캐시가 없습니다.");
+        return;
+    }
+    let mut total_entries = 0;
+    let mut total_bytes = 0;
+    for (endpoint, endpoint_stats) in &stats {
+        println!("{}: {}개 (만료 {}개), {:.1} KB", endpoint.name(), endpoint_stats.entries, endpoint_stats.expired, endpoint_stats.bytes as f64 / 1024.0);
+        total_entries += endpoint_stats.entries;
+        total_bytes += endpoint_stats.bytes;
+    }
+    println!("합계: {}개, {:.1} KB", total_entries, total_bytes as f64 / 1024.0);
+}
+
+pub fn execute_cache_menu() {
+    loop {
+        println!("\n선택할 옵션:");
+        println!("1. 캐시 현황");
+        println!("2. 만료된 캐시 삭제");
+        println!("3. 엔드포인트별 캐시 삭제");
+        println!("4. 전체 캐시 삭제");
+        println!("5. 캐시 사용 {}", if config::get().cache_enabled { "끄기" } else { "켜기" });
+        println!("exit. 돌아가기");
+
+        let mut option = String::new();
+        io::stdin().read_line(&mut option).expect("입력을 읽는 데 실패했습니다.");
+
+        match option.trim() {
+            "1" => print_cache_stats(),
+            "2" => {
+                let removed = purge(|entry| entry.expired);
+                println!("만료된 캐시 {}개를 삭제했습니다.", removed);
+            },
+            "3" => {
+                let endpoints = Endpoint::all();
+                for (index, endpoint) in endpoints.iter().enumerate() {
+                    println!("{}. {}", index + 1, endpoint.name());
+                }
+                let mut input = String::new();
+                io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
+                match input.trim().parse::<usize>() {
+                    Ok(num) if num > 0 && num <= endpoints.len() => {
+                        let selected_endpoint = endpoints[num - 1];
+                        let removed = purge(|entry| entry.endpoint == selected_endpoint);
+                        println!("{} 캐시 {}개를 삭제했습니다.", selected_endpoint.name(), removed);
+                    },
+                    _ => println!("잘못된 입력입니다. 다시 입력해주세요."),
+                }
+            },
+            "4" => {
+                let removed = purge(|_| true);
+                println!("캐시 {}개를 삭제했습니다.", removed);
+            },
+            "5" => {
+                if let Err(e) = config::update(|config| config.cache_enabled = !config.cache_enabled) {
+                    println!("설정을 저장하는 동안 오류가 발생했습니다: {}", e);
+                }
+            },
+            "exit" => break,
+            _ => println!("잘못된 입력입니다. 다시 입력해주세요."),
+        }
+    }
+}
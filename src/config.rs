@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::sync::{OnceLock, RwLock};
+
+const CONFIG_PATH: &str = "jgsm_config.json";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub season_file: String,
+    pub cache_enabled: bool,
+    pub cache_dir: String,
+    pub cache_ttl: CacheTtl,
+    pub records_db: String,
+    pub records_history_days: u64,
+    pub records_sync_hours: u64,
+    pub fetch_concurrency: usize,
+    pub fetch_delay_ms: u64,
+    pub fetch_retries: u32,
+    pub fetch_backoff_ms: u64,
+    pub registry_file: String,
+    pub baduk_base_url: String,
+    pub goratings_base_url: String,
+    pub yikeweiqi_live_url: String,
+    pub webdriver_url: String,
+    pub fixture_record: bool,
+    pub fixture_replay: bool,
+    pub fixture_dir: String,
+    pub fixture_server_port: u16,
+    pub rating_source_order: Vec<String>,
+    pub manual_ratings_file: String,
+    pub event_catalogue_file: String,
+    pub head_to_head_enabled: bool,
+    pub head_to_head_prior_games: f64,
+    pub backtest_dir: String,
+    pub model_params_file: String,
+    pub form_window_weeks: i64,
+    pub projection_enabled: bool,
+    pub projection_history_months: u32,
+    pub projection_horizon_months: u32,
+    pub team_league_weight_enabled: bool,
+    pub rating_conversions_file: String,
+    pub rating_lists_dir: String,
+    pub lineup_objective: String,
+    pub lineup_risk: f64,
+    pub league_points: LeaguePoints,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheTtl {
+    pub search_hours: u64,
+    pub diary_hours: u64,
+    pub current_ranking_hours: u64,
+    pub goratings_hours: u64,
+    pub other_hours: u64,
+}
+
+// 승점 기준으로 라인업을 고를 때 팀 승리, 개인 승 1판, 완봉승에 주는 점수입니다.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LeaguePoints {
+    pub win: f64,
+    pub board_win: f64,
+    pub shutout: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            season_file: "seasons/2023-2024.json".to_string(),
+            cache_enabled: true,
+            cache_dir: "cache".to_string(),
+            cache_ttl: CacheTtl::default(),
+            records_db: "jgsm.db".to_string(),
+            records_history_days: 1095,
+            records_sync_hours: 6,
+            fetch_concurrency: 8,
+            fetch_delay_ms: 100,
+            fetch_retries: 3,
+            fetch_backoff_ms: 500,
+            registry_file: "player_registry.json".to_string(),
+            baduk_base_url: "http://baduk.or.kr".to_string(),
+            goratings_base_url: "https://www.goratings.org".to_string(),
+            yikeweiqi_live_url: "https://home.yikeweiqi.com/#/live".to_string(),
+            webdriver_url: "http://127.0.0.1:4444".to_string(),
+            fixture_record: false,
+            fixture_replay: false,
+            fixture_dir: "fixtures".to_string(),
+            fixture_server_port: 8765,
+            rating_source_order: vec!["baeteil".to_string(), "goratings".to_string(), "manual".to_string()],
+            manual_ratings_file: "manual_ratings.csv".to_string(),
+            event_catalogue_file: "events.json".to_string(),
+            head_to_head_enabled: false,
+            head_to_head_prior_games: 10.0,
+            backtest_dir: "backtests".to_string(),
+            model_params_file: "model_params.json".to_string(),
+            form_window_weeks: 8,
+            projection_enabled: false,
+            projection_history_months: 24,
+            projection_horizon_months: 6,
+            team_league_weight_enabled: false,
+            rating_conversions_file: "rating_conversions.json".to_string(),
+            rating_lists_dir: "rating_lists".to_string(),
+            lineup_objective: "total_win".to_string(),
+            lineup_risk: 0.0,
+            league_points: LeaguePoints::default(),
+        }
+    }
+}
+
+impl Default for CacheTtl {
+    fn default() -> Self {
+        CacheTtl {
+            search_hours: 24 * 7,
+            diary_hours: 6,
+            current_ranking_hours: 12,
+            goratings_hours: 24,
+            other_hours: 6,
+        }
+    }
+}
+
+impl Default for LeaguePoints {
+    fn default() -> Self {
+        LeaguePoints {
+            win: 1.0,
+            board_win: 0.1,
+            shutout: 0.0,
+        }
+    }
+}
+
+struct ConfigState {
+    config: Config,
+    // 설정 파일을 해석하지 못했을 때의 오류입니다. 이 경우 사용자의 파일을 기본 설정으로 덮어쓰지 않습니다.
+    load_error: Option<String>,
+}
+
+fn load() -> ConfigState {
+    let text = match fs::read_to_string(CONFIG_PATH) {
+        Ok(text) => text,
+        Err(_) => return ConfigState { config: Config::default(), load_error: None },
+    };
+    match serde_json::from_str::<Config>(&text) {
+        Ok(config) => ConfigState { config, load_error: None },
+        Err(e) => {
+            println!("{} 파일을 해석하지 못해 기본 설정을 사용합니다: {}", CONFIG_PATH, e);
+            println!("파일을 고치기 전까지는 설정을 변경해도 저장하지 않습니다.");
+            ConfigState { config: Config::default(), load_error: Some(e.to_string()) }
+        }
+    }
+}
+
+fn config_lock() -> &'static RwLock<ConfigState> {
+    static CONFIG: OnceLock<RwLock<ConfigState>> = OnceLock::new();
+    CONFIG.get_or_init(|| RwLock::new(load()))
+}
+
+pub fn get() -> Config {
+    config_lock().read().unwrap().config.clone()
+}
+
+// 설정 파일에 저장하지 않고 이번 실행에서만 사용할 설정으로 바꿉니다. 테스트가 임시 디렉터리와 재현 서버를 가리킬 때 사용합니다.
+#[cfg(test)]
+pub fn set(config: Config) {
+    config_lock().write().unwrap().config = config;
+}
+
+pub fn update<F: FnOnce(&mut Config)>(f: F) -> Result<(), Box<dyn Error>> {
+    let mut state = config_lock().write().unwrap();
+    if let Some(e) = &state.load_error {
+        return Err(format!("{} 파일을 해석하지 못했으므로 덮어쓰지 않습니다: {}", CONFIG_PATH, e).into());
+    }
+    let mut config = state.config.clone();
+    f(&mut config);
+    fs::write(CONFIG_PATH, serde_json::to_string_pretty(&config)?)?;
+    state.config = config;
+    Ok(())
+}
@@ -0,0 +1,224 @@
+use crate::models::Team;
+use std::io;
+
+// 라인업 조건입니다. 판 번호는 0부터 시작합니다.
+#[derive(Clone)]
+pub enum LineupConstraint {
+    FixedBoard { player: String, board: usize },
+    NotOnBoard { player: String, board: usize },
+    OneOfOnBoard { players: Vec<String>, board: usize },
+    MustPlay(String),
+    MustNotPlay(String),
+    // 흑번인 판에만 나옵니다. 흑백을 모르면 나오지 않는 것으로 봅니다.
+    BlackOnly(String),
+}
+
+impl LineupConstraint {
+    // first_board_black은 해당 팀의 1국이 흑번인지이며, 판마다 흑백이 바뀝니다.
+    fn allows(&self, boards: &[&str], first_board_black: Option<bool>) -> bool {
+        let position = |player: &str| boards.iter().position(|name| *name == player);
+        match self {
+            LineupConstraint::FixedBoard { player, board } => boards.get(*board) == Some(&player.as_str()),
+            LineupConstraint::NotOnBoard { player, board } => boards.get(*board) != Some(&player.as_str()),
+            LineupConstraint::OneOfOnBoard { players, board } => boards.get(*board).is_some_and(|name| players.iter().any(|player| player == name)),
+            LineupConstraint::MustPlay(player) => position(player).is_some(),
+            LineupConstraint::MustNotPlay(player) => position(player).is_none(),
+            LineupConstraint::BlackOnly(player) => match position(player) {
+                Some(board) => first_board_black.is_some_and(|black| (board % 2 == 0) == black),
+                None => true,
+            },
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            LineupConstraint::FixedBoard { player, board } => format!("{} {}국 고정", player, board + 1),
+            LineupConstraint::NotOnBoard { player, board } => format!("{} {}국 제외", player, board + 1),
+            LineupConstraint::OneOfOnBoard { players, board } => format!("{}국은 {} 중 한 명", board + 1, players.join(", ")),
+            LineupConstraint::MustPlay(player) => format!("{} 출전", player),
+            LineupConstraint::MustNotPlay(player) => format!("{} 불참", player),
+            LineupConstraint::BlackOnly(player) => format!("{} 흑번일 때만 출전", player),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct LineupConstraints {
+    constraints: Vec<LineupConstraint>,
+}
+
+impl LineupConstraints {
+    pub fn is_empty(&self) -> bool {
+        self.constraints.is_empty()
+    }
+
+    pub fn allows(&self, boards: &[&str], first_board_black: Option<bool>) -> bool {
+        self.constraints.iter().all(|constraint| constraint.allows(boards, first_board_black))
+    }
+
+    // 해당 판에 고정한 기사입니다.
+    pub fn fixed_player(&self, board: usize) -> Option<&str> {
+        self.constraints.iter().find_map(|constraint| match constraint {
+            LineupConstraint::FixedBoard { player, board: fixed } if *fixed == board => Some(player.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn describe(&self) -> Vec<String> {
+        self.constraints.iter().map(|constraint| constraint.describe()).collect()
+    }
+
+    // 조건을 만족하는 라인업이 없으면 그 이유를 보여주고 false를 돌려줍니다.
+    pub fn report(&self, team_name: &str, admitted: usize, first_board_black: Option<bool>) -> bool {
+        if admitted > 0 {
+            return true;
+        }
+        println!("\n{} 팀의 조건을 모두 만족하는 라인업이 없습니다:", team_name);
+        for line in self.describe() {
+            println!("- {}", line);
+        }
+        if first_board_black.is_none() && self.constraints.iter().any(|constraint| matches!(constraint, LineupConstraint::BlackOnly(_))) {
+            println!("흑백을 모르는 경우에는 흑번일 때만 출전하는 기사가 나오지 않는 것으로 봅니다.");
+        }
+        false
+    }
+}
+
+fn parse_player(team: &Team, field: &str) -> Option<String> {
+    match field.parse::<usize>() {
+        Ok(num) if num > 0 && num <= team.players().len() => Some(team.players()[num - 1].korean_name().clone()),
+        _ => None,
+    }
+}
+
+fn parse_board(field: Option<&&str>, boards: usize) -> Option<usize> {
+    match field?.parse::<usize>() {
+        Ok(num) if num > 0 && num <= boards => Some(num - 1),
+        _ => None,
+    }
+}
+
+fn parse_constraint(team: &Team, boards: usize, input: &str) -> Option<LineupConstraint> {
+    let fields: Vec<&str> = input.split_whitespace().collect();
+    let (&keyword, rest) = fields.split_first()?;
+    let player = || rest.first().and_then(|field| parse_player(team, field));
+    let constraint = match keyword {
+        "고정" => LineupConstraint::FixedBoard { player: player()?, board: parse_board(rest.get(1), boards)? },
+        "제외" => LineupConstraint::NotOnBoard { player: player()?, board: parse_board(rest.get(1), boards)? },
+        "후보" => {
+            let players = rest.first()?.split(',').map(|field| parse_player(team, field.trim())).collect::<Option<Vec<String>>>()?;
+            LineupConstraint::OneOfOnBoard { players, board: parse_board(rest.get(1), boards)? }
+        },
+        "출전" => LineupConstraint::MustPlay(player()?),
+        "불참" => LineupConstraint::MustNotPlay(player()?),
+        "흑번" => LineupConstraint::BlackOnly(player()?),
+        _ => return None,
+    };
+    Some(constraint)
+}
+
+// 입력 없이 여러 줄의 조건을 한 번에 읽습니다. 읽지 못한 줄이 있으면 테스트가 실패합니다.
+#[cfg(test)]
+pub fn parse(team: &Team, boards: usize, lines: &[&str]) -> LineupConstraints {
+    LineupConstraints { constraints: lines.iter().map(|line| parse_constraint(team, boards, line).unwrap()).collect() }
+}
+
+// 팀의 라인업 조건을 한 줄에 하나씩 입력받습니다. boards는 한 경기의 판 수입니다.
+pub fn read_constraints(team: &Team, boards: usize) -> LineupConstraints {
+    println!("\n{} 팀의 스쿼드:", team.team_name());
+    for (index, player) in team.players().iter().enumerate() {
+        println!("{}. {} (elo: {:.2}, 전망: {:+.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", index + 1, player.korean_name(), player.elo_rating(), player.projection().change(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight());
+    }
+    println!("{} 팀의 라인업 조건을 한 줄에 하나씩 입력하세요. 기사는 번호로 입력합니다 (조건이 없거나 끝내려면 엔터):", team.team_name());
+    println!("고정 기사 판 (예: 고정 3 1 → 3번 기사를 1국에 고정)");
+    println!("제외 기사 판 (예: 제외 3 1 → 3번 기사는 1국에 나오지 않음)");
+    println!("후보 기사,기사 판 (예: 후보 2,5 4 → 4국에는 2번과 5번 중 한 명)");
+    println!("출전 기사 / 불참 기사 (예: 불참 6 → 6번 기사는 나오지 않음)");
+    println!("흑번 기사 (예: 흑번 4 → 4번 기사는 흑번인 판에만 출전)");
+
+    let mut constraints = LineupConstraints::default();
+    loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
+        let input = input.trim();
+        if input.is_empty() {
+            break;
+        }
+        match parse_constraint(team, boards, input) {
+            Some(constraint) => {
+                println!("추가했습니다: {}", constraint.describe());
+                constraints.constraints.push(constraint);
+            },
+            None => println!("잘못된 입력입니다. 다시 입력해주세요."),
+        }
+    }
+    constraints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Player;
+
+    fn team() -> Team {
+        Team::new("테스트".to_string(), ["갑", "을", "병", "정", "무", "기"].iter().map(|name| Player::named(name)).collect())
+    }
+
+    fn constraints(lines: &[&str]) -> LineupConstraints {
+        parse(&team(), 4, lines)
+    }
+
+    #[test]
+    fn parses_every_keyword_with_one_based_numbers() {
+        let team = team();
+        assert!(matches!(parse_constraint(&team, 4, "고정 3 1"), Some(LineupConstraint::FixedBoard { player, board: 0 }) if player == "병"));
+        assert!(matches!(parse_constraint(&team, 4, "제외 1 4"), Some(LineupConstraint::NotOnBoard { player, board: 3 }) if player == "갑"));
+        assert!(parse_constraint(&team, 4, "후보 2, 5 4").is_none());
+        assert!(matches!(parse_constraint(&team, 4, "후보 2,5 4"), Some(LineupConstraint::OneOfOnBoard { players, board: 3 }) if players == ["을", "무"]));
+        assert!(matches!(parse_constraint(&team, 4, "출전 6"), Some(LineupConstraint::MustPlay(player)) if player == "기"));
+        assert!(matches!(parse_constraint(&team, 4, "불참 6"), Some(LineupConstraint::MustNotPlay(player)) if player == "기"));
+        assert!(matches!(parse_constraint(&team, 4, "흑번 4"), Some(LineupConstraint::BlackOnly(player)) if player == "정"));
+    }
+
+    #[test]
+    fn rejects_unknown_keywords_and_out_of_range_numbers() {
+        let team = team();
+        for input in ["", "지정 1 1", "고정 7 1", "고정 0 1", "고정 1 5", "고정 1", "후보 1,9 2", "출전"] {
+            assert!(parse_constraint(&team, 4, input).is_none(), "{}", input);
+        }
+        assert!(parse_constraint(&team, 5, "고정 1 5").is_some());
+    }
+
+    #[test]
+    fn board_constraints_check_the_named_board() {
+        let fixed = constraints(&["고정 3 1"]);
+        assert!(fixed.allows(&["병", "갑", "을", "정"], None));
+        assert!(!fixed.allows(&["갑", "병", "을", "정"], None));
+        assert_eq!(fixed.fixed_player(0), Some("병"));
+        assert_eq!(fixed.fixed_player(1), None);
+
+        let one_of = constraints(&["후보 2,5 4", "제외 1 1"]);
+        assert!(one_of.allows(&["병", "갑", "정", "무"], None));
+        assert!(!one_of.allows(&["병", "갑", "정", "기"], None));
+        assert!(!one_of.allows(&["갑", "병", "정", "을"], None));
+    }
+
+    #[test]
+    fn player_constraints_check_the_whole_lineup() {
+        let constraints = constraints(&["출전 6", "불참 1"]);
+        assert!(constraints.allows(&["을", "병", "정", "기"], None));
+        assert!(!constraints.allows(&["을", "병", "정", "무"], None));
+        assert!(!constraints.allows(&["갑", "병", "정", "기"], None));
+    }
+
+    #[test]
+    fn black_only_follows_alternating_colours() {
+        let constraints = constraints(&["흑번 4"]);
+        // 1국이 흑번이면 1국과 3국이 흑번입니다.
+        assert!(constraints.allows(&["갑", "을", "정", "병"], Some(true)));
+        assert!(!constraints.allows(&["갑", "정", "을", "병"], Some(true)));
+        assert!(constraints.allows(&["갑", "정", "을", "병"], Some(false)));
+        assert!(!constraints.allows(&["갑", "정", "을", "병"], None));
+        assert!(constraints.allows(&["갑", "을", "병", "무"], None));
+    }
+}
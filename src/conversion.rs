@@ -0,0 +1,341 @@
+use crate::config;
+use crate::rating_archive;
+use crate::registry::Registry;
+use crate::season;
+use crate::utils::baeteil_to_goratings;
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+// 곡선을 맞추려면 두 목록에 모두 있는 기사가 최소 이만큼 있어야 합니다.
+const MIN_PAIRS: usize = 10;
+// 곡선의 꺾이는 점 하나에 들어가는 기사 수와 꺾이는 점의 최대 개수입니다.
+const PAIRS_PER_KNOT: usize = 8;
+const MAX_KNOTS: usize = 8;
+
+// 다른 척도의 레이팅을 goratings로 옮기는 꺾은선입니다. 점은 두 값 모두 커지는 순서이며, 양 끝 밖은 끝 구간의 기울기로 늘립니다.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ScaleCurve {
+    pub fitted_at: String,
+    pub pairs: usize,
+    pub mean_abs_error: f64,
+    pub knots: Vec<(f64, f64)>,
+}
+
+fn interpolate(points: &[(f64, f64)], x: f64) -> f64 {
+    let segment = points.windows(2).position(|window| x <= window[1].0).unwrap_or(points.len() - 2);
+    let ((x0, y0), (x1, y1)) = (points[segment], points[segment + 1]);
+    if x1 == x0 {
+        return y0;
+    }
+    y0 + (x - x0) * (y1 - y0) / (x1 - x0)
+}
+
+impl ScaleCurve {
+    pub fn to_goratings(&self, rating: f64) -> f64 {
+        interpolate(&self.knots, rating)
+    }
+
+    pub fn to_scale(&self, goratings_rating: f64) -> f64 {
+        let inverse: Vec<(f64, f64)> = self.knots.iter().map(|(x, y)| (*y, *x)).collect();
+        interpolate(&inverse, goratings_rating)
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Conversions {
+    curves: BTreeMap<String, ScaleCurve>,
+}
+
+fn load() -> Conversions {
+    let path = config::get().rating_conversions_file;
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(_) => return Conversions::default(),
+    };
+    serde_json::from_str(&text).unwrap_or_else(|e| {
+        println!("경고: 레이팅 환산 파일 {}의 형식이 올바르지 않아 기본 환산식을 사용합니다: {}", path, e);
+        Conversions::default()
+    })
+}
+
+fn conversions_lock() -> &'static RwLock<Conversions> {
+    static CONVERSIONS: OnceLock<RwLock<Conversions>> = OnceLock::new();
+    CONVERSIONS.get_or_init(|| RwLock::new(load()))
+}
+
+pub fn scale_label(scale: &str) -> &str {
+    match scale {
+        "baeteil" => "배태일",
+        "goratings" => "goratings",
+        "china" => "중국기원",
+        "japan" => "일본기원",
+        "taiwan" => "대만기원",
+        _ => scale,
+    }
+}
+
+// 맞춘 곡선이 없으면 None을 돌려줍니다.
+pub fn to_goratings(scale: &str, rating: f64) -> Option<f64> {
+    conversions_lock().read().unwrap().curves.get(scale).map(|curve| curve.to_goratings(rating))
+}
+
+pub fn from_goratings(scale: &str, rating: f64) -> Option<f64> {
+    conversions_lock().read().unwrap().curves.get(scale).map(|curve| curve.to_scale(rating))
+}
+
+// 배태일은 맞춘 곡선이 없어도 모델 상수의 환산식을 쓸 수 있고, 다른 척도는 맞춘 곡선이 있어야 합니다.
+pub fn scale_to_goratings(scale: &str, rating: f64) -> Result<f64, String> {
+    match scale {
+        "goratings" => Ok(rating),
+        "baeteil" => Ok(baeteil_to_goratings(rating)),
+        _ => to_goratings(scale, rating).ok_or_else(|| format!("{} 레이팅의 환산 곡선이 없습니다. '레이팅 환산 곡선 맞추기'를 먼저 실행해주세요.", scale_label(scale))),
+    }
+}
+
+// 기사 수가 비슷한 구간으로 나누어 구간마다 평균점을 두고, 환산값이 거꾸로 가는 이웃 구간은 합칩니다(pool adjacent violators).
+fn fit_knots(mut pairs: Vec<(f64, f64)>) -> Option<Vec<(f64, f64)>> {
+    if pairs.len() < MIN_PAIRS {
+        return None;
+    }
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let knot_count = (pairs.len() / PAIRS_PER_KNOT).clamp(2, MAX_KNOTS);
+
+    let mut pooled: Vec<(f64, f64, f64)> = Vec::new();
+    for index in 0..knot_count {
+        let chunk = &pairs[index * pairs.len() / knot_count..(index + 1) * pairs.len() / knot_count];
+        let count = chunk.len() as f64;
+        let mut block = (chunk.iter().map(|pair| pair.0).sum::<f64>() / count, chunk.iter().map(|pair| pair.1).sum::<f64>() / count, count);
+        while let Some(&last) = pooled.last() {
+            if last.1 < block.1 {
+                break;
+            }
+            pooled.pop();
+            let count = last.2 + block.2;
+            block = ((last.0 * last.2 + block.0 * block.2) / count, (last.1 * last.2 + block.1 * block.2) / count, count);
+        }
+        pooled.push(block);
+    }
+
+    if pooled.len() < 2 {
+        return None;
+    }
+    Some(pooled.into_iter().map(|(x, y, _)| (x, y)).collect())
+}
+
+fn mean_abs_error(pairs: &[(f64, f64)], convert: impl Fn(f64) -> f64) -> f64 {
+    pairs.iter().map(|(x, y)| (convert(*x) - y).abs()).sum::<f64>() / pairs.len() as f64
+}
+
+// 모든 시즌 파일과 기사 식별 정보에서 한글 이름별 goratings 영문 이름을 모읍니다.
+fn english_names() -> HashMap<String, Vec<String>> {
+    let registry = Registry::load();
+    let mut names: HashMap<String, Vec<String>> = HashMap::new();
+    for season in season::list_season_files().iter().filter_map(|path| season::load_season(path).ok()) {
+        for player in season.teams().iter().flat_map(|team| team.players()) {
+            let english_names = names.entry(player.korean_name().clone()).or_default();
+            english_names.push(player.english_name().clone());
+            if let Some(entry) = registry.get(player.korean_name()) {
+                english_names.extend(entry.english_aliases.iter().cloned());
+            }
+        }
+    }
+    names
+}
+
+// 보관된 배태일 랭킹과 같은 달의 goratings 스냅샷에 모두 있는 기사의 (배태일, goratings) 쌍입니다.
+fn baeteil_pairs() -> Result<Vec<(f64, f64)>, Box<dyn Error>> {
+    let english_names = english_names();
+    let mut pairs = Vec::new();
+    for (year, month) in rating_archive::archived_baeteil_months()? {
+        let (baeteil, goratings) = match (rating_archive::archived_baeteil_list(year, month)?, rating_archive::goratings_snapshot_in_month(year, month)?) {
+            (Some(baeteil), Some(goratings)) => (baeteil, goratings),
+            _ => continue,
+        };
+        for (korean_name, names) in &english_names {
+            if let (Some(baeteil_rating), Some(goratings_rating)) = (baeteil.get(korean_name), names.iter().find_map(|name| goratings.get(name))) {
+                pairs.push((*baeteil_rating, *goratings_rating));
+            }
+        }
+    }
+    Ok(pairs)
+}
+
+// goratings 영문 이름, 레이팅, 기준일입니다.
+type ImportedRating = (String, f64, NaiveDate);
+
+// rating_lists 폴더의 {척도}.csv 파일을 읽습니다. 각 줄은 goratings 영문 이름, 레이팅, 기준일(YYYY-MM-DD)입니다.
+fn imported_lists() -> Result<BTreeMap<String, Vec<ImportedRating>>, Box<dyn Error>> {
+    let dir = config::get().rating_lists_dir;
+    let mut lists = BTreeMap::new();
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(lists),
+    };
+    for path in entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).filter(|path| path.extension().is_some_and(|extension| extension == "csv")) {
+        let scale = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let text = fs::read_to_string(&path)?;
+        let mut rows = Vec::new();
+        for (line_no, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with("name") {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').map(|field| field.trim()).collect();
+            if fields.len() < 3 {
+                return Err(format!("{} {}번째 줄의 형식이 올바르지 않습니다: {}", path.display(), line_no + 1, line).into());
+            }
+            let rating = fields[1].parse::<f64>().map_err(|_| format!("{} {}번째 줄의 레이팅이 올바르지 않습니다: {}", path.display(), line_no + 1, fields[1]))?;
+            let date = NaiveDate::parse_from_str(fields[2], "%Y-%m-%d").map_err(|_| format!("{} {}번째 줄의 날짜가 올바르지 않습니다: {}", path.display(), line_no + 1, fields[2]))?;
+            rows.push((fields[0].to_string(), rating, date));
+        }
+        lists.insert(scale, rows);
+    }
+    Ok(lists)
+}
+
+fn imported_pairs(rows: &[ImportedRating]) -> Result<Vec<(f64, f64)>, Box<dyn Error>> {
+    let mut snapshots: HashMap<(i32, u32), Option<HashMap<String, f64>>> = HashMap::new();
+    let mut pairs = Vec::new();
+    for (name, rating, date) in rows {
+        let key = (date.year(), date.month());
+        let snapshot = match snapshots.entry(key) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(rating_archive::goratings_snapshot_in_month(key.0, key.1)?),
+        };
+        if let Some(goratings_rating) = snapshot.as_ref().and_then(|snapshot| snapshot.get(name)) {
+            pairs.push((*rating, *goratings_rating));
+        }
+    }
+    Ok(pairs)
+}
+
+// 가져온 목록에서 기준일까지의 가장 최근 레이팅을 기사마다 goratings로 환산하여 돌려줍니다. 곡선이 없는 척도는 건너뜁니다.
+pub fn imported_ratings(as_of: NaiveDate) -> Result<HashMap<String, (f64, NaiveDate)>, Box<dyn Error>> {
+    let mut ratings: HashMap<String, (f64, NaiveDate)> = HashMap::new();
+    for (scale, rows) in imported_lists()? {
+        for (name, rating, date) in rows {
+            if date > as_of {
+                continue;
+            }
+            let converted = match to_goratings(&scale, rating) {
+                Some(converted) => converted,
+                None => continue,
+            };
+            if ratings.get(&name).is_none_or(|(_, latest)| date > *latest) {
+                ratings.insert(name, (converted, date));
+            }
+        }
+    }
+    Ok(ratings)
+}
+
+// 배태일과 가져온 목록마다 곡선을 맞추고, 지금 쓰는 환산과 오차를 비교한 뒤 확인을 받아 저장합니다.
+pub fn fit_conversions() -> Result<(), Box<dyn Error>> {
+    let mut candidates: Vec<(String, Vec<(f64, f64)>)> = vec![("baeteil".to_string(), baeteil_pairs()?)];
+    for (scale, rows) in imported_lists()? {
+        candidates.push((scale, imported_pairs(&rows)?));
+    }
+
+    let fitted_at = Utc::now().date_naive().to_string();
+    let mut conversions = conversions_lock().read().unwrap().clone();
+    let mut changed = false;
+    for (scale, pairs) in candidates {
+        let knots = match fit_knots(pairs.clone()) {
+            Some(knots) => knots,
+            None => {
+                println!("{}: 같은 달 goratings와 함께 있는 기사가 {}명뿐이라 곡선을 맞추지 않았습니다. (최소 {}명)", scale_label(&scale), pairs.len(), MIN_PAIRS);
+                continue;
+            }
+        };
+        let curve = ScaleCurve { fitted_at: fitted_at.clone(), pairs: pairs.len(), mean_abs_error: 0.0, knots };
+        let curve = ScaleCurve { mean_abs_error: mean_abs_error(&pairs, |rating| curve.to_goratings(rating)), ..curve };
+
+        let current_error = match scale.as_str() {
+            "baeteil" => Some(mean_abs_error(&pairs, baeteil_to_goratings)),
+            _ => conversions.curves.get(&scale).map(|current| mean_abs_error(&pairs, |rating| current.to_goratings(rating))),
+        };
+        println!(
+            "\n{}: {}쌍, 평균 오차 {:.1}{}",
+            scale_label(&scale),
+            curve.pairs,
+            curve.mean_abs_error,
+            current_error.map_or(String::new(), |error| format!(" (지금 쓰는 환산 {:.1})", error))
+        );
+        for (rating, goratings) in &curve.knots {
+            println!("    {:.0} → {:.0}", rating, goratings);
+        }
+        conversions.curves.insert(scale, curve);
+        changed = true;
+    }
+    if !changed {
+        return Ok(());
+    }
+
+    println!("\n이 곡선을 {}에 저장하시겠습니까? (y/n)", config::get().rating_conversions_file);
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
+    if input.trim() == "y" {
+        let path = config::get().rating_conversions_file;
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(&conversions)?)?;
+        *conversions_lock().write().unwrap() = conversions;
+        println!("레이팅 환산 곡선을 저장했습니다.");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(knots: Vec<(f64, f64)>) -> ScaleCurve {
+        ScaleCurve { fitted_at: String::new(), pairs: 0, mean_abs_error: 0.0, knots }
+    }
+
+    #[test]
+    fn too_few_pairs_are_not_fitted() {
+        assert!(fit_knots((0..MIN_PAIRS - 1).map(|index| (index as f64, index as f64)).collect()).is_none());
+    }
+
+    #[test]
+    fn increasing_pairs_keep_one_knot_per_chunk() {
+        let pairs: Vec<(f64, f64)> = (0..32).map(|index| (index as f64, 2.0 * index as f64)).collect();
+        let knots = fit_knots(pairs).unwrap();
+        assert_eq!(knots.len(), 32 / PAIRS_PER_KNOT);
+        assert_eq!(knots[0], (3.5, 7.0));
+        assert!(knots.windows(2).all(|window| window[0].0 < window[1].0 && window[0].1 < window[1].1));
+    }
+
+    #[test]
+    fn decreasing_chunks_are_pooled() {
+        // 가운데 구간의 환산값이 앞 구간보다 작으므로 두 구간을 합쳐 평균을 냅니다.
+        let mut pairs: Vec<(f64, f64)> = Vec::new();
+        for (index, y) in [10.0, 30.0, 20.0, 40.0].into_iter().enumerate() {
+            pairs.extend((0..PAIRS_PER_KNOT).map(|offset| ((index * PAIRS_PER_KNOT + offset) as f64, y)));
+        }
+        let knots = fit_knots(pairs).unwrap();
+        assert_eq!(knots.iter().map(|knot| knot.1).collect::<Vec<_>>(), vec![10.0, 25.0, 40.0]);
+        assert_eq!(knots[1].0, 15.5);
+    }
+
+    #[test]
+    fn curve_extends_end_segments_and_inverts() {
+        let curve = curve(vec![(1000.0, 2000.0), (2000.0, 2500.0), (3000.0, 3500.0)]);
+        assert_eq!(curve.to_goratings(1500.0), 2250.0);
+        assert_eq!(curve.to_goratings(500.0), 1750.0);
+        assert_eq!(curve.to_goratings(3500.0), 4000.0);
+        for rating in [500.0, 1200.0, 2600.0, 3500.0] {
+            assert!((curve.to_scale(curve.to_goratings(rating)) - rating).abs() < 1e-9);
+        }
+    }
+}
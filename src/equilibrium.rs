@@ -0,0 +1,161 @@
+use rayon::prelude::*;
+
+const EPSILON: f64 = 1e-9;
+// 이보다 작은 확률로 고르는 라인업은 균형에 쓰이지 않는 것으로 봅니다.
+const SUPPORT_THRESHOLD: f64 = 1e-6;
+
+// 영합 게임의 혼합전략 균형입니다. 행 선수는 값을 크게, 열 선수는 작게 하려고 하며, value는 균형에서 행 선수가 얻는 기댓값입니다.
+pub struct MixedEquilibrium {
+    pub row_strategy: Vec<f64>,
+    pub column_strategy: Vec<f64>,
+    pub value: f64,
+}
+
+fn pivot(tableau: &mut [Vec<f64>], pivot_row: usize, pivot_column: usize) {
+    let divisor = tableau[pivot_row][pivot_column];
+    for value in tableau[pivot_row].iter_mut() {
+        *value /= divisor;
+    }
+    let pivot_values = tableau[pivot_row].clone();
+    tableau.par_iter_mut().enumerate().filter(|(row, _)| *row != pivot_row).for_each(|(_, values)| {
+        let factor = values[pivot_column];
+        if factor.abs() > EPSILON {
+            for (value, pivot_value) in values.iter_mut().zip(&pivot_values) {
+                *value -= factor * pivot_value;
+            }
+        }
+    });
+}
+
+// 모든 값이 양수가 되도록 옮긴 뒤, 열 선수의 문제(max Σy, Ay ≤ 1, y ≥ 0)를 심플렉스법으로 풉니다.
+// 최적해의 합의 역수가 게임 값이고, 여유 변수의 쌍대 값이 행 선수의 전략이 됩니다.
+pub fn solve_zero_sum(payoff: &[Vec<f64>]) -> MixedEquilibrium {
+    let rows = payoff.len();
+    let columns = payoff.first().map_or(0, |row| row.len());
+    if rows == 0 || columns == 0 {
+        return MixedEquilibrium { row_strategy: vec![1.0; rows], column_strategy: vec![1.0; columns], value: 0.0 };
+    }
+    let shift = 1.0 - payoff.iter().flatten().copied().fold(f64::INFINITY, f64::min);
+
+    let width = columns + rows + 1;
+    let rhs = width - 1;
+    let mut tableau = vec![vec![0.0; width]; rows + 1];
+    for (row, values) in payoff.iter().enumerate() {
+        for (column, value) in values.iter().enumerate() {
+            tableau[row][column] = value + shift;
+        }
+        tableau[row][columns + row] = 1.0;
+        tableau[row][rhs] = 1.0;
+    }
+    tableau[rows][..columns].fill(-1.0);
+    let mut basis: Vec<usize> = (columns..columns + rows).collect();
+
+    // 보통은 가장 많이 개선되는 열을 고르고, 오래 걸리면 순환을 막기 위해 블랜드 규칙으로 바꿉니다.
+    let bland_after = 10 * (rows + columns);
+    for iteration in 0.. {
+        let objective = &tableau[rows];
+        let candidates = (0..rhs).filter(|&column| objective[column] < -EPSILON);
+        let entering = if iteration < bland_after {
+            candidates.min_by(|&a, &b| objective[a].total_cmp(&objective[b]))
+        } else {
+            candidates.min()
+        };
+        let entering = match entering {
+            Some(entering) => entering,
+            None => break,
+        };
+        let leaving = (0..rows)
+            .filter(|&row| tableau[row][entering] > EPSILON)
+            .min_by(|&a, &b| (tableau[a][rhs] / tableau[a][entering]).total_cmp(&(tableau[b][rhs] / tableau[b][entering])).then(basis[a].cmp(&basis[b])));
+        // 옮긴 값이 모두 양수이므로 목적함수는 항상 유계이고, 나가는 행이 없을 수 없습니다.
+        let leaving = match leaving {
+            Some(leaving) => leaving,
+            None => break,
+        };
+        pivot(&mut tableau, leaving, entering);
+        basis[leaving] = entering;
+    }
+
+    let mut column_strategy = vec![0.0; columns];
+    for (row, &variable) in basis.iter().enumerate() {
+        if variable < columns {
+            column_strategy[variable] = tableau[row][rhs];
+        }
+    }
+    let mut row_strategy: Vec<f64> = (0..rows).map(|row| tableau[rows][columns + row].max(0.0)).collect();
+    let total = tableau[rows][rhs];
+    for probability in column_strategy.iter_mut().chain(row_strategy.iter_mut()) {
+        *probability /= total;
+    }
+
+    MixedEquilibrium { row_strategy, column_strategy, value: 1.0 / total - shift }
+}
+
+// 균형에서 실제로 고르는 전략의 (번호, 확률)을 확률이 큰 순서로 돌려줍니다.
+pub fn support(strategy: &[f64]) -> Vec<(usize, f64)> {
+    let mut support: Vec<(usize, f64)> = strategy.iter().copied().enumerate().filter(|(_, probability)| *probability > SUPPORT_THRESHOLD).collect();
+    support.sort_by(|a, b| b.1.total_cmp(&a.1));
+    support
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-6, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn matching_pennies_mixes_evenly() {
+        let equilibrium = solve_zero_sum(&[vec![1.0, -1.0], vec![-1.0, 1.0]]);
+        assert_close(equilibrium.value, 0.0);
+        for probability in equilibrium.row_strategy.iter().chain(&equilibrium.column_strategy) {
+            assert_close(*probability, 0.5);
+        }
+    }
+
+    #[test]
+    fn rock_paper_scissors_mixes_in_thirds() {
+        let payoff = vec![vec![0.0, -1.0, 1.0], vec![1.0, 0.0, -1.0], vec![-1.0, 1.0, 0.0]];
+        let equilibrium = solve_zero_sum(&payoff);
+        assert_close(equilibrium.value, 0.0);
+        for probability in equilibrium.row_strategy.iter().chain(&equilibrium.column_strategy) {
+            assert_close(*probability, 1.0 / 3.0);
+        }
+    }
+
+    #[test]
+    fn saddle_point_is_a_pure_strategy() {
+        // 두 번째 행이 첫 행보다 항상 좋고, 두 번째 열이 첫 열보다 항상 작습니다.
+        let equilibrium = solve_zero_sum(&[vec![0.3, 0.1], vec![0.4, 0.2]]);
+        assert_close(equilibrium.value, 0.2);
+        assert_eq!(support(&equilibrium.row_strategy), vec![(1, 1.0)]);
+        assert_eq!(support(&equilibrium.column_strategy).len(), 1);
+        assert_close(equilibrium.column_strategy[1], 1.0);
+    }
+
+    #[test]
+    fn value_matches_both_guarantees() {
+        let payoff = vec![vec![0.62, 0.41, 0.55], vec![0.38, 0.70, 0.47], vec![0.51, 0.49, 0.60]];
+        let equilibrium = solve_zero_sum(&payoff);
+        let row_probabilities: f64 = equilibrium.row_strategy.iter().sum();
+        let column_probabilities: f64 = equilibrium.column_strategy.iter().sum();
+        assert_close(row_probabilities, 1.0);
+        assert_close(column_probabilities, 1.0);
+        // 행 전략은 어떤 열에도 게임 값 이상을, 열 전략은 어떤 행에도 게임 값 이하를 보장합니다.
+        for column in 0..payoff[0].len() {
+            let value: f64 = payoff.iter().zip(&equilibrium.row_strategy).map(|(row, probability)| row[column] * probability).sum();
+            assert!(value >= equilibrium.value - 1e-6);
+        }
+        for row in payoff.iter() {
+            let value: f64 = row.iter().zip(&equilibrium.column_strategy).map(|(payoff, probability)| payoff * probability).sum();
+            assert!(value <= equilibrium.value + 1e-6);
+        }
+    }
+
+    #[test]
+    fn support_is_sorted_by_probability() {
+        assert_eq!(support(&[0.2, 0.0, 0.7, 0.1]), vec![(2, 0.7), (0, 0.2), (3, 0.1)]);
+    }
+}
@@ -0,0 +1,49 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum DataError {
+    Network { url: String, message: String },
+    Parse { site: String, message: String },
+    NotFound { site: String, name: String },
+    Ambiguous { name: String, candidates: String },
+    Storage(String),
+    Sync { name: String, message: String },
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DataError::Network { url, message } => write!(f, "{} 요청에 실패했습니다: {}", url, message),
+            DataError::Parse { site, message } => write!(f, "{} 페이지를 해석하지 못했습니다: {}", site, message),
+            DataError::NotFound { site, name } => write!(f, "{}에서 {} 기사를 찾을 수 없습니다.", site, name),
+            DataError::Ambiguous { name, candidates } => write!(
+                f,
+                "{} 기사의 검색 결과가 여러 명입니다: {}. '기사 식별 정보 관리'에서 기사를 지정해주세요.",
+                name, candidates
+            ),
+            DataError::Storage(message) => write!(f, "로컬 데이터를 읽거나 쓰는 데 실패했습니다: {}", message),
+            DataError::Sync { name, message } => write!(f, "{} 기사의 대국 기록을 동기화하지 못했습니다: {}", name, message),
+        }
+    }
+}
+
+impl Error for DataError {}
+
+impl From<rusqlite::Error> for DataError {
+    fn from(e: rusqlite::Error) -> Self {
+        DataError::Storage(e.to_string())
+    }
+}
+
+impl From<std::io::Error> for DataError {
+    fn from(e: std::io::Error) -> Self {
+        DataError::Storage(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for DataError {
+    fn from(e: serde_json::Error) -> Self {
+        DataError::Storage(e.to_string())
+    }
+}
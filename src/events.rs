@@ -0,0 +1,190 @@
+use crate::config;
+use serde::Deserialize;
+use std::fs;
+use std::sync::OnceLock;
+
+// 설정한 대회 목록 파일을 읽을 수 없을 때 사용하는 기본 목록입니다.
+const BUNDLED_CATALOGUE: &str = include_str!("../events.json");
+
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeControl {
+    Live,
+    Blitz,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct EventInfo {
+    pub name: String,
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    #[serde(default)]
+    time_control: Option<TimeControl>,
+    #[serde(default)]
+    pub main_time_minutes: Option<u32>,
+    #[serde(default)]
+    pub byoyomi_seconds: Option<u32>,
+    #[serde(default)]
+    pub byoyomi_periods: Option<u32>,
+    #[serde(default)]
+    pub international: bool,
+    #[serde(default)]
+    pub team_league: bool,
+    #[serde(default)]
+    pub kb_league: bool,
+    #[serde(default)]
+    pub excluded: bool,
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    // 최근 대국을 반영할 때의 K 계수입니다. 지정하지 않으면 단체전과 개인전의 기본 K 계수를 사용합니다.
+    #[serde(default)]
+    pub k: Option<f64>,
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+impl Default for EventInfo {
+    fn default() -> Self {
+        EventInfo {
+            name: "기타 기전".to_string(),
+            patterns: Vec::new(),
+            time_control: None,
+            main_time_minutes: None,
+            byoyomi_seconds: None,
+            byoyomi_periods: None,
+            international: false,
+            team_league: false,
+            kb_league: false,
+            excluded: false,
+            weight: default_weight(),
+            k: None,
+        }
+    }
+}
+
+impl EventInfo {
+    // 제한시간 종류를 지정하지 않았으면 기본 시간으로 판단합니다. 10분 이하면 속기전으로 봅니다.
+    pub fn time_control(&self) -> TimeControl {
+        match (self.time_control, self.main_time_minutes) {
+            (Some(time_control), _) => time_control,
+            (None, Some(minutes)) if minutes <= 10 => TimeControl::Blitz,
+            _ => TimeControl::Live,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let mut parts = vec![self.name.clone()];
+        parts.push(match self.time_control() {
+            TimeControl::Live => "일반".to_string(),
+            TimeControl::Blitz => "속기".to_string(),
+        });
+        if let Some(minutes) = self.main_time_minutes {
+            let mut time = format!("기본 {}분", minutes);
+            if let (Some(seconds), Some(periods)) = (self.byoyomi_seconds, self.byoyomi_periods) {
+                time.push_str(&format!(" 초읽기 {}초 {}회", seconds, periods));
+            }
+            parts.push(time);
+        }
+        parts.push(if self.international { "국제".to_string() } else { "국내".to_string() });
+        if self.team_league {
+            parts.push("단체전".to_string());
+        }
+        if self.kb_league {
+            parts.push("KB리그".to_string());
+        }
+        if self.excluded {
+            parts.push("가중치 계산 제외".to_string());
+        }
+        if self.weight != 1.0 {
+            parts.push(format!("가중치 x{}", self.weight));
+        }
+        if let Some(k) = self.k {
+            parts.push(format!("K {}", k));
+        }
+        parts.join(", ")
+    }
+
+    fn matches(&self, event_name: &str) -> bool {
+        self.patterns.iter().any(|pattern| event_name.contains(pattern.as_str()))
+    }
+}
+
+#[derive(Deserialize)]
+pub struct EventCatalogue {
+    events: Vec<EventInfo>,
+    #[serde(skip)]
+    fallback: EventInfo,
+}
+
+impl EventCatalogue {
+    fn load() -> EventCatalogue {
+        let path = config::get().event_catalogue_file;
+        let text = fs::read_to_string(&path).unwrap_or_else(|_| BUNDLED_CATALOGUE.to_string());
+        serde_json::from_str(&text).unwrap_or_else(|e| {
+            println!("경고: 대회 목록 파일 {}의 형식이 올바르지 않아 기본 목록을 사용합니다: {}", path, e);
+            serde_json::from_str(BUNDLED_CATALOGUE).expect("기본 대회 목록의 형식이 올바르지 않습니다.")
+        })
+    }
+
+    // 목록의 앞쪽 항목부터 비교하여 처음 맞는 대회 정보를 돌려줍니다.
+    pub fn classify(&self, event_name: &str) -> &EventInfo {
+        self.events.iter().find(|event| event.matches(event_name)).unwrap_or(&self.fallback)
+    }
+
+    // 시즌 표기(예: 2023-2024)가 대회명에 들어 있는 KB리그 대국만 해당 시즌으로 봅니다.
+    pub fn is_kb_league_season(&self, event_name: &str, season: Option<&str>) -> bool {
+        self.classify(event_name).kb_league && season.is_none_or(|season| event_name.contains(season))
+    }
+}
+
+pub fn catalogue() -> &'static EventCatalogue {
+    static CATALOGUE: OnceLock<EventCatalogue> = OnceLock::new();
+    CATALOGUE.get_or_init(EventCatalogue::load)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundled() -> EventCatalogue {
+        serde_json::from_str(BUNDLED_CATALOGUE).unwrap()
+    }
+
+    // 대회 목록 이전에는 대회명에 "바둑리그"가 들어 있으면 단체전으로 보았고, 대회 가중치는 없었습니다.
+    #[test]
+    fn bundled_catalogue_keeps_the_old_team_league_rule() {
+        let catalogue = bundled();
+        let names = [
+            "2023-2024 KB국민은행 바둑리그",
+            "2024-2025 KB국민은행 바둑리그 포스트시즌",
+            "KB바둑리그",
+            "2024 여자바둑리그",
+            "2024 시니어바둑리그",
+            "중국 갑조리그",
+            "춘란배 세계바둑선수권",
+            "응씨배 세계프로바둑선수권",
+            "크라운해태배 오픈",
+            "KBS바둑왕전",
+            "퓨처스리그",
+            "LG배 조선일보 기왕전",
+            "GS칼텍스배",
+        ];
+        for name in names {
+            assert_eq!(catalogue.classify(name).team_league, name.contains("바둑리그"), "{}", name);
+        }
+        assert!(catalogue.events.iter().all(|event| event.weight == 1.0));
+    }
+
+    #[test]
+    fn earlier_entries_win() {
+        let catalogue = bundled();
+        // 중국 기전의 문자열이 먼저 맞으므로 "바둑리그"가 들어 있어도 단체전이 아닙니다.
+        let event = catalogue.classify("중국 바둑리그");
+        assert!(event.excluded && !event.team_league);
+        assert!(catalogue.classify("2023-2024 KB국민은행 바둑리그").kb_league);
+        assert!(catalogue.is_kb_league_season("2023-2024 KB국민은행 바둑리그", Some("2023-2024")));
+        assert!(!catalogue.is_kb_league_season("2022-2023 KB국민은행 바둑리그", Some("2023-2024")));
+    }
+}
@@ -0,0 +1,458 @@
+use crate::cache;
+use crate::config;
+use crate::error::DataError;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::thread;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Site {
+    Baduk,
+    Goratings,
+}
+
+impl Site {
+    fn all() -> [Site; 2] {
+        [Site::Baduk, Site::Goratings]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Site::Baduk => "baduk",
+            Site::Goratings => "goratings",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Site> {
+        Site::all().into_iter().find(|site| site.name() == name)
+    }
+
+    fn configured_base_url(&self) -> String {
+        let config = config::get();
+        let base_url = match self {
+            Site::Baduk => config.baduk_base_url,
+            Site::Goratings => config.goratings_base_url,
+        };
+        base_url.trim_end_matches('/').to_string()
+    }
+
+    // 재현 모드에서는 모든 요청을 로컬 재현 서버로 보냅니다.
+    fn base_url(&self) -> String {
+        if config::get().fixture_replay {
+            format!("{}/{}", stand_in_address(), self.name())
+        } else {
+            self.configured_base_url()
+        }
+    }
+}
+
+pub fn baduk_url(path: &str) -> String {
+    format!("{}{}", Site::Baduk.base_url(), path)
+}
+
+pub fn goratings_url(path: &str) -> String {
+    format!("{}{}", Site::Goratings.base_url(), path)
+}
+
+#[derive(Serialize, Deserialize)]
+struct FixtureEntry {
+    method: String,
+    target: String,
+    form: Vec<(String, String)>,
+    body: String,
+}
+
+// 쿼리 문자열의 인코딩 차이로 키가 달라지지 않도록 기록할 때와 재현할 때 같은 방식으로 정리합니다.
+fn normalize_target(target: &str) -> Option<String> {
+    let url = Url::parse(&format!("http://stand-in{}", target)).ok()?;
+    Some(match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    })
+}
+
+fn split_url(url: &str) -> Option<(Site, String)> {
+    Site::all().into_iter().find_map(|site| {
+        let rest = url.strip_prefix(&site.configured_base_url())?;
+        normalize_target(if rest.is_empty() { "/" } else { rest }).map(|target| (site, target))
+    })
+}
+
+fn fixture_path(dir: &Path, site: Site, key: &str) -> PathBuf {
+    dir.join(site.name()).join(format!("{}.json", key))
+}
+
+fn write_fixture(dir: &Path, site: Site, method: &str, target: &str, form: &[(&str, &str)], body: &str) -> Result<(), DataError> {
+    let entry = FixtureEntry {
+        method: method.to_string(),
+        target: target.to_string(),
+        form: form.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+        body: body.to_string(),
+    };
+    let path = fixture_path(dir, site, &cache::cache_key(method, target, form));
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(&entry)?)?;
+    Ok(())
+}
+
+pub fn record(method: &str, url: &str, form: &[(&str, &str)], body: &str) -> Result<(), DataError> {
+    let config = config::get();
+    if !config.fixture_record || config.fixture_replay {
+        return Ok(());
+    }
+    match split_url(url) {
+        Some((site, target)) => write_fixture(Path::new(&config.fixture_dir), site, method, &target, form, body),
+        None => Ok(()),
+    }
+}
+
+fn lookup(dir: &Path, method: &str, target: &str, form: &[(String, String)]) -> Option<String> {
+    let (site_name, rest) = target.trim_start_matches('/').split_once('/').unwrap_or((target.trim_start_matches('/'), ""));
+    let site = Site::from_name(site_name)?;
+    let target = normalize_target(&format!("/{}", rest))?;
+    let form: Vec<(&str, &str)> = form.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect();
+
+    let text = fs::read_to_string(fixture_path(dir, site, &cache::cache_key(method, &target, &form))).ok()?;
+    serde_json::from_str::<FixtureEntry>(&text).ok().map(|entry| entry.body)
+}
+
+fn handle_connection(mut stream: TcpStream, dir: &Path) -> Result<(), Box<dyn Error>> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let form: Vec<(String, String)> = Url::parse(&format!("http://stand-in/?{}", String::from_utf8_lossy(&body)))
+        .map(|url| url.query_pairs().into_owned().collect())
+        .unwrap_or_default();
+
+    let (status, body) = match lookup(dir, &method, &target, &form) {
+        Some(body) => ("200 OK", body),
+        None => {
+            println!("재현 데이터가 없습니다: {} {}", method, target);
+            ("404 Not Found", "fixture not found".to_string())
+        }
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    Ok(())
+}
+
+// 처음 필요할 때 한 번만 서버를 띄웁니다. 이미 다른 프로세스가 같은 포트로 재현 서버를 띄웠다면 그 서버를 사용합니다.
+pub fn stand_in_address() -> String {
+    static ADDRESS: OnceLock<String> = OnceLock::new();
+    ADDRESS
+        .get_or_init(|| {
+            let port = config::get().fixture_server_port;
+            match TcpListener::bind(("127.0.0.1", port)) {
+                Ok(listener) => {
+                    thread::spawn(move || {
+                        for stream in listener.incoming().flatten() {
+                            thread::spawn(move || {
+                                if let Err(e) = handle_connection(stream, Path::new(&config::get().fixture_dir)) {
+                                    println!("재현 서버에서 요청을 처리하는 중 오류가 발생했습니다: {}", e);
+                                }
+                            });
+                        }
+                    });
+                },
+                Err(e) => println!("재현 서버를 {}번 포트에서 시작하지 못했습니다: {}", port, e),
+            }
+            format!("http://127.0.0.1:{}", port)
+        })
+        .clone()
+}
+
+fn count_fixtures(site: Site) -> usize {
+    fs::read_dir(Path::new(&config::get().fixture_dir).join(site.name()))
+        .map(|entries| entries.filter_map(|entry| entry.ok()).filter(|entry| entry.path().extension().is_some_and(|extension| extension == "json")).count())
+        .unwrap_or(0)
+}
+
+fn print_fixture_status() {
+    let config = config::get();
+    println!("\n기록 디렉터리: {}", config.fixture_dir);
+    for site in Site::all() {
+        println!("{}: {}개 (원래 주소: {})", site.name(), count_fixtures(site), site.configured_base_url());
+    }
+    println!("응답 기록: {}", if config.fixture_record { "켜짐" } else { "꺼짐" });
+    println!("재현 모드: {}", if config.fixture_replay { "켜짐" } else { "꺼짐" });
+}
+
+fn serve_until_enter() {
+    let address = stand_in_address();
+    for site in Site::all() {
+        println!("{} 재현 주소: {}/{}", site.name(), address, site.name());
+    }
+    println!("재현 서버가 실행 중입니다. 종료하려면 엔터를 누르세요.");
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
+}
+
+pub fn execute_fixture_menu() {
+    loop {
+        let config = config::get();
+        println!("\n선택할 옵션:");
+        println!("1. 기록 현황");
+        println!("2. 응답 기록 {}", if config.fixture_record { "끄기" } else { "켜기" });
+        println!("3. 재현 모드 {}", if config.fixture_replay { "끄기" } else { "켜기" });
+        println!("4. 기록 디렉터리 변경");
+        println!("5. 재현 서버만 실행");
+        println!("exit. 돌아가기");
+
+        let mut option = String::new();
+        io::stdin().read_line(&mut option).expect("입력을 읽는 데 실패했습니다.");
+
+        let result = match option.trim() {
+            "1" => {
+                print_fixture_status();
+                Ok(())
+            },
+            "2" => config::update(|config| config.fixture_record = !config.fixture_record),
+            "3" => config::update(|config| config.fixture_replay = !config.fixture_replay),
+            "4" => {
+                println!("기록 디렉터리를 입력하세요: ");
+                let mut dir = String::new();
+                io::stdin().read_line(&mut dir).expect("입력을 읽는 데 실패했습니다.");
+                let dir = dir.trim().to_string();
+                if dir.is_empty() {
+                    println!("잘못된 입력입니다. 다시 입력해주세요.");
+                    Ok(())
+                } else {
+                    config::update(|config| config.fixture_dir = dir)
+                }
+            },
+            "5" => {
+                serve_until_enter();
+                Ok(())
+            },
+            "exit" => break,
+            _ => {
+                println!("잘못된 입력입니다. 다시 입력해주세요.");
+                Ok(())
+            }
+        };
+
+        if let Err(e) = result {
+            println!("설정을 저장하는 동안 오류가 발생했습니다: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::{Player, Team};
+    use crate::utils;
+    use chrono::{Datelike, Duration, NaiveDate, Utc};
+    use std::sync::{Mutex, MutexGuard};
+
+    const RANKING_PAGE: &str = r#"<html><body><button class="on">2024년 3월</button>
+<table class="tbstyle03"><tbody>
+<tr><td>1</td><td><a href="/player/1">신진서</a></td><td>10,123</td></tr>
+<tr><td>2</td><td><a href="/player/2">박정환</a></td><td>9,876</td></tr>
+</tbody></table></body></html>"#;
+
+    #[test]
+    fn stand_in_replays_a_recorded_ranking_page() {
+        let dir = std::env::temp_dir().join(format!("jgsm-fixtures-{}", std::process::id()));
+        let form = [("pageNo", "1"), ("keyColumn", "2024"), ("keyWord", "3"), ("etcKey1", "1")];
+        let target = normalize_target("/record/rankingPlayer_in.asp").unwrap();
+        write_fixture(&dir, Site::Baduk, "POST", &target, &form, RANKING_PAGE).unwrap();
+
+        // 기록한 요청과 폼이 다른 요청을 차례로 보내므로 두 번만 받습니다.
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        let server_dir = dir.clone();
+        let server = thread::spawn(move || {
+            for stream in listener.incoming().take(2) {
+                handle_connection(stream.unwrap(), &server_dir).unwrap();
+            }
+        });
+
+        let client = reqwest::blocking::Client::new();
+        let url = format!("http://{}/{}/record/rankingPlayer_in.asp", address, Site::Baduk.name());
+        let response = client.post(&url).form(&form).send().unwrap();
+        assert!(response.status().is_success());
+        let (ratings, ranking_month) = utils::parse_player_ratings_on_baeteil(&response.text().unwrap()).unwrap();
+        assert_eq!(ranking_month, "20243");
+        assert_eq!(ratings.len(), 2);
+        assert_eq!(ratings["신진서"], 10123.0);
+        assert_eq!(ratings["박정환"], 9876.0);
+
+        let missing = client.post(&url).form(&[("pageNo", "2")]).send().unwrap();
+        assert_eq!(missing.status().as_u16(), 404);
+
+        server.join().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // 기록해 둔 두 달의 랭킹과 한 기사의 기보 목록입니다. 날짜는 보관 기간 안에 들도록 오늘을 기준으로 정합니다.
+    struct Recorded {
+        month_a: NaiveDate,
+        month_b: NaiveDate,
+    }
+
+    const SHIN_CODE: i32 = 1001;
+
+    fn ranking_page(month: NaiveDate) -> String {
+        format!(
+            r#"<html><body><button class="on">{}년 {}월</button>
+<table class="tbstyle03"><tbody>
+<tr><td>1</td><td><a href="/player/1">신진서</a></td><td>10,123</td></tr>
+<tr><td>2</td><td><a href="/player/2">박정환</a></td><td>9,876</td></tr>
+<tr><td>3</td><td><a href="/player/3">변상일</a></td><td>9,700</td></tr>
+</tbody></table></body></html>"#,
+            month.year(),
+            month.month()
+        )
+    }
+
+    // 기보 목록처럼 최근 대국부터 적습니다. (날짜, 대회, 승자, 패자, 결과)
+    fn diary_page(rows: &[(NaiveDate, &str, &str, &str, &str)]) -> String {
+        let rows: String = rows
+            .iter()
+            .map(|(date, event_name, winner_name, loser_name, result)| {
+                format!("<tr><td class=\"no\">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n", date, event_name, winner_name, loser_name, result)
+            })
+            .collect();
+        format!("<html><body><table><tbody>\n{}</tbody></table></body></html>", rows)
+    }
+
+    fn record_pages(dir: &Path, month_a: NaiveDate, month_b: NaiveDate) {
+        for month in [month_a, month_b] {
+            let year_text = month.year().to_string();
+            let month_text = month.month().to_string();
+            let form = [("pageNo", "1"), ("keyColumn", year_text.as_str()), ("keyWord", month_text.as_str()), ("etcKey1", "1")];
+            let target = normalize_target("/record/rankingPlayer_in.asp").unwrap();
+            write_fixture(dir, Site::Baduk, "POST", &target, &form, &ranking_page(month)).unwrap();
+        }
+
+        let kb_league = "2023-2024 KB국민은행 바둑리그";
+        let first_page = diary_page(&[
+            (month_b + Duration::try_days(4).unwrap(), kb_league, "신진서", "변상일", "백 불계승"),
+            (month_a + Duration::try_days(19).unwrap(), "GS칼텍스배", "박정환", "신진서", "백 1집반승"),
+            (month_a + Duration::try_days(11).unwrap(), "GS칼텍스배", "변상일", "신진서", "백 불계승"),
+            (month_a + Duration::try_days(4).unwrap(), kb_league, "신진서", "박정환", "백 불계승"),
+        ]);
+        for (page_no, body) in [(1, first_page), (2, diary_page(&[]))] {
+            let target = normalize_target(&format!("/record/diary_in.asp?foreignKey=&pageNo={}&keyWord={}&etcKey=&etc2=1", page_no, SHIN_CODE)).unwrap();
+            write_fixture(dir, Site::Baduk, "GET", &target, &[], &body).unwrap();
+        }
+    }
+
+    // 설정은 프로세스 전체에서 공유하므로 한 번만 임시 디렉터리와 재현 서버로 돌려 두고, 같은 기록 데이터베이스를 쓰는 테스트를 차례로 실행합니다.
+    fn recorded() -> MutexGuard<'static, Recorded> {
+        static RECORDED: OnceLock<Mutex<Recorded>> = OnceLock::new();
+        RECORDED
+            .get_or_init(|| {
+                let dir = std::env::temp_dir().join(format!("jgsm-replay-{}", std::process::id()));
+                let _ = fs::remove_dir_all(&dir);
+                fs::create_dir_all(&dir).unwrap();
+
+                let this_month = Utc::now().date_naive().with_day(1).unwrap();
+                let month_b = (this_month - Duration::try_days(1).unwrap()).with_day(1).unwrap();
+                let month_a = (month_b - Duration::try_days(1).unwrap()).with_day(1).unwrap();
+                record_pages(&dir, month_a, month_b);
+
+                let registry_file = dir.join("player_registry.json");
+                fs::write(&registry_file, format!(r#"{{"players":[{{"korean_name":"신진서","federation_code":{}}}]}}"#, SHIN_CODE)).unwrap();
+
+                let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+                let address = listener.local_addr().unwrap();
+                let server_dir = dir.clone();
+                thread::spawn(move || {
+                    for stream in listener.incoming().flatten() {
+                        let server_dir = server_dir.clone();
+                        thread::spawn(move || handle_connection(stream, &server_dir).unwrap());
+                    }
+                });
+
+                let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+                config::set(Config {
+                    cache_enabled: false,
+                    records_db: path("jgsm.db"),
+                    registry_file: path("player_registry.json"),
+                    event_catalogue_file: path("events.json"),
+                    model_params_file: path("model_params.json"),
+                    baduk_base_url: format!("http://{}/{}", address, Site::Baduk.name()),
+                    goratings_base_url: format!("http://{}/{}", address, Site::Goratings.name()),
+                    fetch_delay_ms: 0,
+                    ..Config::default()
+                });
+                Mutex::new(Recorded { month_a, month_b })
+            })
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    #[test]
+    fn ranking_fetch_replays_the_recorded_month() {
+        let recorded = recorded();
+        let month = recorded.month_a;
+        let (ratings, ranking_month) = utils::fetch_player_ratings_on_baeteil(&month.year().to_string(), &month.month().to_string()).unwrap();
+        assert_eq!(ranking_month, format!("{}{}", month.year(), month.month()));
+        assert_eq!(ratings.len(), 3);
+        assert_eq!(ratings["신진서"], 10123.0);
+        assert_eq!(ratings["변상일"], 9700.0);
+    }
+
+    #[test]
+    fn head_to_head_counts_the_replayed_diary_up_to_the_date() {
+        let recorded = recorded();
+        let today = Utc::now().date_naive();
+        let record = utils::fetch_head_to_head_record("신진서", "박정환", today).unwrap();
+        assert_eq!(record["신진서"], 1);
+        assert_eq!(record["박정환"], 1);
+
+        // 기준일 뒤의 대국은 세지 않습니다.
+        let record = utils::fetch_head_to_head_record("신진서", "박정환", recorded.month_a + Duration::try_days(10).unwrap()).unwrap();
+        assert_eq!(record["신진서"], 1);
+        assert_eq!(record["박정환"], 0);
+    }
+
+    #[test]
+    fn weights_follow_the_replayed_games() {
+        let recorded = recorded();
+        let other_team = Team::new("상대".to_string(), vec![Player::named("박정환"), Player::named("변상일")]);
+        let (white_weight, black_weight, relative_weights, _, _, kb_weight) =
+            utils::get_relative_and_color_weight("신진서", "Shin Jinseo", &other_team, recorded.month_b + Duration::try_days(10).unwrap()).unwrap();
+
+        // 마지막 달의 대국은 다음 달이 시작될 때 더해지므로 첫 달의 대국만 반영됩니다. 백으로 한 판 이기고 흑으로 두 판 졌으며, KB리그 대국은 이긴 한 판입니다.
+        assert!(white_weight > 0.0, "{}", white_weight);
+        assert!(black_weight < 0.0, "{}", black_weight);
+        assert!(kb_weight > 0.0, "{}", kb_weight);
+        assert_eq!(relative_weights.len(), 2);
+        assert!(relative_weights["변상일"] < 0.0, "{}", relative_weights["변상일"]);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::config;
+use crate::model_params;
+use crate::models::{FormGame, RecentForm};
+use crate::records;
+use crate::utils::{baeteil_to_goratings, calculate_win_probability};
+use chrono::{Duration, NaiveDate};
+use std::collections::HashMap;
+
+// 연승/연패는 이 판 수까지만 반영합니다.
+const MAX_STREAK: i32 = 5;
+
+// 기준일까지 최근 몇 주의 대국으로 컨디션 가중치를 계산합니다.
+// 기대 승률 대비 성적, 현재 연승/연패, 평소보다 많거나 적은 대국 수를 각각 점수로 바꿔 더합니다.
+// nowcast_since 이후의 대국은 이미 최근 대국 반영으로 레이팅에 들어갔으므로 기대 승률 대비 성적에서 뺍니다.
+pub fn recent_form(name: &str, elo_rating: f64, rating_list: &HashMap<String, f64>, nowcast_since: Option<NaiveDate>, as_of: NaiveDate) -> RecentForm {
+    let params = model_params::get();
+    let weeks = config::get().form_window_weeks.max(1);
+    let since = as_of - Duration::try_weeks(weeks).unwrap();
+    let games = records::player_games_between(name, since, as_of).unwrap_or_default();
+
+    let mut form_games = Vec::new();
+    let mut performance_points = 0.0;
+    let mut streak: i32 = 0;
+    for game in &games {
+        let won = game.winner_name() == name;
+        streak = match (won, streak) {
+            (true, streak) if streak > 0 => streak + 1,
+            (true, _) => 1,
+            (false, streak) if streak < 0 => streak - 1,
+            (false, _) => -1,
+        };
+
+        // 상대 레이팅을 모르거나 이미 레이팅에 반영된 대국이면 연승/연패와 대국 수에만 반영합니다.
+        if nowcast_since.is_some_and(|since| game.date() >= since) {
+            continue;
+        }
+        let opponent = game.opponent_of(name);
+        if let Some(opponent_rating) = rating_list.get(opponent) {
+            let expected = calculate_win_probability(elo_rating, baeteil_to_goratings(*opponent_rating));
+            let points = params.form_performance_k * (if won { 1.0 } else { 0.0 } - expected);
+            performance_points += points;
+            form_games.push(FormGame::new(game.date(), opponent.to_string(), won, expected, points));
+        }
+    }
+
+    // 2연승부터 반영합니다.
+    let streak_points = params.form_streak_points * streak.signum() as f64 * (streak.abs().min(MAX_STREAK) - 1).max(0) as f64;
+
+    let expected_games = weeks as f64 * params.form_expected_games_per_week;
+    let activity_points = if expected_games > 0.0 {
+        params.form_activity_points * ((games.len() as f64 - expected_games) / expected_games).clamp(-1.0, 1.0)
+    } else {
+        0.0
+    };
+
+    RecentForm::new(form_games, games.len(), streak, performance_points, streak_points, activity_points)
+}
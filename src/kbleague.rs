@@ -1,104 +1,30 @@
-use crate::models::{Lineup, MatchResult, Player, PlayerRelativity, Team, TeamRelativity};
+use crate::best_response::{self, Aggregate, LineupResponse, OpponentWeighting};
+use crate::constraints::{self, LineupConstraints};
+use crate::equilibrium;
+use crate::lineup_prediction;
+use crate::model_params;
+use crate::pipeline;
+use crate::objective::Objective;
+use crate::models::{Lineup, MatchResult, Player, PlayerRelativity, Stone, Team, TeamRelativity};
+use crate::season;
+use crate::uncertainty;
 use crate::utils;
-use chrono::NaiveDate;
 use std::collections::{HashMap, HashSet};
 use std::io::{self, Write};
-use tokio;
-
-fn init_teams() -> Vec<Team> {
-    let mut teams: Vec<Team> = Vec::new();
-    teams.push(Team::new(
-        "한국물가정보".to_string(),
-        vec![
-            Player::new("강동윤".to_string(), "Kang Dongyun".to_string(), "姜东润".to_string(), NaiveDate::from_ymd_opt(1989, 1, 23).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("한승주".to_string(), "Han Seungjoo".to_string(), "韩升周".to_string(), NaiveDate::from_ymd_opt(1996, 11, 27).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("박민규".to_string(), "Park Minkyu".to_string(), "朴珉奎".to_string(), NaiveDate::from_ymd_opt(1994, 6, 5).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("최재영".to_string(), "Choi Jaeyoung".to_string(), "崔宰荣".to_string(), NaiveDate::from_ymd_opt(1997, 4, 10).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("이춘규".to_string(), "Lee Chungyu".to_string(), "李春揆".to_string(), NaiveDate::from_ymd_opt(1989, 3, 27).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("당이페이".to_string(), "Dang Yifei".to_string(), "党毅飞".to_string(), NaiveDate::from_ymd_opt(1995, 6, 17).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-        ]
-    ));
-    teams.push(Team::new(
-        "수려한합천".to_string(),
-        vec![
-            Player::new("원성진".to_string(), "Weon Seongjin".to_string(), "元晟溱".to_string(), NaiveDate::from_ymd_opt(1985, 7, 15).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("한우진".to_string(), "Han Woojin".to_string(), "韩友赈".to_string(), NaiveDate::from_ymd_opt(2005, 6, 12).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("송지훈".to_string(), "Song Jihoon".to_string(), "宋知勋".to_string(), NaiveDate::from_ymd_opt(1998, 2, 23).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("한태희".to_string(), "Han Taehee".to_string(), "韩态熙".to_string(), NaiveDate::from_ymd_opt(1993, 9, 17).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("윤성식".to_string(), "Yun Seongsik".to_string(), "尹圣植".to_string(), NaiveDate::from_ymd_opt(2000, 6, 25).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("김승진".to_string(), "Kim Seungjin".to_string(), "金升珍".to_string(), NaiveDate::from_ymd_opt(2006, 5, 19).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-        ]
-    ));
-    teams.push(Team::new(
-        "마한의 심장 영암".to_string(),
-        vec![
-            Player::new("안성준".to_string(), "An Sungjoon".to_string(), "安成浚".to_string(), NaiveDate::from_ymd_opt(1991, 9, 16).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("설현준".to_string(), "Seol Hyunjun".to_string(), "偰玹准".to_string(), NaiveDate::from_ymd_opt(1999, 1, 29).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("최철한".to_string(), "Choi Cheolhan".to_string(), "崔哲瀚".to_string(), NaiveDate::from_ymd_opt(1985, 3, 12).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("박종훈".to_string(), "Park Jonghoon".to_string(), "朴钟勋".to_string(), NaiveDate::from_ymd_opt(2000, 1, 14).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("엄동건".to_string(), "Eom Donggeon".to_string(), "严动虔".to_string(), NaiveDate::from_ymd_opt(2000, 6, 9).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("쉬하오훙".to_string(), "Xu Haohong".to_string(), "许皓鋐".to_string(), NaiveDate::from_ymd_opt(2001, 4, 30).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-        ]
-    ));
-    teams.push(Team::new(
-        "정관장천녹".to_string(),
-        vec![
-            Player::new("변상일".to_string(), "Byun Sangil".to_string(), "卞相壹".to_string(), NaiveDate::from_ymd_opt(1997, 1, 14).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("홍성지".to_string(), "Hong Seongji".to_string(), "洪性志".to_string(), NaiveDate::from_ymd_opt(1987, 8, 7).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("김정현(大)".to_string(), "Kim Junghyun".to_string(), "金庭贤".to_string(), NaiveDate::from_ymd_opt(1991, 4, 12).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("한상훈".to_string(), "Han Sanghoon".to_string(), "韩尙勋".to_string(), NaiveDate::from_ymd_opt(1988, 5, 16).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("김승구".to_string(), "Kim Seunggu".to_string(), "金丞求".to_string(), NaiveDate::from_ymd_opt(2006, 6, 13).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("박상진".to_string(), "Park Sangjin".to_string(), "朴常镇".to_string(), NaiveDate::from_ymd_opt(2001, 5, 19).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-        ]
-    ));
-    teams.push(Team::new(
-        "울산 고려아연".to_string(),
-        vec![
-            Player::new("신민준".to_string(), "Shin Minjun".to_string(), "申旻埈".to_string(), NaiveDate::from_ymd_opt(1999, 1, 11).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("이창석".to_string(), "Lee Changseok".to_string(), "李昌锡".to_string(), NaiveDate::from_ymd_opt(1996, 4, 27).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("문민종".to_string(), "Moon Minjong".to_string(), "文敏钟".to_string(), NaiveDate::from_ymd_opt(2003, 2, 12).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("한상조".to_string(), "Han Sangcho".to_string(), "韩相朝".to_string(), NaiveDate::from_ymd_opt(1999, 9, 28).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("김채영".to_string(), "Kim Chaeyoung".to_string(), "金彩瑛".to_string(), NaiveDate::from_ymd_opt(1996, 1, 15).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("랴오위안허".to_string(), "Liao Yuanhe".to_string(), "廖元赫".to_string(), NaiveDate::from_ymd_opt(2000, 12, 20).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-        ]
-    ));
-    teams.push(Team::new(
-        "바둑메카 의정부".to_string(),
-        vec![
-            Player::new("김명훈".to_string(), "Kim Myounghoon".to_string(), "金明训".to_string(), NaiveDate::from_ymd_opt(1997, 4, 7).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("박건호".to_string(), "Park Geunho".to_string(), "朴键昊".to_string(), NaiveDate::from_ymd_opt(1998, 6, 14).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("이원영".to_string(), "Lee Wonyoung".to_string(), "李元荣".to_string(), NaiveDate::from_ymd_opt(1992, 5, 8).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("허영호".to_string(), "Heo Yongho".to_string(), "许映皓".to_string(), NaiveDate::from_ymd_opt(1986, 7, 2).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("박재근".to_string(), "Park Jaekeun".to_string(), "朴材根".to_string(), NaiveDate::from_ymd_opt(1996, 4, 16).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("양카이원".to_string(), "Yang Kaiwen".to_string(), "杨楷文".to_string(), NaiveDate::from_ymd_opt(1997, 1, 28).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-        ]
-    ));
-    teams.push(Team::new(
-        "Kixx".to_string(),
-        vec![
-            Player::new("신진서".to_string(), "Shin Jinseo".to_string(), "申真谞".to_string(), NaiveDate::from_ymd_opt(2000, 3, 17).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("박진솔".to_string(), "Park Jinsol".to_string(), "朴进率".to_string(), NaiveDate::from_ymd_opt(1986, 9, 7).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("김승재".to_string(), "Kim Seungjae".to_string(), "金升宰".to_string(), NaiveDate::from_ymd_opt(1992, 8, 11).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("백현우".to_string(), "Baek Hyeonwoo".to_string(), "白现宇".to_string(), NaiveDate::from_ymd_opt(2001, 2, 12).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("김창훈".to_string(), "Kim Changhoon".to_string(), "金昌勋".to_string(), NaiveDate::from_ymd_opt(1995, 8, 20).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-        ]
-    ));
-    teams.push(Team::new(
-        "원익".to_string(),
-        vec![
-            Player::new("박정환".to_string(), "Park Junghwan".to_string(), "朴廷桓".to_string(), NaiveDate::from_ymd_opt(1993, 1, 11).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("이지현(남)".to_string(), "Lee Jihyun (m)".to_string(), "李志贤".to_string(), NaiveDate::from_ymd_opt(1992, 9, 30).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("박영훈".to_string(), "Park Yeonghun".to_string(), "朴永训".to_string(), NaiveDate::from_ymd_opt(1985, 4, 1).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("김진휘".to_string(), "Kim Jinhwi".to_string(), "金真辉".to_string(), NaiveDate::from_ymd_opt(1996, 1, 26).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("금지우".to_string(), "Geum Jiwoo".to_string(), "琴沚玗".to_string(), NaiveDate::from_ymd_opt(2001, 8, 29).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("구쯔하오".to_string(), "Gu Zihao".to_string(), "辜梓豪".to_string(), NaiveDate::from_ymd_opt(1998, 3, 13).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-        ]
-    ));
-    teams
+
+fn init_teams() -> Result<Vec<Team>, String> {
+    let season = season::load_current_season()?;
+    Ok(season.teams().clone())
 }
 
 pub fn execute_kbleague() {
-    let mut teams = init_teams();
+    let mut teams = match init_teams() {
+        Ok(teams) => teams,
+        Err(e) => {
+            println!("시즌 파일을 불러오는 동안 오류가 발생했습니다: {}", e);
+            return;
+        }
+    };
     let mut selected_teams: Vec<Team> = Vec::new();
     for _ in 0..2 {
         loop {
@@ -143,7 +69,7 @@ pub fn execute_kbleague() {
         loop {
             println!("\n{} 팀의 스쿼드:", selected_team.team_name());
             for (index, player) in selected_team.players().iter().enumerate() {
-                println!("{}. {} (elo: {:.2})", index + 1, player.korean_name(), player.elo_rating());
+                println!("{}. {} (elo: {:.2} ±{:.0}, {}){}", index + 1, player.korean_name(), player.elo_rating(), player.rating_deviation(), player.describe_rating_source(), player.data_quality_note());
             }
             let mut input = String::new();
             print!("\n제외할 기사를 선택하세요 (완료시 엔터): ");
@@ -173,7 +99,7 @@ pub fn execute_kbleague() {
         println!("\n{} 팀의 기사에 대한 컨디션 가중치를 입력하세요.", selected_team.team_name());
         loop {
             for (index, player) in selected_team.players().iter().enumerate() {
-                println!("{}. {} (elo: {:.2})\n    컨디션 가중치: {:.2}", index + 1, player.korean_name(), player.elo_rating(), player.condition_weight());
+                println!("{}. {} (elo: {:.2})\n    컨디션 가중치: {:.2} (최근 경기력 {:+.2}: {})", index + 1, player.korean_name(), player.elo_rating(), player.condition_weight(), player.form().weight(), player.form().describe());
             }
             println!("컨디션 가중치를 입력할 기사를 선택하세요 (완료시 엔터): ");
             let mut input = String::new();
@@ -186,6 +112,10 @@ pub fn execute_kbleague() {
             if selected_index > 0 && selected_index <= selected_team.players().len() {
                 let player = &mut selected_team.players_mut()[selected_index - 1];
 
+                println!("\n{} 기사의 최근 대국 ({}):", player.korean_name(), player.form().describe());
+                for game in player.form().games() {
+                    println!("    {}", game.describe());
+                }
                 input.clear();
                 println!("\n{} 기사의 컨디션 가중치를 입력하세요.(음수 입력 가능) (변경하지 않으려면 엔터): ", player.korean_name());
                 io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
@@ -277,7 +207,7 @@ pub fn execute_kbleague() {
     for team in selected_teams.iter() {
         println!("\n{}:", team.team_name());
         for player in team.players() {
-            println!("{} (elo: {:.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2}, 흑번: {:.2}, 백번: {:.2})", player.korean_name(), player.elo_rating(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight(), player.black_weight(), player.white_weight());
+            println!("{} (elo: {:.2}, 전망: {:+.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2}, 흑번: {:.2}, 백번: {:.2})", player.korean_name(), player.elo_rating(), player.projection().change(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight(), player.black_weight(), player.white_weight());
             let mut relative_weights: Vec<(&String, f64)> = player.relative_weight().iter().map(|(k, v)| (k, *v)).collect();
             relative_weights.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
             for (k, v) in relative_weights {
@@ -307,8 +237,7 @@ pub fn execute_kbleague() {
         }
     }
 
-    let mut first_rapid_black: bool = false;
-    let mut first_rapid_none_color: bool = false;
+    let mut first_rapid: Option<Stone> = None;
 
     loop {
         println!("\n{}의 1국 장고(rapid)가 흑번인지 백번인지 선택해주세요:", selected_teams[0].team_name());
@@ -322,17 +251,15 @@ pub fn execute_kbleague() {
 
         match color_option {
             "1" => {
-                first_rapid_none_color = false;
-                first_rapid_black = true;
+                first_rapid = Some(Stone::Black);
                 break;
             },
             "2" => {
-                first_rapid_none_color = false;
-                first_rapid_black = false;
+                first_rapid = Some(Stone::White);
                 break;
             },
             "3" => {
-                first_rapid_none_color = true;
+                first_rapid = None;
                 break;
             },
             "exit" => break,
@@ -340,8 +267,11 @@ pub fn execute_kbleague() {
         }
     }
 
+    // 라인업 조건에서 흑번인 판을 가리기 위한 좌측팀 1국의 흑백입니다.
+    let first_rapid_colour = first_rapid.map(|stone| stone == Stone::Black);
+
     println!("\n상대전적을 업데이트 중...");
-    match utils::generate_player_relativities(&selected_teams, first_rapid_black, first_rapid_none_color) {
+    match utils::generate_player_relativities(&selected_teams, first_rapid) {
         Ok(player_relativities) => {
             println!("\n라인업 메트릭스 생성 중...");
             let mut match_results_matrix: Vec<Vec<MatchResult>> = Vec::new();
@@ -378,13 +308,13 @@ pub fn execute_kbleague() {
                 println!("1. {}의 스쿼드", selected_teams[0].team_name());
                 println!("2. {}의 스쿼드", selected_teams[1].team_name());
                 println!("3. 양팀의 라인업 메트릭스를 Excel로 출력\n");
-                println!("4. {} 최고 평균승률 라인업", selected_teams[0].team_name());
-                println!("5. {} 베스트24 라인업에 대한 {} 최고 평균승률 라인업", selected_teams[1].team_name(), selected_teams[0].team_name());
+                println!("4. {} 최고 평균 라인업", selected_teams[0].team_name());
+                println!("5. {} 베스트24 라인업에 대한 {} 최고 평균 라인업", selected_teams[1].team_name(), selected_teams[0].team_name());
                 println!("6. {} 미니맥스 라인업(최선 + 상대 카운터픽)", selected_teams[0].team_name());
-                println!("7. {} 예상라인업에 대한 {} 카운터픽(최고평균)", selected_teams[1].team_name(), selected_teams[0].team_name());
+                println!("7. {} 예상라인업(지난 오더 예측 또는 직접 입력한 분포)에 대한 {} 카운터픽(최고평균)", selected_teams[1].team_name(), selected_teams[0].team_name());
                 println!("8. {} 예상라인업에 대한 {} 카운터픽(미니맥스)\n", selected_teams[1].team_name(), selected_teams[0].team_name());
 
-                println!("9. 양측최선 라인업 승리확률(내쉬균형)");
+                println!("9. 양측최선 라인업 승리확률(내쉬균형, 상수합이 아닌 평가 기준은 최대최소)");
                 println!("10. 지정 라인업 승리확률");
                 println!("11. 에이스 결정전 Excel로 출력");
                 println!("12. 실시간 팀 승률\n");
@@ -395,18 +325,19 @@ pub fn execute_kbleague() {
                 let mut option = String::new();
                 io::stdin().read_line(&mut option).expect("입력을 읽는 데 실패했습니다.");
                 let option = option.trim();
+                let objective = Objective::current();
 
                 match option {
                     "1" => {
                         println!("\n{} 팀의 스쿼드:", selected_teams[0].team_name());
                         for player in selected_teams[0].players() {
-                            println!("{} (elo: {:.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", player.korean_name(), player.elo_rating(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight());
+                            println!("{} (elo: {:.2}, 전망: {:+.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", player.korean_name(), player.elo_rating(), player.projection().change(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight());
                         }
                     },
                     "2" => {
                         println!("\n{} 팀의 스쿼드:", selected_teams[1].team_name());
                         for player in selected_teams[1].players() {
-                            println!("{} (elo: {:.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", player.korean_name(), player.elo_rating(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight());
+                            println!("{} (elo: {:.2}, 전망: {:+.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", player.korean_name(), player.elo_rating(), player.projection().change(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight());
                         }
                     },
                     "3" => {
@@ -416,795 +347,142 @@ pub fn execute_kbleague() {
                         }
                     },
                     "4" => {
-                        let team1_filtered_lineups = utils::filter_team1_lineups(&selected_teams, &team1_all_lineups);
-
-                        let mut avg_probabilities: Vec<(Lineup, f64, f64, f64, f64, f64, f64, f64, f64)> = Vec::new();
-                        for lineup in &team1_filtered_lineups {
-                            let mut total_win_prob = 0.0;
-                            let mut three_one_prob = 0.0;
-                            let mut four_zero_prob = 0.0;
-                            let mut two_two_prob = 0.0;
-                            let mut first_rapid_win_prob = 0.0;
-                            let mut second_blitz_win_prob = 0.0;
-                            let mut third_blitz_win_prob = 0.0;
-                            let mut forth_blitz_win_prob = 0.0;
-                            let mut count = 0.0;
-
-                            for match_result in &match_results_matrix {
-                                for result in match_result {
-                                    if result.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                    result.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                    result.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                    result.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name() {
-                                        total_win_prob += result.total_win_probability();
-                                        three_one_prob += result.four_zero_probability() + result.three_one_probability();
-                                        four_zero_prob += result.four_zero_probability();
-                                        two_two_prob += result.two_two_probability();
-                                        first_rapid_win_prob += result.first_rapid_win_probability();
-                                        second_blitz_win_prob += result.second_blitz_win_probability();
-                                        third_blitz_win_prob += result.third_blitz_win_probability();
-                                        forth_blitz_win_prob += result.forth_blitz_win_probability();
-                                        count += 1.0;
-                                    }
-                                }
-                            }
-
-                            if count > 0.0 {
-                                avg_probabilities.push((
-                                    lineup.clone(),
-                                    total_win_prob / count,
-                                    three_one_prob / count,
-                                    four_zero_prob / count,
-                                    two_two_prob / count,
-                                    first_rapid_win_prob / count,
-                                    second_blitz_win_prob / count,
-                                    third_blitz_win_prob / count,
-                                    forth_blitz_win_prob / count
-                                ));
-                            }
-                        }
-
-
-                        println!("========================");
-                        avg_probabilities.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-                        if let Some((best_lineup, avg_total_win_prob, avg_win_prob, avg_four_zero_prob, avg_two_two_prob, avg_first_rapid_win_prob, avg_second_blitz_win_prob, avg_third_blitz_win_prob, avg_forth_blitz_win_prob)) = avg_probabilities.first() {
-                            println!("평균 총 승리확률이 가장 높은 라인업");
-                            println!("1국 장고(rapid): {} (평균 승리확률: {:.2}%)", best_lineup.first_rapid().korean_name(), avg_first_rapid_win_prob);
-                            println!("2국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.second_blitz().korean_name(), avg_second_blitz_win_prob);
-                            println!("3국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.third_blitz().korean_name(), avg_third_blitz_win_prob);
-                            println!("4국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.forth_blitz().korean_name(), avg_forth_blitz_win_prob);
-                            println!("\n평균 총 승리확률: {:.2}%", avg_total_win_prob);
-                            println!("평균 동점 없는 승리확률: {:.2}%", avg_win_prob);
-                            println!("평균 완봉승 확률: {:.2}%", avg_four_zero_prob);
-                            println!("평균 동점 확률: {:.2}%", avg_two_two_prob);
-                        } else {
-                            println!("적합한 구성을 찾을 수 없습니다.");
-                        }
-                        println!("========================");
-                        avg_probabilities.sort_by(|a, b| {
-                            if (b.2 - a.2).abs() < 0.001 {
-                                if (b.1 - a.1).abs() < 0.001 {
-                                    std::cmp::Ordering::Equal
-                                } else {
-                                    b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
-                                }
-                            } else {
-                                b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal)
-                            }
-                        });
-                        if let Some((best_lineup, avg_total_win_prob, avg_win_prob, avg_four_zero_prob, avg_two_two_prob, avg_first_rapid_win_prob, avg_second_blitz_win_prob, avg_third_blitz_win_prob, avg_forth_blitz_win_prob)) = avg_probabilities.first() {
-                            println!("평균 동점 없는 승리확률이 가장 높은 라인업");
-                            println!("1국 장고(rapid): {} (평균 승리확률: {:.2}%)", best_lineup.first_rapid().korean_name(), avg_first_rapid_win_prob);
-                            println!("2국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.second_blitz().korean_name(), avg_second_blitz_win_prob);
-                            println!("3국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.third_blitz().korean_name(), avg_third_blitz_win_prob);
-                            println!("4국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.forth_blitz().korean_name(), avg_forth_blitz_win_prob);
-                            println!("\n평균 총 승리확률: {:.2}%", avg_total_win_prob);
-                            println!("평균 동점 없는 승리확률: {:.2}%", avg_win_prob);
-                            println!("평균 완봉승 확률: {:.2}%", avg_four_zero_prob);
-                            println!("평균 동점 확률: {:.2}%", avg_two_two_prob);
-                        } else {
-                            println!("적합한 구성을 찾을 수 없습니다.");
-                        }
-                        println!("========================");
-                        avg_probabilities.sort_by(|a, b| {
-                            if (b.3 - a.3).abs() < 0.001 {
-                                if (b.1 - a.1).abs() < 0.001 {
-                                    std::cmp::Ordering::Equal
-                                } else {
-                                    b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
-                                }
-                            } else {
-                                b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal)
-                            }
-                        });
-                        if let Some((best_lineup, avg_total_win_prob, avg_win_prob, avg_four_zero_prob, avg_two_two_prob, avg_first_rapid_win_prob, avg_second_blitz_win_prob, avg_third_blitz_win_prob, avg_forth_blitz_win_prob)) = avg_probabilities.first() {
-                            println!("평균 완봉승확률이 가장 높은 라인업");
-                            println!("1국 장고(rapid): {} (평균 승리확률: {:.2}%)", best_lineup.first_rapid().korean_name(), avg_first_rapid_win_prob);
-                            println!("2국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.second_blitz().korean_name(), avg_second_blitz_win_prob);
-                            println!("3국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.third_blitz().korean_name(), avg_third_blitz_win_prob);
-                            println!("4국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.forth_blitz().korean_name(), avg_forth_blitz_win_prob);
-                            println!("\n평균 총 승리확률: {:.2}%", avg_total_win_prob);
-                            println!("평균 동점 없는 승리확률: {:.2}%", avg_win_prob);
-                            println!("평균 완봉승 확률: {:.2}%", avg_four_zero_prob);
-                            println!("평균 동점 확률: {:.2}%", avg_two_two_prob);
-                        } else {
-                            println!("적합한 구성을 찾을 수 없습니다.");
-                        }
-                        println!("========================");
-                        avg_probabilities.sort_by(|a, b| {
-                            if (b.4 - a.4).abs() < 0.001 {
-                                if (b.1 - a.1).abs() < 0.001 {
-                                    std::cmp::Ordering::Equal
-                                } else {
-                                    b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
-                                }
-                            } else {
-                                b.4.partial_cmp(&a.4).unwrap_or(std::cmp::Ordering::Equal)
-                            }
-                        });
-                        if let Some((best_lineup, avg_total_win_prob, avg_win_prob, avg_four_zero_prob, avg_two_two_prob, avg_first_rapid_win_prob, avg_second_blitz_win_prob, avg_third_blitz_win_prob, avg_forth_blitz_win_prob)) = avg_probabilities.first() {
-                            println!("평균 동점 확률이 가장 높은 라인업");
-                            println!("1국 장고(rapid): {} (평균 승리확률: {:.2}%)", best_lineup.first_rapid().korean_name(), avg_first_rapid_win_prob);
-                            println!("2국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.second_blitz().korean_name(), avg_second_blitz_win_prob);
-                            println!("3국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.third_blitz().korean_name(), avg_third_blitz_win_prob);
-                            println!("4국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.forth_blitz().korean_name(), avg_forth_blitz_win_prob);
-                            println!("\n평균 총 승리확률: {:.2}%", avg_total_win_prob);
-                            println!("평균 동점 없는 승리확률: {:.2}%", avg_win_prob);
-                            println!("평균 완봉승 확률: {:.2}%", avg_four_zero_prob);
-                            println!("평균 동점 확률: {:.2}%", avg_two_two_prob);
-                        } else {
-                            println!("적합한 구성을 찾을 수 없습니다.");
+                        let team1_filtered_lineups = utils::filter_team1_lineups(&selected_teams, &team1_all_lineups, first_rapid_colour);
+                        if team1_filtered_lineups.is_empty() {
+                            continue;
                         }
-                        println!("========================");
+                        let responses = best_response::best_responses(&match_results_matrix, &team1_filtered_lineups, &OpponentWeighting::Uniform, Aggregate::Expected, &objective, team1_filtered_lineups.len());
+                        print_average_responses(&match_results_matrix, &responses, None, &objective);
 
                         println!("\n계속하려면 엔터를 누르세요.");
                         let mut pause = String::new();
                         io::stdin().read_line(&mut pause).expect("입력을 읽는 데 실패했습니다.");
                     },
                     "5" => {
-                        let team1_filtered_lineups = utils::filter_team1_lineups(&selected_teams, &team1_all_lineups);
-
-                        let mut avg_probabilities: Vec<(Lineup, f64, f64, f64, f64, f64, f64, f64, f64)> = Vec::new();
-                        for lineup in &team1_filtered_lineups {
-                            let mut total_win_prob = 0.0;
-                            let mut three_one_prob = 0.0;
-                            let mut four_zero_prob = 0.0;
-                            let mut two_two_prob = 0.0;
-                            let mut first_rapid_win_prob = 0.0;
-                            let mut second_blitz_win_prob = 0.0;
-                            let mut third_blitz_win_prob = 0.0;
-                            let mut forth_blitz_win_prob = 0.0;
-                            let mut count = 0.0;
-
-                            if let Some(match_result) = match_results_matrix.iter().find(|match_result| 
-                                match_result.get(0).map_or(false, |result| 
-                                    result.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                    result.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                    result.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                    result.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name()
-                                )
-                            ) {
-                                for result in match_result.iter().take(24) {
-                                    total_win_prob += result.total_win_probability();
-                                    three_one_prob += result.four_zero_probability() + result.three_one_probability();
-                                    four_zero_prob += result.four_zero_probability();
-                                    two_two_prob += result.two_two_probability();
-                                    first_rapid_win_prob += result.first_rapid_win_probability();
-                                    second_blitz_win_prob += result.second_blitz_win_probability();
-                                    third_blitz_win_prob += result.third_blitz_win_probability();
-                                    forth_blitz_win_prob += result.forth_blitz_win_probability();
-                                    count += 1.0;
-                                }
-                            }
-
-                            if count > 0.0 {
-                                avg_probabilities.push((
-                                    lineup.clone(),
-                                    total_win_prob / count,
-                                    three_one_prob / count,
-                                    four_zero_prob / count,
-                                    two_two_prob / count,
-                                    first_rapid_win_prob / count,
-                                    second_blitz_win_prob / count,
-                                    third_blitz_win_prob / count,
-                                    forth_blitz_win_prob / count
-                                ));
-                            }
+                        let team1_filtered_lineups = utils::filter_team1_lineups(&selected_teams, &team1_all_lineups, first_rapid_colour);
+                        if team1_filtered_lineups.is_empty() {
+                            continue;
                         }
-
-
-                        println!("========================");
-                        avg_probabilities.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-                        if let Some((best_lineup, avg_total_win_prob, avg_win_prob, avg_four_zero_prob, avg_two_two_prob, avg_first_rapid_win_prob, avg_second_blitz_win_prob, avg_third_blitz_win_prob, avg_forth_blitz_win_prob)) = avg_probabilities.first() {
-                            println!("평균 총 승리확률이 가장 높은 라인업");
-                            println!("1국 장고(rapid): {} (평균 승리확률: {:.2}%)", best_lineup.first_rapid().korean_name(), avg_first_rapid_win_prob);
-                            println!("2국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.second_blitz().korean_name(), avg_second_blitz_win_prob);
-                            println!("3국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.third_blitz().korean_name(), avg_third_blitz_win_prob);
-                            println!("4국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.forth_blitz().korean_name(), avg_forth_blitz_win_prob);
-                            println!("\n평균 총 승리확률: {:.2}%", avg_total_win_prob);
-                            println!("평균 동점 없는 승리확률: {:.2}%", avg_win_prob);
-                            println!("평균 완봉승 확률: {:.2}%", avg_four_zero_prob);
-                            println!("평균 동점 확률: {:.2}%", avg_two_two_prob);
-                        } else {
-                            println!("적합한 구성을 찾을 수 없습니다.");
-                        }
-                        println!("========================");
-                        avg_probabilities.sort_by(|a, b| {
-                            if (b.2 - a.2).abs() < 0.001 {
-                                if (b.1 - a.1).abs() < 0.001 {
-                                    std::cmp::Ordering::Equal
-                                } else {
-                                    b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
-                                }
-                            } else {
-                                b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal)
-                            }
-                        });
-                        if let Some((best_lineup, avg_total_win_prob, avg_win_prob, avg_four_zero_prob, avg_two_two_prob, avg_first_rapid_win_prob, avg_second_blitz_win_prob, avg_third_blitz_win_prob, avg_forth_blitz_win_prob)) = avg_probabilities.first() {
-                            println!("평균 동점 없는 승리확률이 가장 높은 라인업");
-                            println!("1국 장고(rapid): {} (평균 승리확률: {:.2}%)", best_lineup.first_rapid().korean_name(), avg_first_rapid_win_prob);
-                            println!("2국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.second_blitz().korean_name(), avg_second_blitz_win_prob);
-                            println!("3국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.third_blitz().korean_name(), avg_third_blitz_win_prob);
-                            println!("4국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.forth_blitz().korean_name(), avg_forth_blitz_win_prob);
-                            println!("\n평균 총 승리확률: {:.2}%", avg_total_win_prob);
-                            println!("평균 동점 없는 승리확률: {:.2}%", avg_win_prob);
-                            println!("평균 완봉승 확률: {:.2}%", avg_four_zero_prob);
-                            println!("평균 동점 확률: {:.2}%", avg_two_two_prob);
-                        } else {
-                            println!("적합한 구성을 찾을 수 없습니다.");
-                        }
-                        println!("========================");
-                        avg_probabilities.sort_by(|a, b| {
-                            if (b.3 - a.3).abs() < 0.001 {
-                                if (b.1 - a.1).abs() < 0.001 {
-                                    std::cmp::Ordering::Equal
-                                } else {
-                                    b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
-                                }
-                            } else {
-                                b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal)
-                            }
-                        });
-                        if let Some((best_lineup, avg_total_win_prob, avg_win_prob, avg_four_zero_prob, avg_two_two_prob, avg_first_rapid_win_prob, avg_second_blitz_win_prob, avg_third_blitz_win_prob, avg_forth_blitz_win_prob)) = avg_probabilities.first() {
-                            println!("평균 완봉승확률이 가장 높은 라인업");
-                            println!("1국 장고(rapid): {} (평균 승리확률: {:.2}%)", best_lineup.first_rapid().korean_name(), avg_first_rapid_win_prob);
-                            println!("2국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.second_blitz().korean_name(), avg_second_blitz_win_prob);
-                            println!("3국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.third_blitz().korean_name(), avg_third_blitz_win_prob);
-                            println!("4국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.forth_blitz().korean_name(), avg_forth_blitz_win_prob);
-                            println!("\n평균 총 승리확률: {:.2}%", avg_total_win_prob);
-                            println!("평균 동점 없는 승리확률: {:.2}%", avg_win_prob);
-                            println!("평균 완봉승 확률: {:.2}%", avg_four_zero_prob);
-                            println!("평균 동점 확률: {:.2}%", avg_two_two_prob);
-                        } else {
-                            println!("적합한 구성을 찾을 수 없습니다.");
-                        }
-                        println!("========================");
-                        avg_probabilities.sort_by(|a, b| {
-                            if (b.4 - a.4).abs() < 0.001 {
-                                if (b.1 - a.1).abs() < 0.001 {
-                                    std::cmp::Ordering::Equal
-                                } else {
-                                    b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
-                                }
-                            } else {
-                                b.4.partial_cmp(&a.4).unwrap_or(std::cmp::Ordering::Equal)
-                            }
-                        });
-                        if let Some((best_lineup, avg_total_win_prob, avg_win_prob, avg_four_zero_prob, avg_two_two_prob, avg_first_rapid_win_prob, avg_second_blitz_win_prob, avg_third_blitz_win_prob, avg_forth_blitz_win_prob)) = avg_probabilities.first() {
-                            println!("평균 동점 확률이 가장 높은 라인업");
-                            println!("1국 장고(rapid): {} (평균 승리확률: {:.2}%)", best_lineup.first_rapid().korean_name(), avg_first_rapid_win_prob);
-                            println!("2국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.second_blitz().korean_name(), avg_second_blitz_win_prob);
-                            println!("3국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.third_blitz().korean_name(), avg_third_blitz_win_prob);
-                            println!("4국 속기(blitz): {} (평균 승리확률: {:.2}%)", best_lineup.forth_blitz().korean_name(), avg_forth_blitz_win_prob);
-                            println!("\n평균 총 승리확률: {:.2}%", avg_total_win_prob);
-                            println!("평균 동점 없는 승리확률: {:.2}%", avg_win_prob);
-                            println!("평균 완봉승 확률: {:.2}%", avg_four_zero_prob);
-                            println!("평균 동점 확률: {:.2}%", avg_two_two_prob);
-                        } else {
-                            println!("적합한 구성을 찾을 수 없습니다.");
-                        }
-                        println!("========================");
+                        let responses = best_response::best_responses(&match_results_matrix, &team1_filtered_lineups, &OpponentWeighting::Top(24), Aggregate::Expected, &objective, team1_filtered_lineups.len());
+                        print_average_responses(&match_results_matrix, &responses, None, &objective);
 
                         println!("\n계속하려면 엔터를 누르세요.");
                         let mut pause = String::new();
                         io::stdin().read_line(&mut pause).expect("입력을 읽는 데 실패했습니다.");
                     },
                     "6" => {
-                        let team1_filtered_lineups = utils::filter_team1_lineups(&selected_teams, &team1_all_lineups);
-
-                        let mut best_match_result: Option<&MatchResult> = None;
-                        let mut highest_min_total_win_prob = 0.0;
-
-                        for lineup in &team1_filtered_lineups {
-                            let mut min_total_win_prob = std::f64::MAX;
-
-                            for match_result in &match_results_matrix {
-                                for result in match_result.iter() {
-                                    if result.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                    result.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                    result.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                    result.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name() {
-                                        if result.total_win_probability() < min_total_win_prob {
-                                            min_total_win_prob = result.total_win_probability();
-                                        }
-                                    }
-                                }
-                            }
-
-                            if min_total_win_prob != std::f64::MAX && min_total_win_prob > highest_min_total_win_prob {
-                                highest_min_total_win_prob = min_total_win_prob;
-                                best_match_result = match_results_matrix.iter().flatten().find(|&r| 
-                                    r.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                    r.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                    r.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                    r.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name() &&
-                                    (r.total_win_probability() - highest_min_total_win_prob).abs() < std::f64::EPSILON
-                                );
-                            }
-                        }
-
-                        println!("========================");
-                        if let Some(best_result) = best_match_result {
-                            let player1_best_tiebreaker_names: HashSet<String> = best_result.tiebreaker_relativities().iter()
-                                .filter_map(|detail| detail.as_ref())
-                                .map(|detail| detail.player1().korean_name().to_string())
-                                .collect();
-                            let player2_best_tiebreaker_names: HashSet<String> = best_result.tiebreaker_relativities().iter()
-                                .filter_map(|detail| detail.as_ref())
-                                .map(|detail| detail.player2().korean_name().to_string())
-                                .collect();
-                            println!("1국 장고(rapid): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.first_rapid().player1().korean_name(), best_result.first_rapid().player2().korean_name(), best_result.first_rapid().player1_wins(), best_result.first_rapid().player2_wins(), best_result.first_rapid_win_probability());
-                            println!("2국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.second_blitz().player1().korean_name(), best_result.second_blitz().player2().korean_name(), best_result.second_blitz().player1_wins(), best_result.second_blitz().player2_wins(), best_result.second_blitz_win_probability());
-                            println!("3국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.third_blitz().player1().korean_name(), best_result.third_blitz().player2().korean_name(), best_result.third_blitz().player1_wins(), best_result.third_blitz().player2_wins(), best_result.third_blitz_win_probability());
-                            println!("4국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.forth_blitz().player1().korean_name(), best_result.forth_blitz().player2().korean_name(), best_result.forth_blitz().player1_wins(), best_result.forth_blitz().player2_wins(), best_result.forth_blitz_win_probability());
-                            println!("\n4-0: {:.2}%", best_result.four_zero_probability());
-                            println!("3-1: {:.2}%", best_result.three_one_probability());
-                            println!("2-2: {:.2}% => ({}) vs ({}): {:.2}%", best_result.two_two_probability(), player1_best_tiebreaker_names.iter().cloned().collect::<Vec<_>>().join(", "), player2_best_tiebreaker_names.iter().cloned().collect::<Vec<_>>().join(", "), best_result.tiebreaker_win_probability());
-                            println!("1-3: {:.2}%", best_result.one_three_probability());
-                            println!("0-4: {:.2}%", best_result.zero_four_probability());
-                            println!("\n총 승리확률: {:.2}%", best_result.total_win_probability());
-                        } else {
-                            println!("적합한 매치 결과를 찾을 수 없습니다.");
+                        let team1_filtered_lineups = utils::filter_team1_lineups(&selected_teams, &team1_all_lineups, first_rapid_colour);
+                        if team1_filtered_lineups.is_empty() {
+                            continue;
                         }
-                        println!("========================");
-
+                        let responses = best_response::best_responses(&match_results_matrix, &team1_filtered_lineups, &OpponentWeighting::Uniform, Aggregate::WorstCase, &objective, 1);
+                        print_worst_case_response(&match_results_matrix, responses.first(), &objective);
 
                         println!("\n계속하려면 엔터를 누르세요.");
                         let mut pause = String::new();
                         io::stdin().read_line(&mut pause).expect("입력을 읽는 데 실패했습니다.");
                     },
                     "7" => {
-                        let team1_filtered_lineups = utils::filter_team1_lineups(&selected_teams, &team1_all_lineups);
-
-                        let unknown_player = Player::new("알 수 없음".to_string(), "unknown".to_string(), "未知".to_string(), NaiveDate::from_ymd_opt(2000, 1, 1).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new());
-
-                        let mut team2_combination: Vec<&Player> = Vec::new();
-                        println!("\n{} 팀의 스쿼드:", selected_teams[1].team_name());
-                        let mut last_index = 0;
-                        for (index, player) in selected_teams[1].players().iter().enumerate() {
-                            println!("{}. {} (elo: {:.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", index + 1, player.korean_name(), player.elo_rating(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight());
-                            last_index = index;
-                        }
-                        println!("{}. 알 수 없음", last_index + 2);
-                        for i in 0..4 {
-                            loop {
-                                let mut input = String::new();
-                                println!("\n{} 팀의 {}국 {} 기사 번호를 입력하세요:", selected_teams[1].team_name(), i + 1, if i == 0 { "장고(rapid)" } else { "속기(blitz)" });
-                                io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
-                                match input.trim().parse::<usize>() {
-                                    Ok(num) if num > 0 && num <= selected_teams[1].players().len() => {
-                                        team2_combination.push(&selected_teams[1].players()[num - 1]);
-                                        break;
-                                    },
-                                    Ok(num) if num == selected_teams[1].players().len() + 1 => {
-                                        team2_combination.push(&unknown_player);
-                                        break;
-                                    },
-                                    _ => println!("잘못된 입력입니다. 다시 입력해주세요."),
-                                }
-                            }
-                        }
-
-                        let team2_unknown_player_count = team2_combination.iter().filter(|&player| player.english_name() == "unknown").count();
-                        let team2_combinations = [36, 9, 4, 2, 1][team2_unknown_player_count.min(4)];
-
-                        let mut avg_probabilities: Vec<(Lineup, f64, f64, f64, f64, f64, f64, f64, f64)> = Vec::new();
-                        for lineup in &team1_filtered_lineups {
-                            let mut total_win_prob = 0.0;
-                            let mut three_one_prob = 0.0;
-                            let mut four_zero_prob = 0.0;
-                            let mut two_two_prob = 0.0;
-                            let mut first_rapid_win_prob = 0.0;
-                            let mut second_blitz_win_prob = 0.0;
-                            let mut third_blitz_win_prob = 0.0;
-                            let mut forth_blitz_win_prob = 0.0;
-                            let mut count = 0.0;
-
-                            for match_result in &match_results_matrix {
-                                let filtered_results = match_result.iter().filter(|result| 
-                                    team2_combination.iter().enumerate().all(|(index, player)| {
-                                        match index {
-                                            0 => player.english_name() == "unknown" || player.korean_name() == result.first_rapid().player2().korean_name(),
-                                            1 => player.english_name() == "unknown" || player.korean_name() == result.second_blitz().player2().korean_name(),
-                                            2 => player.english_name() == "unknown" || player.korean_name() == result.third_blitz().player2().korean_name(),
-                                            3 => player.english_name() == "unknown" || player.korean_name() == result.forth_blitz().player2().korean_name(),
-                                            _ => false,
-                                        }
-                                    })
-                                );
-
-                                for result in filtered_results.take(team2_combinations) {
-                                    if result.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                    result.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                    result.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                    result.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name() {
-                                        total_win_prob += result.total_win_probability();
-                                        three_one_prob += result.four_zero_probability() + result.three_one_probability();
-                                        four_zero_prob += result.four_zero_probability();
-                                        two_two_prob += result.two_two_probability();
-                                        first_rapid_win_prob += result.first_rapid_win_probability();
-                                        second_blitz_win_prob += result.second_blitz_win_probability();
-                                        third_blitz_win_prob += result.third_blitz_win_probability();
-                                        forth_blitz_win_prob += result.forth_blitz_win_probability();
-                                        count += 1.0;
-                                    }
-                                }
-                            }
-
-                            if count > 0.0 {
-                                avg_probabilities.push((
-                                    lineup.clone(),
-                                    total_win_prob / count,
-                                    three_one_prob / count,
-                                    four_zero_prob / count,
-                                    two_two_prob / count,
-                                    first_rapid_win_prob / count,
-                                    second_blitz_win_prob / count,
-                                    third_blitz_win_prob / count,
-                                    forth_blitz_win_prob / count
-                                ));
-                            }
-                        }
-
-
-                        println!("========================");
-                        avg_probabilities.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-                        if let Some((best_lineup, avg_total_win_prob, avg_win_prob, avg_four_zero_prob, avg_two_two_prob, avg_first_rapid_win_prob, avg_second_blitz_win_prob, avg_third_blitz_win_prob, avg_forth_blitz_win_prob)) = avg_probabilities.first() {
-                            println!("평균 총 승리확률이 가장 높은 라인업");
-                            println!("1국 장고(rapid): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.first_rapid().korean_name(), team2_combination[0].korean_name(), avg_first_rapid_win_prob);
-                            println!("2국 속기(blitz): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.second_blitz().korean_name(), team2_combination[1].korean_name(), avg_second_blitz_win_prob);
-                            println!("3국 속기(blitz): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.third_blitz().korean_name(), team2_combination[2].korean_name(), avg_third_blitz_win_prob);
-                            println!("4국 속기(blitz): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.forth_blitz().korean_name(), team2_combination[3].korean_name(), avg_forth_blitz_win_prob);
-                            println!("\n평균 총 승리확률: {:.2}%", avg_total_win_prob);
-                            println!("평균 동점 없는 승리확률: {:.2}%", avg_win_prob);
-                            println!("평균 완봉승 확률: {:.2}%", avg_four_zero_prob);
-                            println!("평균 동점 확률: {:.2}%", avg_two_two_prob);
-                        } else {
-                            println!("적합한 구성을 찾을 수 없습니다.");
-                        }
-                        println!("========================");
-                        avg_probabilities.sort_by(|a, b| {
-                            if (b.2 - a.2).abs() < 0.001 {
-                                if (b.1 - a.1).abs() < 0.001 {
-                                    std::cmp::Ordering::Equal
-                                } else {
-                                    b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
-                                }
-                            } else {
-                                b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal)
-                            }
-                        });
-                        if let Some((best_lineup, avg_total_win_prob, avg_win_prob, avg_four_zero_prob, avg_two_two_prob, avg_first_rapid_win_prob, avg_second_blitz_win_prob, avg_third_blitz_win_prob, avg_forth_blitz_win_prob)) = avg_probabilities.first() {
-                            println!("평균 동점 없는 승리확률이 가장 높은 라인업");
-                            println!("1국 장고(rapid): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.first_rapid().korean_name(), team2_combination[0].korean_name(), avg_first_rapid_win_prob);
-                            println!("2국 속기(blitz): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.second_blitz().korean_name(), team2_combination[1].korean_name(), avg_second_blitz_win_prob);
-                            println!("3국 속기(blitz): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.third_blitz().korean_name(), team2_combination[2].korean_name(), avg_third_blitz_win_prob);
-                            println!("4국 속기(blitz): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.forth_blitz().korean_name(), team2_combination[3].korean_name(), avg_forth_blitz_win_prob);
-                            println!("\n평균 총 승리확률: {:.2}%", avg_total_win_prob);
-                            println!("평균 동점 없는 승리확률: {:.2}%", avg_win_prob);
-                            println!("평균 완봉승 확률: {:.2}%", avg_four_zero_prob);
-                            println!("평균 동점 확률: {:.2}%", avg_two_two_prob);
-                        } else {
-                            println!("적합한 구성을 찾을 수 없습니다.");
-                        }
-                        println!("========================");
-                        avg_probabilities.sort_by(|a, b| {
-                            if (b.3 - a.3).abs() < 0.001 {
-                                if (b.1 - a.1).abs() < 0.001 {
-                                    std::cmp::Ordering::Equal
-                                } else {
-                                    b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
-                                }
-                            } else {
-                                b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal)
-                            }
-                        });
-                        if let Some((best_lineup, avg_total_win_prob, avg_win_prob, avg_four_zero_prob, avg_two_two_prob, avg_first_rapid_win_prob, avg_second_blitz_win_prob, avg_third_blitz_win_prob, avg_forth_blitz_win_prob)) = avg_probabilities.first() {
-                            println!("평균 완봉승확률이 가장 높은 라인업");
-                            println!("1국 장고(rapid): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.first_rapid().korean_name(), team2_combination[0].korean_name(), avg_first_rapid_win_prob);
-                            println!("2국 속기(blitz): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.second_blitz().korean_name(), team2_combination[1].korean_name(), avg_second_blitz_win_prob);
-                            println!("3국 속기(blitz): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.third_blitz().korean_name(), team2_combination[2].korean_name(), avg_third_blitz_win_prob);
-                            println!("4국 속기(blitz): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.forth_blitz().korean_name(), team2_combination[3].korean_name(), avg_forth_blitz_win_prob);
-                            println!("\n평균 총 승리확률: {:.2}%", avg_total_win_prob);
-                            println!("평균 동점 없는 승리확률: {:.2}%", avg_win_prob);
-                            println!("평균 완봉승 확률: {:.2}%", avg_four_zero_prob);
-                            println!("평균 동점 확률: {:.2}%", avg_two_two_prob);
-                        } else {
-                            println!("적합한 구성을 찾을 수 없습니다.");
-                        }
-                        println!("========================");
-                        avg_probabilities.sort_by(|a, b| {
-                            if (b.4 - a.4).abs() < 0.001 {
-                                if (b.1 - a.1).abs() < 0.001 {
-                                    std::cmp::Ordering::Equal
-                                } else {
-                                    b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
-                                }
-                            } else {
-                                b.4.partial_cmp(&a.4).unwrap_or(std::cmp::Ordering::Equal)
-                            }
-                        });
-                        if let Some((best_lineup, avg_total_win_prob, avg_win_prob, avg_four_zero_prob, avg_two_two_prob, avg_first_rapid_win_prob, avg_second_blitz_win_prob, avg_third_blitz_win_prob, avg_forth_blitz_win_prob)) = avg_probabilities.first() {
-                            println!("평균 동점 확률이 가장 높은 라인업");
-                            println!("1국 장고(rapid): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.first_rapid().korean_name(), team2_combination[0].korean_name(), avg_first_rapid_win_prob);
-                            println!("2국 속기(blitz): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.second_blitz().korean_name(), team2_combination[1].korean_name(), avg_second_blitz_win_prob);
-                            println!("3국 속기(blitz): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.third_blitz().korean_name(), team2_combination[2].korean_name(), avg_third_blitz_win_prob);
-                            println!("4국 속기(blitz): {} vs {} (평균 승리확률: {:.2}%)", best_lineup.forth_blitz().korean_name(), team2_combination[3].korean_name(), avg_forth_blitz_win_prob);
-                            println!("\n평균 총 승리확률: {:.2}%", avg_total_win_prob);
-                            println!("평균 동점 없는 승리확률: {:.2}%", avg_win_prob);
-                            println!("평균 완봉승 확률: {:.2}%", avg_four_zero_prob);
-                            println!("평균 동점 확률: {:.2}%", avg_two_two_prob);
-                        } else {
-                            println!("적합한 구성을 찾을 수 없습니다.");
-                        }
-                        println!("========================");
+                        let team1_filtered_lineups = utils::filter_team1_lineups(&selected_teams, &team1_all_lineups, first_rapid_colour);
+                        if team1_filtered_lineups.is_empty() {
+                            continue;
+                        }
+
+                        let distribution = match read_opponent_distribution(&selected_teams[1], &match_results_matrix) {
+                            Some(distribution) => distribution,
+                            None => continue,
+                        };
+                        let weighted = matches!(distribution, OpponentWeighting::Weights(_));
+                        let (weighting, opponent) = match read_opponent(&selected_teams[1], &match_results_matrix, distribution, first_rapid_colour) {
+                            Some(opponent) => opponent,
+                            None => continue,
+                        };
+                        if weighted {
+                            print_expected_lineups(&selected_teams[1], &match_results_matrix, &weighting.column_weights(&match_results_matrix));
+                        }
+                        let responses = best_response::best_responses(&match_results_matrix, &team1_filtered_lineups, &weighting, Aggregate::Expected, &objective, team1_filtered_lineups.len());
+                        print_average_responses(&match_results_matrix, &responses, Some(&opponent), &objective);
 
                         println!("\n계속하려면 엔터를 누르세요.");
                         let mut pause = String::new();
                         io::stdin().read_line(&mut pause).expect("입력을 읽는 데 실패했습니다.");
                     },
                     "8" => {
-                        let team1_filtered_lineups = utils::filter_team1_lineups(&selected_teams, &team1_all_lineups);
-
-                        let unknown_player = Player::new("알 수 없음".to_string(), "unknown".to_string(), "未知".to_string(), NaiveDate::from_ymd_opt(2000, 1, 1).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new());
-
-                        let mut team2_combination: Vec<&Player> = Vec::new();
-                        println!("\n{} 팀의 스쿼드:", selected_teams[1].team_name());
-                        let mut last_index = 0;
-                        for (index, player) in selected_teams[1].players().iter().enumerate() {
-                            println!("{}. {} (elo: {:.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", index + 1, player.korean_name(), player.elo_rating(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight());
-                            last_index = index;
+                        let team1_filtered_lineups = utils::filter_team1_lineups(&selected_teams, &team1_all_lineups, first_rapid_colour);
+                        if team1_filtered_lineups.is_empty() {
+                            continue;
                         }
-                        println!("{}. 알 수 없음", last_index + 2);
-                        for i in 0..4 {
-                            loop {
-                                let mut input = String::new();
-                                println!("\n{} 팀의 {}국 {} 기사 번호를 입력하세요:", selected_teams[1].team_name(), i + 1, if i == 0 { "장고(rapid)" } else { "속기(blitz)" });
-                                io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
-                                match input.trim().parse::<usize>() {
-                                    Ok(num) if num > 0 && num <= selected_teams[1].players().len() => {
-                                        team2_combination.push(&selected_teams[1].players()[num - 1]);
-                                        break;
-                                    },
-                                    Ok(num) if num == selected_teams[1].players().len() + 1 => {
-                                        team2_combination.push(&unknown_player);
-                                        break;
-                                    },
-                                    _ => println!("잘못된 입력입니다. 다시 입력해주세요."),
-                                }
-                            }
-                        }
-
-                        let mut best_match_result: Option<&MatchResult> = None;
-                        let mut highest_min_total_win_prob = 0.0;
-
-                        for lineup in &team1_filtered_lineups {
-                            let mut min_total_win_prob = std::f64::MAX;
-                            for match_result in &match_results_matrix {
-                                let filtered_results = match_result.iter().filter(|result| 
-                                    team2_combination.iter().enumerate().all(|(index, player)| {
-                                        match index {
-                                            0 => player.english_name() == "unknown" || player.korean_name() == result.first_rapid().player2().korean_name(),
-                                            1 => player.english_name() == "unknown" || player.korean_name() == result.second_blitz().player2().korean_name(),
-                                            2 => player.english_name() == "unknown" || player.korean_name() == result.third_blitz().player2().korean_name(),
-                                            3 => player.english_name() == "unknown" || player.korean_name() == result.forth_blitz().player2().korean_name(),
-                                            _ => false,
-                                        }
-                                    })
-                                );
-                                for result in filtered_results {
-                                    if result.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                    result.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                    result.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                    result.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name() {
-                                        if result.total_win_probability() < min_total_win_prob {
-                                            min_total_win_prob = result.total_win_probability();
-                                        }
-                                    }
-                                }
-                            }
-
-                            if min_total_win_prob != std::f64::MAX && min_total_win_prob > highest_min_total_win_prob {
-                                highest_min_total_win_prob = min_total_win_prob;
-                                best_match_result = match_results_matrix.iter().flatten().find(|&r| 
-                                    r.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                    r.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                    r.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                    r.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name() &&
-                                    (r.total_win_probability() - highest_min_total_win_prob).abs() < std::f64::EPSILON
-                                );
-                            }
-                        }
-
-                        println!("========================");
-                        if let Some(best_result) = best_match_result {
-                            let player1_best_tiebreaker_names: HashSet<String> = best_result.tiebreaker_relativities().iter()
-                                .filter_map(|detail| detail.as_ref())
-                                .map(|detail| detail.player1().korean_name().to_string())
-                                .collect();
-                            let player2_best_tiebreaker_names: HashSet<String> = best_result.tiebreaker_relativities().iter()
-                                .filter_map(|detail| detail.as_ref())
-                                .map(|detail| detail.player2().korean_name().to_string())
-                                .collect();
-                            println!("1국 장고(rapid): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.first_rapid().player1().korean_name(), best_result.first_rapid().player2().korean_name(), best_result.first_rapid().player1_wins(), best_result.first_rapid().player2_wins(), best_result.first_rapid_win_probability());
-                            println!("2국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.second_blitz().player1().korean_name(), best_result.second_blitz().player2().korean_name(), best_result.second_blitz().player1_wins(), best_result.second_blitz().player2_wins(), best_result.second_blitz_win_probability());
-                            println!("3국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.third_blitz().player1().korean_name(), best_result.third_blitz().player2().korean_name(), best_result.third_blitz().player1_wins(), best_result.third_blitz().player2_wins(), best_result.third_blitz_win_probability());
-                            println!("4국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.forth_blitz().player1().korean_name(), best_result.forth_blitz().player2().korean_name(), best_result.forth_blitz().player1_wins(), best_result.forth_blitz().player2_wins(), best_result.forth_blitz_win_probability());
-                            println!("\n4-0: {:.2}%", best_result.four_zero_probability());
-                            println!("3-1: {:.2}%", best_result.three_one_probability());
-                            println!("2-2: {:.2}% => ({}) vs ({}): {:.2}%", best_result.two_two_probability(), player1_best_tiebreaker_names.iter().cloned().collect::<Vec<_>>().join(", "), player2_best_tiebreaker_names.iter().cloned().collect::<Vec<_>>().join(", "), best_result.tiebreaker_win_probability());
-                            println!("1-3: {:.2}%", best_result.one_three_probability());
-                            println!("0-4: {:.2}%", best_result.zero_four_probability());
-                            println!("\n총 승리확률: {:.2}%", best_result.total_win_probability());
-                        } else {
-                            println!("적합한 매치 결과를 찾을 수 없습니다.");
-                        }
-                        println!("========================");
 
+                        // 최악의 경우는 가중치의 크기와 관계없이 가중치가 있는 라인업 가운데에서 고르므로, 예측한 분포는 쓰지 않고 조건만 받습니다.
+                        let (weighting, _) = match read_opponent(&selected_teams[1], &match_results_matrix, OpponentWeighting::Uniform, first_rapid_colour) {
+                            Some(opponent) => opponent,
+                            None => continue,
+                        };
+                        let responses = best_response::best_responses(&match_results_matrix, &team1_filtered_lineups, &weighting, Aggregate::WorstCase, &objective, 1);
+                        print_worst_case_response(&match_results_matrix, responses.first(), &objective);
 
                         println!("\n계속하려면 엔터를 누르세요.");
                         let mut pause = String::new();
                         io::stdin().read_line(&mut pause).expect("입력을 읽는 데 실패했습니다.");
                     },
                     "9" => {
-                        let mut team1_best_lineup: Option<&MatchResult> = None;
-                        let mut team2_best_lineup: Option<&MatchResult> = None;
-                        let mut highest_min_total_win_prob = 0.0;
-                        let mut lowest_max_total_win_prob = 100.0;
-                        let mut team1_lowest_tiebreaker_prob = 100.0;
-                        let mut team2_lowest_tiebreaker_prob = 100.0;
-                        let mut team1_highest_perfect_prob = 0.0;
-                        let mut team2_highest_perfect_prob = 0.0;
-
-                        for lineup in &team1_all_lineups {
-                            let mut min_total_win_prob = std::f64::MAX;
-
-                            for match_result in &match_results_matrix {
-                                for result in match_result.iter() {
-                                    if result.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                    result.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                    result.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                    result.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name() {
-                                        if result.total_win_probability() < min_total_win_prob || (
-                                            result.total_win_probability() == min_total_win_prob &&
-                                            result.two_two_probability() < team2_lowest_tiebreaker_prob
-                                        ) || (
-                                            result.total_win_probability() == min_total_win_prob &&
-                                            result.two_two_probability() == team2_lowest_tiebreaker_prob &&
-                                            result.four_zero_probability() < team2_highest_perfect_prob
-                                        ) {
-                                            min_total_win_prob = result.total_win_probability();
-                                            team1_lowest_tiebreaker_prob = result.two_two_probability();
-                                            team1_highest_perfect_prob = result.four_zero_probability();
-                                        }
-                                    }
-                                }
-                            }
+                        let team1_filtered_lineups = utils::filter_team1_lineups(&selected_teams, &team1_all_lineups, first_rapid_colour);
+                        if team1_filtered_lineups.is_empty() {
+                            continue;
+                        }
+                        let (weighting, _) = match read_opponent(&selected_teams[1], &match_results_matrix, OpponentWeighting::Uniform, first_rapid_colour) {
+                            Some(opponent) => opponent,
+                            None => continue,
+                        };
+                        // 조건을 만족하는 행과 열만으로 게임을 풀고, 균형의 번호를 원래 메트릭스의 번호로 되돌립니다.
+                        let rows: Vec<usize> = team1_filtered_lineups.iter().filter_map(|lineup| best_response::row_of(&match_results_matrix, lineup)).collect();
+                        let columns: Vec<usize> = weighting.column_weights(&match_results_matrix).iter().enumerate().filter(|(_, weight)| **weight > 0.0).map(|(column, _)| column).collect();
+
+                        println!("\n혼합전략 균형 계산 중...");
+                        let payoff: Vec<Vec<f64>> = rows.iter().map(|&row| columns.iter().map(|&column| objective.value(&match_results_matrix[row][column])).collect()).collect();
+                        let mut equilibrium = equilibrium::solve_zero_sum(&payoff);
+                        let mut row_strategy = vec![0.0; match_results_matrix.len()];
+                        for (index, &row) in rows.iter().enumerate() {
+                            row_strategy[row] = equilibrium.row_strategy[index];
+                        }
+                        let mut column_strategy = vec![0.0; match_results_matrix[0].len()];
+                        for (index, &column) in columns.iter().enumerate() {
+                            column_strategy[column] = equilibrium.column_strategy[index];
+                        }
+                        equilibrium.row_strategy = row_strategy;
+                        equilibrium.column_strategy = column_strategy;
 
-                            if (min_total_win_prob != std::f64::MAX && min_total_win_prob > highest_min_total_win_prob) ||
-                            (
-                                min_total_win_prob == highest_min_total_win_prob &&
-                                team1_lowest_tiebreaker_prob < team1_best_lineup.expect("REASON").two_two_probability()
-                            ) || (
-                                min_total_win_prob == highest_min_total_win_prob &&
-                                team1_lowest_tiebreaker_prob == team1_best_lineup.expect("REASON").two_two_probability() &&
-                                team1_highest_perfect_prob > team1_best_lineup.expect("REASON").four_zero_probability()
-                            ) {
-                                highest_min_total_win_prob = min_total_win_prob;
-                                team1_best_lineup = match_results_matrix.iter().flatten().find(|&r| 
-                                    r.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                    r.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                    r.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                    r.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name()
-                                );
-                            }
-                        }
-
-                        for lineup in &team1_all_lineups {
-                            let mut max_total_win_prob = std::f64::MIN;
-
-                            for match_result in &match_results_matrix {
-                                for result in match_result.iter() {
-                                    if result.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                    result.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                    result.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                    result.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name() {
-                                        if result.total_win_probability() > max_total_win_prob || (
-                                            result.total_win_probability() == max_total_win_prob &&
-                                            result.two_two_probability() < team2_lowest_tiebreaker_prob
-                                        ) || (
-                                            result.total_win_probability() == max_total_win_prob &&
-                                            result.two_two_probability() == team2_lowest_tiebreaker_prob &&
-                                            result.four_zero_probability() == team2_highest_perfect_prob
-                                        ) {
-                                            max_total_win_prob = result.total_win_probability();
-                                            team2_lowest_tiebreaker_prob = result.two_two_probability();
-                                            team2_highest_perfect_prob = result.zero_four_probability();
-                                        }
-                                    }
+                        println!("========================");
+                        objective.print_mixed_value(selected_teams[0].team_name(), equilibrium.value);
+                        println!("\n{} 혼합전략 라인업 분포:", selected_teams[0].team_name());
+                        for (row, probability) in equilibrium::support(&equilibrium.row_strategy) {
+                            let result = &match_results_matrix[row][0];
+                            println!("{:.1}%: 1국 {}, 2국 {}, 3국 {}, 4국 {}", probability * 100.0, result.first_rapid().player1().korean_name(), result.second_blitz().player1().korean_name(), result.third_blitz().player1().korean_name(), result.forth_blitz().player1().korean_name());
+                        }
+                        println!("\n{}", objective.column_strategy_title(selected_teams[1].team_name()));
+                        for (column, probability) in equilibrium::support(&equilibrium.column_strategy) {
+                            let result = &match_results_matrix[0][column];
+                            println!("{:.1}%: 1국 {}, 2국 {}, 3국 {}, 4국 {}", probability * 100.0, result.first_rapid().player2().korean_name(), result.second_blitz().player2().korean_name(), result.third_blitz().player2().korean_name(), result.forth_blitz().player2().korean_name());
+                        }
+
+                        // 양팀이 위 분포대로 라인업을 고를 때의 결과별 확률입니다.
+                        let mut outcome_probabilities = [0.0; 5];
+                        for (row, row_probability) in equilibrium::support(&equilibrium.row_strategy) {
+                            for (column, column_probability) in equilibrium::support(&equilibrium.column_strategy) {
+                                let result = &match_results_matrix[row][column];
+                                let outcomes = [result.four_zero_probability(), result.three_one_probability(), result.two_two_probability(), result.one_three_probability(), result.zero_four_probability()];
+                                for (total, outcome) in outcome_probabilities.iter_mut().zip(outcomes) {
+                                    *total += row_probability * column_probability * outcome;
                                 }
                             }
-
-                            if (max_total_win_prob != std::f64::MIN && max_total_win_prob < lowest_max_total_win_prob) || (
-                                max_total_win_prob == lowest_max_total_win_prob &&
-                                team2_lowest_tiebreaker_prob < team2_best_lineup.expect("REASON").two_two_probability()
-                            ) || (
-                                max_total_win_prob == lowest_max_total_win_prob &&
-                                team2_lowest_tiebreaker_prob == team2_best_lineup.expect("REASON").two_two_probability() &&
-                                team2_highest_perfect_prob > team2_best_lineup.expect("REASON").four_zero_probability()
-                            ) {
-                                lowest_max_total_win_prob = max_total_win_prob;
-                                team2_best_lineup = match_results_matrix.iter().flatten().find(|&r| 
-                                    r.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                    r.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                    r.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                    r.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name()
-                                );
-                            }
-                        }
-
-                        let best_match_result1 = match_results_matrix.iter().flatten().find(|&match_result| {
-                            match (team1_best_lineup, team2_best_lineup) {
-                                (Some(team1_lineup), Some(team2_lineup)) => {
-                                    match_result.first_rapid().player1().korean_name() == team1_lineup.first_rapid().player1().korean_name() &&
-                                    match_result.second_blitz().player1().korean_name() == team1_lineup.second_blitz().player1().korean_name() &&
-                                    match_result.third_blitz().player1().korean_name() == team1_lineup.third_blitz().player1().korean_name() &&
-                                    match_result.forth_blitz().player1().korean_name() == team1_lineup.forth_blitz().player1().korean_name() &&
-                                    match_result.first_rapid().player2().korean_name() == team2_lineup.first_rapid().player2().korean_name() &&
-                                    match_result.second_blitz().player2().korean_name() == team2_lineup.second_blitz().player2().korean_name() &&
-                                    match_result.third_blitz().player2().korean_name() == team2_lineup.third_blitz().player2().korean_name() &&
-                                    match_result.forth_blitz().player2().korean_name() == team2_lineup.forth_blitz().player2().korean_name()
-                                },
-                                _ => false,
-                            }
-                        });
-
-                        println!("========================");
-                        if let Some(best_result) = best_match_result1 {
-                            let player1_best_tiebreaker_names: HashSet<String> = best_result.tiebreaker_relativities().iter()
-                                .filter_map(|detail| detail.as_ref())
-                                .map(|detail| detail.player1().korean_name().to_string())
-                                .collect();
-                            let player2_best_tiebreaker_names: HashSet<String> = best_result.tiebreaker_relativities().iter()
-                                .filter_map(|detail| detail.as_ref())
-                                .map(|detail| detail.player2().korean_name().to_string())
-                                .collect();
-                            println!("1국 장고(rapid): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.first_rapid().player1().korean_name(), best_result.first_rapid().player2().korean_name(), best_result.first_rapid().player1_wins(), best_result.first_rapid().player2_wins(), best_result.first_rapid_win_probability());
-                            println!("2국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.second_blitz().player1().korean_name(), best_result.second_blitz().player2().korean_name(), best_result.second_blitz().player1_wins(), best_result.second_blitz().player2_wins(), best_result.second_blitz_win_probability());
-                            println!("3국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.third_blitz().player1().korean_name(), best_result.third_blitz().player2().korean_name(), best_result.third_blitz().player1_wins(), best_result.third_blitz().player2_wins(), best_result.third_blitz_win_probability());
-                            println!("4국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.forth_blitz().player1().korean_name(), best_result.forth_blitz().player2().korean_name(), best_result.forth_blitz().player1_wins(), best_result.forth_blitz().player2_wins(), best_result.forth_blitz_win_probability());
-                            println!("\n4-0: {:.2}%", best_result.four_zero_probability());
-                            println!("3-1: {:.2}%", best_result.three_one_probability());
-                            println!("2-2: {:.2}% => ({}) vs ({}): {:.2}%", best_result.two_two_probability(), player1_best_tiebreaker_names.iter().cloned().collect::<Vec<_>>().join(", "), player2_best_tiebreaker_names.iter().cloned().collect::<Vec<_>>().join(", "), best_result.tiebreaker_win_probability());
-                            println!("1-3: {:.2}%", best_result.one_three_probability());
-                            println!("0-4: {:.2}%", best_result.zero_four_probability());
-                            println!("\n총 승리확률: {:.2}%", best_result.total_win_probability());
-                        } else {
-                            println!("적합한 매치 결과를 찾을 수 없습니다.");
                         }
+                        println!("\n4-0: {:.2}%", outcome_probabilities[0]);
+                        println!("3-1: {:.2}%", outcome_probabilities[1]);
+                        println!("2-2: {:.2}%", outcome_probabilities[2]);
+                        println!("1-3: {:.2}%", outcome_probabilities[3]);
+                        println!("0-4: {:.2}%", outcome_probabilities[4]);
                         println!("========================");
 
-
                         println!("\n계속하려면 엔터를 누르세요.");
                         let mut pause = String::new();
                         io::stdin().read_line(&mut pause).expect("입력을 읽는 데 실패했습니다.");
@@ -1234,16 +512,16 @@ pub fn execute_kbleague() {
                             .collect();
 
                         println!("========================");
-                        println!("1국 장고(rapid): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", match_result.first_rapid().player1().korean_name(), match_result.first_rapid().player2().korean_name(), match_result.first_rapid().player1_wins(), match_result.first_rapid().player2_wins(), match_result.first_rapid_win_probability());
-                        println!("2국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", match_result.second_blitz().player1().korean_name(), match_result.second_blitz().player2().korean_name(), match_result.second_blitz().player1_wins(), match_result.second_blitz().player2_wins(), match_result.second_blitz_win_probability());
-                        println!("3국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", match_result.third_blitz().player1().korean_name(), match_result.third_blitz().player2().korean_name(), match_result.third_blitz().player1_wins(), match_result.third_blitz().player2_wins(), match_result.third_blitz_win_probability());
-                        println!("4국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", match_result.forth_blitz().player1().korean_name(), match_result.forth_blitz().player2().korean_name(), match_result.forth_blitz().player1_wins(), match_result.forth_blitz().player2_wins(), match_result.forth_blitz_win_probability());
+                        println!("1국 장고(rapid): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%, 90% 구간: {})", match_result.first_rapid().player1().korean_name(), match_result.first_rapid().player2().korean_name(), match_result.first_rapid().player1_wins(), match_result.first_rapid().player2_wins(), match_result.first_rapid_win_probability(), uncertainty::format_interval(match_result.first_rapid_win_interval()));
+                        println!("2국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%, 90% 구간: {})", match_result.second_blitz().player1().korean_name(), match_result.second_blitz().player2().korean_name(), match_result.second_blitz().player1_wins(), match_result.second_blitz().player2_wins(), match_result.second_blitz_win_probability(), uncertainty::format_interval(match_result.second_blitz_win_interval()));
+                        println!("3국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%, 90% 구간: {})", match_result.third_blitz().player1().korean_name(), match_result.third_blitz().player2().korean_name(), match_result.third_blitz().player1_wins(), match_result.third_blitz().player2_wins(), match_result.third_blitz_win_probability(), uncertainty::format_interval(match_result.third_blitz_win_interval()));
+                        println!("4국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%, 90% 구간: {})", match_result.forth_blitz().player1().korean_name(), match_result.forth_blitz().player2().korean_name(), match_result.forth_blitz().player1_wins(), match_result.forth_blitz().player2_wins(), match_result.forth_blitz_win_probability(), uncertainty::format_interval(match_result.forth_blitz_win_interval()));
                         println!("\n4-0: {:.2}%", match_result.four_zero_probability());
                         println!("3-1: {:.2}%", match_result.three_one_probability());
                         println!("2-2: {:.2}% => ({}) vs ({}): {:.2}%", match_result.two_two_probability(), player1_best_tiebreaker_names.iter().cloned().collect::<Vec<_>>().join(", "), player2_best_tiebreaker_names.iter().cloned().collect::<Vec<_>>().join(", "), match_result.tiebreaker_win_probability());
                         println!("1-3: {:.2}%", match_result.one_three_probability());
                         println!("0-4: {:.2}%", match_result.zero_four_probability());
-                        println!("\n총 승리확률: {:.2}%", match_result.total_win_probability());
+                        println!("\n총 승리확률: {:.2}% (90% 구간: {})", match_result.total_win_probability(), uncertainty::format_interval(match_result.total_win_interval()));
                         println!("========================");
 
                         println!("\n계속하려면 엔터를 누르세요.");
@@ -1255,6 +533,7 @@ pub fn execute_kbleague() {
                         let team2_combination = utils::select_team_combination(&selected_teams[1]);
 
                         let outcomes = ["WWLL", "WLWL", "WLLW", "LWWL", "LWLW", "LLWW"];
+                        let params = model_params::get();
                         let mut outcome_map: HashMap<&str, Vec<PlayerRelativity>> = HashMap::new();
 
                         for &outcome in outcomes.iter() {
@@ -1271,13 +550,9 @@ pub fn execute_kbleague() {
                                     let player1_position = team1_combination.iter().position(|p| p.korean_name() == relativity.player1().korean_name());
                                     let player2_position = team2_combination.iter().position(|p| p.korean_name() == relativity.player2().korean_name());
                                     let player1_penalty = if let Some(pos) = player1_position {
-                                        let base_penalty = match pos {
-                                            0 => 1.0 / 1.04,
-                                            1 => 1.0 / 1.02,
-                                            _ => 1.0 / 1.08,
-                                        };
+                                        let base_penalty = 1.0 / (1.0 + params.tiebreaker_penalties[pos]);
                                         if defeated_players.contains(&team1_combination[pos]) {
-                                            base_penalty * (1.0 / 1.10)
+                                            base_penalty * (1.0 / (1.0 + params.tiebreaker_defeated_penalty))
                                         } else {
                                             base_penalty
                                         }
@@ -1286,13 +561,9 @@ pub fn execute_kbleague() {
                                     };
 
                                     let player2_penalty = if let Some(pos) = player2_position {
-                                        let base_penalty = match pos {
-                                            0 => 1.04,
-                                            1 => 1.02,
-                                            _ => 1.08,
-                                        };
+                                        let base_penalty = 1.0 + params.tiebreaker_penalties[pos];
                                         if defeated_players.contains(&team2_combination[pos]) {
-                                            base_penalty * 1.10
+                                            base_penalty * (1.0 + params.tiebreaker_defeated_penalty)
                                         } else {
                                             base_penalty
                                         }
@@ -1338,14 +609,7 @@ pub fn execute_kbleague() {
                             result.forth_blitz().player2().korean_name() == team2_combination[3].korean_name()
                         }).expect("매치 결과를 찾을 수 없습니다.");
 
-                        let rt = tokio::runtime::Builder::new_multi_thread()
-                            .enable_all()
-                            .build()
-                            .unwrap();
-
-                        rt.block_on(async {
-                            utils::live_win_ratings(match_result.clone(), player_relativities.clone()).await;
-                        });
+                        pipeline::block_on(utils::live_win_ratings(match_result.clone(), player_relativities.clone()));
                     },
                     "13" => {
                         execute_kbleague_power_ranking();
@@ -1359,8 +623,180 @@ pub fn execute_kbleague() {
     }
 }
 
+// 우측팀의 라인업 조건을 입력받아 가중치에 더합니다. 조건을 만족하는 상대 라인업이 없으면 None입니다.
+fn read_opponent(team: &Team, match_results_matrix: &[Vec<MatchResult>], weighting: OpponentWeighting, first_rapid_colour: Option<bool>) -> Option<(OpponentWeighting, LineupConstraints)> {
+    let constraints = constraints::read_constraints(team, 4);
+    let team2_colour = first_rapid_colour.map(|black| !black);
+    let weighting = OpponentWeighting::Constrained(Box::new(weighting), constraints.clone(), team2_colour);
+    let admitted = weighting.column_weights(match_results_matrix).iter().filter(|weight| **weight > 0.0).count();
+    if constraints.report(team.team_name(), admitted, team2_colour) {
+        Some((weighting, constraints))
+    } else {
+        None
+    }
+}
+
+// 우측팀의 라인업을 모두 같은 확률로 볼지, 지난 KB리그 오더로 예측한 분포나 직접 입력한 분포로 볼지 고릅니다.
+fn read_opponent_distribution(team: &Team, match_results_matrix: &[Vec<MatchResult>]) -> Option<OpponentWeighting> {
+    println!("\n{} 라인업 분포:", team.team_name());
+    println!("1. 모든 라인업을 같은 확률로 보기");
+    println!("2. 지난 KB리그 오더로 예측");
+    println!("3. 라인업별 가중치 직접 입력");
+    let mut source = String::new();
+    io::stdin().read_line(&mut source).expect("입력을 읽는 데 실패했습니다.");
+    match source.trim() {
+        "1" => Some(OpponentWeighting::Uniform),
+        "2" => Some(OpponentWeighting::Weights(predicted_weights(team, match_results_matrix))),
+        "3" => read_opponent_weights(team, match_results_matrix).map(OpponentWeighting::Weights),
+        _ => {
+            println!("잘못된 입력입니다. 다시 입력해주세요.");
+            None
+        }
+    }
+}
+
+// 지난 KB리그 오더로 예측한 출전 확률을 보여주고 라인업 메트릭스의 열 가중치로 바꿉니다.
+fn predicted_weights(team: &Team, match_results_matrix: &[Vec<MatchResult>]) -> Vec<f64> {
+    let model = lineup_prediction::fit(team);
+    println!("\n{}의 지난 KB리그 오더 {}경기로 예측한 출전 확률:", team.team_name(), model.team_matches());
+    for line in model.describe() {
+        println!("{}", line);
+    }
+    match_results_matrix[0].iter().map(|result| {
+        model.probability([result.first_rapid().player2().korean_name().as_str(), result.second_blitz().player2().korean_name().as_str(), result.third_blitz().player2().korean_name().as_str(), result.forth_blitz().player2().korean_name().as_str()])
+    }).collect()
+}
+
+// 상대 라인업을 한 줄에 하나씩 기사 번호 네 개와 가중치로 받아 라인업 메트릭스의 열 가중치로 바꿉니다. 가중치를 준 라인업이 없으면 None입니다.
+fn read_opponent_weights(team: &Team, match_results_matrix: &[Vec<MatchResult>]) -> Option<Vec<f64>> {
+    let mut weights = vec![0.0; match_results_matrix.first().map_or(0, |row| row.len())];
+    println!("\n{} 팀의 스쿼드:", team.team_name());
+    for (index, player) in team.players().iter().enumerate() {
+        println!("{}. {}", index + 1, player.korean_name());
+    }
+    println!("상대 라인업을 한 줄에 하나씩 1~4국 기사 번호와 가중치로 입력하세요 (예: 1 2 3 4 30, 끝내려면 엔터):");
+    loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
+        let fields: Vec<&str> = input.split_whitespace().collect();
+        if fields.is_empty() {
+            break;
+        }
+        let numbers: Vec<usize> = fields.iter().take(4).filter_map(|field| field.parse().ok()).filter(|&number| number > 0 && number <= team.players().len()).collect();
+        let weight = fields.get(4).and_then(|field| field.parse::<f64>().ok()).filter(|weight| *weight > 0.0);
+        let column = match (numbers.len(), weight) {
+            (4, Some(_)) => match_results_matrix[0].iter().position(|result| {
+                [result.first_rapid().player2(), result.second_blitz().player2(), result.third_blitz().player2(), result.forth_blitz().player2()]
+                    .iter()
+                    .zip(&numbers)
+                    .all(|(player, number)| player.korean_name() == team.players()[number - 1].korean_name())
+            }),
+            _ => None,
+        };
+        match (column, weight) {
+            (Some(column), Some(weight)) => weights[column] += weight,
+            _ => println!("잘못된 입력입니다. 다시 입력해주세요."),
+        }
+    }
+    if weights.iter().all(|weight| *weight == 0.0) {
+        println!("가중치를 준 상대 라인업이 없습니다.");
+        return None;
+    }
+    Some(weights)
+}
+
+// 가중치가 큰 상대 라인업 10개를 확률로 바꿔 보여줍니다.
+fn print_expected_lineups(team: &Team, match_results_matrix: &[Vec<MatchResult>], column_weights: &[f64]) {
+    let total_weight: f64 = column_weights.iter().sum();
+    let mut weighted_columns: Vec<usize> = (0..column_weights.len()).filter(|&column| column_weights[column] > 0.0).collect();
+    weighted_columns.sort_by(|a, b| column_weights[*b].total_cmp(&column_weights[*a]));
+    println!("\n{} 예상 라인업 (상위 10개):", team.team_name());
+    for &column in weighted_columns.iter().take(10) {
+        let result = &match_results_matrix[0][column];
+        println!("{:.1}%: 1국 {}, 2국 {}, 3국 {}, 4국 {}", column_weights[column] / total_weight * 100.0, result.first_rapid().player2().korean_name(), result.second_blitz().player2().korean_name(), result.third_blitz().player2().korean_name(), result.forth_blitz().player2().korean_name());
+    }
+}
+
+fn describe_response(match_results_matrix: &[Vec<MatchResult>], response: &LineupResponse, objective: &Objective) -> String {
+    let result = &match_results_matrix[response.row][0];
+    format!("1국 {}, 2국 {}, 3국 {}, 4국 {} (평가값: {}, 총 승리확률: {:.2}%, 3-1: {:.2}%, 완봉승: {:.2}%, 동점: {:.2}%)", result.first_rapid().player1().korean_name(), result.second_blitz().player1().korean_name(), result.third_blitz().player1().korean_name(), result.forth_blitz().player1().korean_name(), objective.format(response.objective_value), response.total_win_probability, response.three_one_probability, response.four_zero_probability, response.two_two_probability)
+}
+
+// 목록은 평가 기준의 값이 큰 순서이므로, 첫 라인업을 자세히 보여주고 나머지는 다음 후보로 보여줍니다.
+fn print_average_responses(match_results_matrix: &[Vec<MatchResult>], responses: &[LineupResponse], opponent: Option<&LineupConstraints>, objective: &Objective) {
+    println!("========================");
+    let response = match responses.first() {
+        Some(response) => response,
+        None => {
+            println!("적합한 구성을 찾을 수 없습니다.");
+            println!("========================");
+            return;
+        }
+    };
+    let result = &match_results_matrix[response.row][0];
+    let players = [result.first_rapid().player1(), result.second_blitz().player1(), result.third_blitz().player1(), result.forth_blitz().player1()];
+    if let Some(opponent) = opponent.filter(|opponent| !opponent.is_empty()) {
+        println!("상대 라인업 조건: {}", opponent.describe().join(", "));
+    }
+    println!("평균 {}이(가) 가장 높은 라인업", objective.label());
+    for (board, label) in ["1국 장고(rapid)", "2국 속기(blitz)", "3국 속기(blitz)", "4국 속기(blitz)"].iter().enumerate() {
+        match opponent {
+            Some(opponent) => println!("{}: {} vs {} (평균 승리확률: {:.2}%)", label, players[board].korean_name(), opponent.fixed_player(board).unwrap_or("알 수 없음"), response.board_win_probabilities[board]),
+            None => println!("{}: {} (평균 승리확률: {:.2}%)", label, players[board].korean_name(), response.board_win_probabilities[board]),
+        }
+    }
+    println!("\n평가값: {}", objective.format(response.objective_value));
+    println!("평균 총 승리확률: {:.2}%", response.total_win_probability);
+    println!("평균 동점 없는 승리확률: {:.2}%", response.outright_win_probability());
+    println!("평균 완봉승 확률: {:.2}%", response.four_zero_probability);
+    println!("평균 동점 확률: {:.2}%", response.two_two_probability);
+    if responses.len() > 1 {
+        println!("\n다음 후보:");
+        for response in responses.iter().skip(1).take(4) {
+            println!("{}", describe_response(match_results_matrix, response, objective));
+        }
+    }
+    println!("========================");
+}
+
+fn print_worst_case_response(match_results_matrix: &[Vec<MatchResult>], response: Option<&LineupResponse>, objective: &Objective) {
+    println!("========================");
+    match response.and_then(|response| response.worst_column.map(|column| &match_results_matrix[response.row][column])) {
+        Some(best_result) => {
+            let player1_best_tiebreaker_names: HashSet<String> = best_result.tiebreaker_relativities().iter()
+                .filter_map(|detail| detail.as_ref())
+                .map(|detail| detail.player1().korean_name().to_string())
+                .collect();
+            let player2_best_tiebreaker_names: HashSet<String> = best_result.tiebreaker_relativities().iter()
+                .filter_map(|detail| detail.as_ref())
+                .map(|detail| detail.player2().korean_name().to_string())
+                .collect();
+            println!("1국 장고(rapid): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%, 90% 구간: {})", best_result.first_rapid().player1().korean_name(), best_result.first_rapid().player2().korean_name(), best_result.first_rapid().player1_wins(), best_result.first_rapid().player2_wins(), best_result.first_rapid_win_probability(), uncertainty::format_interval(best_result.first_rapid_win_interval()));
+            println!("2국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%, 90% 구간: {})", best_result.second_blitz().player1().korean_name(), best_result.second_blitz().player2().korean_name(), best_result.second_blitz().player1_wins(), best_result.second_blitz().player2_wins(), best_result.second_blitz_win_probability(), uncertainty::format_interval(best_result.second_blitz_win_interval()));
+            println!("3국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%, 90% 구간: {})", best_result.third_blitz().player1().korean_name(), best_result.third_blitz().player2().korean_name(), best_result.third_blitz().player1_wins(), best_result.third_blitz().player2_wins(), best_result.third_blitz_win_probability(), uncertainty::format_interval(best_result.third_blitz_win_interval()));
+            println!("4국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%, 90% 구간: {})", best_result.forth_blitz().player1().korean_name(), best_result.forth_blitz().player2().korean_name(), best_result.forth_blitz().player1_wins(), best_result.forth_blitz().player2_wins(), best_result.forth_blitz_win_probability(), uncertainty::format_interval(best_result.forth_blitz_win_interval()));
+            println!("\n4-0: {:.2}%", best_result.four_zero_probability());
+            println!("3-1: {:.2}%", best_result.three_one_probability());
+            println!("2-2: {:.2}% => ({}) vs ({}): {:.2}%", best_result.two_two_probability(), player1_best_tiebreaker_names.iter().cloned().collect::<Vec<_>>().join(", "), player2_best_tiebreaker_names.iter().cloned().collect::<Vec<_>>().join(", "), best_result.tiebreaker_win_probability());
+            println!("1-3: {:.2}%", best_result.one_three_probability());
+            println!("0-4: {:.2}%", best_result.zero_four_probability());
+            println!("\n총 승리확률: {:.2}% (90% 구간: {})", best_result.total_win_probability(), uncertainty::format_interval(best_result.total_win_interval()));
+            println!("최악의 대진에서 {}: {}", objective.label(), objective.format(objective.value(best_result)));
+        },
+        None => println!("적합한 매치 결과를 찾을 수 없습니다."),
+    }
+    println!("========================");
+}
+
 pub fn execute_kbleague_power_ranking() {
-    let teams = init_teams();
+    let teams = match init_teams() {
+        Ok(teams) => teams,
+        Err(e) => {
+            println!("시즌 파일을 불러오는 동안 오류가 발생했습니다: {}", e);
+            return;
+        }
+    };
+    let objective = Objective::current();
     let mut team_relativities_matrix: Vec<Vec<TeamRelativity>> = Vec::new();
 
     for (index1, team1) in teams.iter().enumerate() {
@@ -1398,159 +834,25 @@ pub fn execute_kbleague_power_ranking() {
             }
 
             println!("상대전적을 업데이트 중...");
-            match utils::generate_player_relativities(&selected_teams, false, false) {
+            match utils::generate_player_relativities(&selected_teams, None) {
                 Ok(player_relativities) => {
                     println!("라인업 메트릭스 생성 중...");
-                    let mut match_results_matrix: Vec<Vec<MatchResult>> = Vec::new();
-                    let mut team1_lineups_with_avg: Vec<(Lineup, f64)> = team1_all_lineups.iter().map(|lineup| {
-                        let avg_total_win_probability: f64 = team2_all_lineups.iter().map(|opponent_lineup| {
-                            let match_result = utils::calculate_match_result(lineup.clone(), opponent_lineup.clone(), player_relativities.clone());
-                            match_result.total_win_probability()
-                        }).sum::<f64>() / team2_all_lineups.len() as f64;
-                        (lineup.clone(), avg_total_win_probability)
-                    }).collect();
-
-                    let mut team2_lineups_with_avg: Vec<(Lineup, f64)> = team2_all_lineups.iter().map(|lineup| {
-                        let avg_total_win_probability: f64 = team1_all_lineups.iter().map(|opponent_lineup| {
-                            let match_result = utils::calculate_match_result(opponent_lineup.clone(), lineup.clone(), player_relativities.clone());
-                            match_result.total_win_probability()
-                        }).sum::<f64>() / team1_all_lineups.len() as f64;
-                        (lineup.clone(), avg_total_win_probability)
+                    let match_results_matrix: Vec<Vec<MatchResult>> = team1_all_lineups.iter().map(|team1_lineup| {
+                        team2_all_lineups.iter().map(|team2_lineup| utils::calculate_match_result(team1_lineup.clone(), team2_lineup.clone(), player_relativities.clone())).collect()
                     }).collect();
 
-                    team1_lineups_with_avg.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-                    team2_lineups_with_avg.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-
-                    for (team1_lineup, _) in team1_lineups_with_avg {
-                        let mut row: Vec<MatchResult> = Vec::new();
-                        for (team2_lineup, _) in &team2_lineups_with_avg {
-                            let match_result = utils::calculate_match_result(team1_lineup.clone(), team2_lineup.clone(), player_relativities.clone());
-                            row.push(match_result);
-                        }
-                        match_results_matrix.push(row);
-                    }
-
-                    let mut team1_best_lineup: Option<&MatchResult> = None;
-                    let mut team2_best_lineup: Option<&MatchResult> = None;
-                    let mut highest_min_total_win_prob = 0.0;
-                    let mut lowest_max_total_win_prob = 100.0;
-                    let mut team1_lowest_tiebreaker_prob = 100.0;
-                    let mut team2_lowest_tiebreaker_prob = 100.0;
-                    let mut team1_highest_perfect_prob = 0.0;
-                    let mut team2_highest_perfect_prob = 0.0;
-
-                    for lineup in &team1_all_lineups {
-                        let mut min_total_win_prob = std::f64::MAX;
-
-                        for match_result in &match_results_matrix {
-                            for result in match_result.iter() {
-                                if result.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                result.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                result.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                result.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name() {
-                                    if result.total_win_probability() < min_total_win_prob || (
-                                        result.total_win_probability() == min_total_win_prob &&
-                                        result.two_two_probability() < team2_lowest_tiebreaker_prob
-                                    ) || (
-                                        result.total_win_probability() == min_total_win_prob &&
-                                        result.two_two_probability() == team2_lowest_tiebreaker_prob &&
-                                        result.four_zero_probability() < team2_highest_perfect_prob
-                                    ) {
-                                        min_total_win_prob = result.total_win_probability();
-                                        team1_lowest_tiebreaker_prob = result.two_two_probability();
-                                        team1_highest_perfect_prob = result.four_zero_probability();
-                                    }
-                                }
-                            }
-                        }
-
-                        if (min_total_win_prob != std::f64::MAX && min_total_win_prob > highest_min_total_win_prob) ||
-                        (
-                            min_total_win_prob == highest_min_total_win_prob &&
-                            team1_lowest_tiebreaker_prob < team1_best_lineup.expect("REASON").two_two_probability()
-                        ) || (
-                            min_total_win_prob == highest_min_total_win_prob &&
-                            team1_lowest_tiebreaker_prob == team1_best_lineup.expect("REASON").two_two_probability() &&
-                            team1_highest_perfect_prob > team1_best_lineup.expect("REASON").four_zero_probability()
-                        ) {
-                            highest_min_total_win_prob = min_total_win_prob;
-                            team1_best_lineup = match_results_matrix.iter().flatten().find(|&r| 
-                                r.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                r.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                r.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                r.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name()
-                            );
-                        }
-                    }
-
-                    for lineup in &team1_all_lineups {
-                        let mut max_total_win_prob = std::f64::MIN;
-
-                        for match_result in &match_results_matrix {
-                            for result in match_result.iter() {
-                                if result.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                result.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                result.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                result.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name() {
-                                    if result.total_win_probability() > max_total_win_prob || (
-                                        result.total_win_probability() == max_total_win_prob &&
-                                        result.two_two_probability() < team2_lowest_tiebreaker_prob
-                                    ) || (
-                                        result.total_win_probability() == max_total_win_prob &&
-                                        result.two_two_probability() == team2_lowest_tiebreaker_prob &&
-                                        result.four_zero_probability() == team2_highest_perfect_prob
-                                    ) {
-                                        max_total_win_prob = result.total_win_probability();
-                                        team2_lowest_tiebreaker_prob = result.two_two_probability();
-                                        team2_highest_perfect_prob = result.zero_four_probability();
-                                    }
-                                }
-                            }
-                        }
-
-                        if (max_total_win_prob != std::f64::MIN && max_total_win_prob < lowest_max_total_win_prob) || (
-                            max_total_win_prob == lowest_max_total_win_prob &&
-                            team2_lowest_tiebreaker_prob < team2_best_lineup.expect("REASON").two_two_probability()
-                        ) || (
-                            max_total_win_prob == lowest_max_total_win_prob &&
-                            team2_lowest_tiebreaker_prob == team2_best_lineup.expect("REASON").two_two_probability() &&
-                            team2_highest_perfect_prob > team2_best_lineup.expect("REASON").four_zero_probability()
-                        ) {
-                            lowest_max_total_win_prob = max_total_win_prob;
-                            team2_best_lineup = match_results_matrix.iter().flatten().find(|&r| 
-                                r.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                r.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                r.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                r.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name()
-                            );
-                        }
-                    }
-
-                    let best_match_result1 = match_results_matrix.iter().flatten().find(|&match_result| {
-                        match (team1_best_lineup, team2_best_lineup) {
-                            (Some(team1_lineup), Some(team2_lineup)) => {
-                                match_result.first_rapid().player1().korean_name() == team1_lineup.first_rapid().player1().korean_name() &&
-                                match_result.second_blitz().player1().korean_name() == team1_lineup.second_blitz().player1().korean_name() &&
-                                match_result.third_blitz().player1().korean_name() == team1_lineup.third_blitz().player1().korean_name() &&
-                                match_result.forth_blitz().player1().korean_name() == team1_lineup.forth_blitz().player1().korean_name() &&
-                                match_result.first_rapid().player2().korean_name() == team2_lineup.first_rapid().player2().korean_name() &&
-                                match_result.second_blitz().player2().korean_name() == team2_lineup.second_blitz().player2().korean_name() &&
-                                match_result.third_blitz().player2().korean_name() == team2_lineup.third_blitz().player2().korean_name() &&
-                                match_result.forth_blitz().player2().korean_name() == team2_lineup.forth_blitz().player2().korean_name()
-                            },
-                            _ => false,
-                        }
-                    });
-
-                    if let Some(best_result1) = best_match_result1 {
-                        println!("총 승리확률: {:.2}%", best_result1.total_win_probability());
-                        row.push(TeamRelativity::new(
-                            selected_teams[0].clone(),
-                            selected_teams[1].clone(),
-                            best_result1.total_win_probability()
-                        ));
-                    } else {
-                        println!("적합한 매치 결과를 찾을 수 없습니다.");
+                    // 상대가 가장 불리한 라인업을 낸다고 볼 때 평가 기준으로 가장 좋은 라인업의 대진으로 팀 승률을 정합니다.
+                    let responses = best_response::best_responses(&match_results_matrix, &team1_all_lineups, &OpponentWeighting::Uniform, Aggregate::WorstCase, &objective, 1);
+                    match responses.first().and_then(|response| response.worst_column.map(|column| (response, &match_results_matrix[response.row][column]))) {
+                        Some((response, result)) => {
+                            println!("{}: {}, 총 승리확률: {:.2}% (90% 구간: {})", objective.label(), objective.format(response.objective_value), result.total_win_probability(), uncertainty::format_interval(result.total_win_interval()));
+                            row.push(TeamRelativity::new(
+                                selected_teams[0].clone(),
+                                selected_teams[1].clone(),
+                                result.total_win_probability()
+                            ));
+                        },
+                        None => println!("적합한 매치 결과를 찾을 수 없습니다."),
                     }
                 },
                 Err(e) => println!("상대전적을 생성하는 동안 오류가 발생했습니다: {}", e),
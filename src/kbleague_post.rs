@@ -1,58 +1,55 @@
-use crate::models::{PostLineup, PostMatchResult, Player, Team, TeamRelativity};
+use crate::constraints::{self, LineupConstraints};
+use crate::equilibrium;
+use crate::models::{PostLineup, PostMatchResult, Team, TeamRelativity};
+use crate::objective::{Objective, StartingColour};
+use crate::season;
 use crate::utils;
-use chrono::NaiveDate;
 use rayon::prelude::*;
 use std::collections::HashMap;
 use std::io::{self, Write};
 use indicatif::{ProgressBar, ProgressStyle};
 
-fn init_teams() -> Vec<Team> {
-    let mut teams: Vec<Team> = Vec::new();
-    teams.push(Team::new(
-        "한국물가정보".to_string(),
-        vec![
-            Player::new("강동윤".to_string(), "Kang Dongyun".to_string(), "姜东润".to_string(), NaiveDate::from_ymd_opt(1989, 1, 23).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("한승주".to_string(), "Han Seungjoo".to_string(), "韩升周".to_string(), NaiveDate::from_ymd_opt(1996, 11, 27).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("박민규".to_string(), "Park Minkyu".to_string(), "朴珉奎".to_string(), NaiveDate::from_ymd_opt(1994, 6, 5).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("최재영".to_string(), "Choi Jaeyoung".to_string(), "崔宰荣".to_string(), NaiveDate::from_ymd_opt(1997, 4, 10).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("이춘규".to_string(), "Lee Chungyu".to_string(), "李春揆".to_string(), NaiveDate::from_ymd_opt(1989, 3, 27).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("당이페이".to_string(), "Dang Yifei".to_string(), "党毅飞".to_string(), NaiveDate::from_ymd_opt(1995, 6, 17).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-        ]
-    ));
-    teams.push(Team::new(
-        "수려한합천".to_string(),
-        vec![
-            Player::new("원성진".to_string(), "Weon Seongjin".to_string(), "元晟溱".to_string(), NaiveDate::from_ymd_opt(1985, 7, 15).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("한우진".to_string(), "Han Woojin".to_string(), "韩友赈".to_string(), NaiveDate::from_ymd_opt(2005, 6, 12).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("송지훈".to_string(), "Song Jihoon".to_string(), "宋知勋".to_string(), NaiveDate::from_ymd_opt(1998, 2, 23).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("한태희".to_string(), "Han Taehee".to_string(), "韩态熙".to_string(), NaiveDate::from_ymd_opt(1993, 9, 17).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("윤성식".to_string(), "Yun Seongsik".to_string(), "尹圣植".to_string(), NaiveDate::from_ymd_opt(2000, 6, 25).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("김승진".to_string(), "Kim Seungjin".to_string(), "金升珍".to_string(), NaiveDate::from_ymd_opt(2006, 5, 19).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-        ]
-    ));
-    teams.push(Team::new(
-        "울산 고려아연".to_string(),
-        vec![
-            Player::new("신민준".to_string(), "Shin Minjun".to_string(), "申旻埈".to_string(), NaiveDate::from_ymd_opt(1999, 1, 11).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("이창석".to_string(), "Lee Changseok".to_string(), "李昌锡".to_string(), NaiveDate::from_ymd_opt(1996, 4, 27).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("문민종".to_string(), "Moon Minjong".to_string(), "文敏钟".to_string(), NaiveDate::from_ymd_opt(2003, 2, 12).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("한상조".to_string(), "Han Sangcho".to_string(), "韩相朝".to_string(), NaiveDate::from_ymd_opt(1999, 9, 28).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("김채영".to_string(), "Kim Chaeyoung".to_string(), "金彩瑛".to_string(), NaiveDate::from_ymd_opt(1996, 1, 15).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("랴오위안허".to_string(), "Liao Yuanhe".to_string(), "廖元赫".to_string(), NaiveDate::from_ymd_opt(2000, 12, 20).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-        ]
-    ));
-    teams.push(Team::new(
-        "원익".to_string(),
-        vec![
-            Player::new("박정환".to_string(), "Park Junghwan".to_string(), "朴廷桓".to_string(), NaiveDate::from_ymd_opt(1993, 1, 11).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("이지현(남)".to_string(), "Lee Jihyun (m)".to_string(), "李志贤".to_string(), NaiveDate::from_ymd_opt(1992, 9, 30).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("박영훈".to_string(), "Park Yeonghun".to_string(), "朴永训".to_string(), NaiveDate::from_ymd_opt(1985, 4, 1).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("김진휘".to_string(), "Kim Jinhwi".to_string(), "金真辉".to_string(), NaiveDate::from_ymd_opt(1996, 1, 26).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("금지우".to_string(), "Geum Jiwoo".to_string(), "琴沚玗".to_string(), NaiveDate::from_ymd_opt(2001, 8, 29).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-            Player::new("구쯔하오".to_string(), "Gu Zihao".to_string(), "辜梓豪".to_string(), NaiveDate::from_ymd_opt(1998, 3, 13).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new()),
-        ]
-    ));
-    teams
+fn init_teams() -> Result<Vec<Team>, String> {
+    let season = season::load_current_season()?;
+    if season.postseason_teams().len() < 2 {
+        return Err(format!("{} 시즌 파일에 postseason_teams가 2개 팀 이상 지정되어 있지 않습니다.", season.season()));
+    }
+    Ok(season.postseason_teams().clone())
+}
+
+fn lineup_names(result: &PostMatchResult) -> ([&str; 5], [&str; 5]) {
+    (
+        [result.first_rapid().player1().korean_name().as_str(), result.second_blitz().player1().korean_name().as_str(), result.third_blitz().player1().korean_name().as_str(), result.forth_blitz().player1().korean_name().as_str(), result.fifth_bullet().player1().korean_name().as_str()],
+        [result.first_rapid().player2().korean_name().as_str(), result.second_blitz().player2().korean_name().as_str(), result.third_blitz().player2().korean_name().as_str(), result.forth_blitz().player2().korean_name().as_str(), result.fifth_bullet().player2().korean_name().as_str()],
+    )
+}
+
+// 양팀의 라인업 조건을 입력받습니다. 흑백 어느 쪽이든 조건을 만족하는 라인업이 없는 팀이 있으면 이유를 보여주고 None을 돌려줍니다.
+fn read_post_constraints(selected_teams: &[Team], match_results_matrix: &[Vec<PostMatchResult>]) -> Option<(LineupConstraints, LineupConstraints)> {
+    let team1_constraints = constraints::read_constraints(&selected_teams[0], 5);
+    let team2_constraints = constraints::read_constraints(&selected_teams[1], 5);
+    for black in [true, false] {
+        let team1_admitted = match_results_matrix.iter().filter(|row| team1_constraints.allows(&lineup_names(&row[0]).0, Some(black))).count();
+        let team2_admitted = match_results_matrix[0].iter().filter(|result| team2_constraints.allows(&lineup_names(result).1, Some(!black))).count();
+        if !team1_constraints.report(selected_teams[0].team_name(), team1_admitted, Some(black)) || !team2_constraints.report(selected_teams[1].team_name(), team2_admitted, Some(!black)) {
+            println!("({} 기준 {})", selected_teams[0].team_name(), if black { "흑백흑백흑" } else { "백흑백흑백" });
+            return None;
+        }
+    }
+    Some((team1_constraints, team2_constraints))
+}
+
+// 조건에 맞지 않는 라인업을 고르지 않도록, 좌측팀 라인업이 어긋나면 음의 무한대, 우측팀 라인업이 어긋나면 양의 무한대로 봅니다.
+fn constrained_value(objective: &Objective, result: &PostMatchResult, colour: StartingColour, team1_constraints: &LineupConstraints, team2_constraints: &LineupConstraints) -> f64 {
+    let black = matches!(colour, StartingColour::Black);
+    let (team1_boards, team2_boards) = lineup_names(result);
+    if !team1_constraints.allows(&team1_boards, Some(black)) {
+        f64::NEG_INFINITY
+    } else if !team2_constraints.allows(&team2_boards, Some(!black)) {
+        f64::INFINITY
+    } else {
+        objective.value_post(result, colour)
+    }
 }
 
 pub fn execute_kbleague_post() {
@@ -68,7 +65,13 @@ pub fn execute_kbleague_post() {
 
         match option {
             "1" => {
-                let mut teams = init_teams();
+                let mut teams = match init_teams() {
+                    Ok(teams) => teams,
+                    Err(e) => {
+                        println!("시즌 파일을 불러오는 동안 오류가 발생했습니다: {}", e);
+                        continue;
+                    }
+                };
                 let mut selected_teams: Vec<Team> = Vec::new();
                 for _ in 0..2 {
                     loop {
@@ -113,7 +116,7 @@ pub fn execute_kbleague_post() {
                     loop {
                         println!("\n{} 팀의 스쿼드:", selected_team.team_name());
                         for (index, player) in selected_team.players().iter().enumerate() {
-                            println!("{}. {} (elo: {:.2})", index + 1, player.korean_name(), player.elo_rating());
+                            println!("{}. {} (elo: {:.2} ±{:.0}, {}){}", index + 1, player.korean_name(), player.elo_rating(), player.rating_deviation(), player.describe_rating_source(), player.data_quality_note());
                         }
                         let mut input = String::new();
                         print!("\n제외할 기사를 선택하세요 (완료시 엔터): ");
@@ -143,7 +146,7 @@ pub fn execute_kbleague_post() {
                     println!("\n{} 팀의 기사에 대한 컨디션 가중치를 입력하세요.", selected_team.team_name());
                     loop {
                         for (index, player) in selected_team.players().iter().enumerate() {
-                            println!("{}. {} (elo: {:.2})\n    컨디션 가중치: {:.2}", index + 1, player.korean_name(), player.elo_rating(), player.condition_weight());
+                            println!("{}. {} (elo: {:.2})\n    컨디션 가중치: {:.2} (최근 경기력 {:+.2}: {})", index + 1, player.korean_name(), player.elo_rating(), player.condition_weight(), player.form().weight(), player.form().describe());
                         }
                         println!("컨디션 가중치를 입력할 기사를 선택하세요 (완료시 엔터): ");
                         let mut input = String::new();
@@ -156,6 +159,10 @@ pub fn execute_kbleague_post() {
                         if selected_index > 0 && selected_index <= selected_team.players().len() {
                             let player = &mut selected_team.players_mut()[selected_index - 1];
 
+                            println!("\n{} 기사의 최근 대국 ({}):", player.korean_name(), player.form().describe());
+                            for game in player.form().games() {
+                                println!("    {}", game.describe());
+                            }
                             input.clear();
                             println!("\n{} 기사의 컨디션 가중치를 입력하세요.(음수 입력 가능) (변경하지 않으려면 엔터): ", player.korean_name());
                             io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
@@ -247,7 +254,7 @@ pub fn execute_kbleague_post() {
                 for team in selected_teams.iter() {
                     println!("\n{}:", team.team_name());
                     for player in team.players() {
-                        println!("{} (elo: {:.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2}, 흑번: {:.2}, 백번: {:.2})", player.korean_name(), player.elo_rating(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight(), player.black_weight(), player.white_weight());
+                        println!("{} (elo: {:.2}, 전망: {:+.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2}, 흑번: {:.2}, 백번: {:.2})", player.korean_name(), player.elo_rating(), player.projection().change(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight(), player.black_weight(), player.white_weight());
                         let mut relative_weights: Vec<(&String, f64)> = player.relative_weight().iter().map(|(k, v)| (k, *v)).collect();
                         relative_weights.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
                         for (k, v) in relative_weights {
@@ -315,9 +322,9 @@ pub fn execute_kbleague_post() {
                             println!("2. {}의 스쿼드", selected_teams[1].team_name());
                             println!("3. 양팀의 라인업 메트릭스를 Excel로 출력\n");
                             println!("4. {} 미니맥스 라인업(최선 + 상대 카운터픽)", selected_teams[0].team_name());
-                            println!("5. {} 예상라인업에 대한 {} 카운터픽(미니맥스)(개발중)\n", selected_teams[1].team_name(), selected_teams[0].team_name());
+                            println!("5. {} 예상라인업에 대한 {} 카운터픽(미니맥스, 4번에서 상대 라인업 조건으로 계산)\n", selected_teams[1].team_name(), selected_teams[0].team_name());
 
-                            println!("6. 양측최선 라인업 승리확률(내쉬균형)");
+                            println!("6. 양측최선 라인업 승리확률(내쉬균형, 상수합이 아닌 평가 기준은 최대최소)");
                             println!("7. 지정 라인업 승리확률");
                             println!("8. 에이스 결정전 Excel로 출력(개발중)");
                             println!("9. 실시간 팀 승률(개발중)\n");
@@ -327,18 +334,19 @@ pub fn execute_kbleague_post() {
                             let mut option = String::new();
                             io::stdin().read_line(&mut option).expect("입력을 읽는 데 실패했습니다.");
                             let option = option.trim();
+                            let objective = Objective::current();
 
                             match option {
                                 "1" => {
                                     println!("\n{} 팀의 스쿼드:", selected_teams[0].team_name());
                                     for player in selected_teams[0].players() {
-                                        println!("{} (elo: {:.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", player.korean_name(), player.elo_rating(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight());
+                                        println!("{} (elo: {:.2}, 전망: {:+.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", player.korean_name(), player.elo_rating(), player.projection().change(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight());
                                     }
                                 },
                                 "2" => {
                                     println!("\n{} 팀의 스쿼드:", selected_teams[1].team_name());
                                     for player in selected_teams[1].players() {
-                                        println!("{} (elo: {:.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", player.korean_name(), player.elo_rating(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight());
+                                        println!("{} (elo: {:.2}, 전망: {:+.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", player.korean_name(), player.elo_rating(), player.projection().change(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight());
                                     }
                                 },
                                 "3" => {
@@ -348,6 +356,10 @@ pub fn execute_kbleague_post() {
                                     }
                                 },
                                 "4" => {
+                                    let (team1_constraints, team2_constraints) = match read_post_constraints(&selected_teams, &match_results_matrix) {
+                                        Some(constraints) => constraints,
+                                        None => continue,
+                                    };
                                     let mut random_lineup_min_win_probs: HashMap<String, (f64, f64)> = HashMap::new(); // 라인업 이름을 키로, (최소 백 승리 확률, 최소 흑 승리 확률)을 값으로 저장
                                     let mut lineup_min_win_probs: HashMap<String, (f64, f64)> = HashMap::new(); // 라인업 이름을 키로, (최소 백 승리 확률, 최소 흑 승리 확률)을 값으로 저장
                                     let mut lineup_random_min_win_probs: HashMap<String, f64> = HashMap::new(); // 라인업 이름을 키로, 승리 확률을 값으로 저장
@@ -365,10 +377,12 @@ pub fn execute_kbleague_post() {
                                         );
 
                                         let entry = lineup_min_win_probs.entry(lineup_key.clone()).or_insert((std::f64::MAX, std::f64::MAX));
-                                        let is_new_min_white = entry.0 > match_result.white_started_total_win_probability();
-                                        let is_new_min_black = entry.1 > match_result.black_started_total_win_probability();
-                                        entry.0 = entry.0.min(match_result.white_started_total_win_probability());
-                                        entry.1 = entry.1.min(match_result.black_started_total_win_probability());
+                                        let white_value = constrained_value(&objective, match_result, StartingColour::White, &team1_constraints, &team2_constraints);
+                                        let black_value = constrained_value(&objective, match_result, StartingColour::Black, &team1_constraints, &team2_constraints);
+                                        let is_new_min_white = entry.0 > white_value;
+                                        let is_new_min_black = entry.1 > black_value;
+                                        entry.0 = entry.0.min(white_value);
+                                        entry.1 = entry.1.min(black_value);
 
                                         // 새로운 최소값이 발견되면 해당 match_result를 저장
                                         if is_new_min_white || is_new_min_black {
@@ -457,7 +471,7 @@ pub fn execute_kbleague_post() {
                                                     println!("좌측팀 기준 백흑백흑백");
                                                     println!("4국 속기(blitz): {}", best_white_match_result.forth_blitz().player1().korean_name());
                                                     println!("5국 초속기(bullet): {}", best_white_match_result.fifth_bullet().player1().korean_name());
-                                                    println!("최악의 대진에서 총 승리확률: {:.2}%\n", avg_win_prob);
+                                                    println!("최악의 대진에서 {}: {}\n", objective.label(), objective.format(avg_win_prob));
 
                                                     println!("최선의 오더 후 흑백흑백흑인 경우 최악의 대진일 때");
                                                     println!("1국 흑 장고(rapid): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", 
@@ -609,500 +623,53 @@ pub fn execute_kbleague_post() {
                                     io::stdin().read_line(&mut pause).expect("입력을 읽는 데 실패했습니다.");
                                 },
                                 "5" => {
-                                    // let team1_filtered_lineups = utils::filter_team1_lineups(&selected_teams, &team1_all_lineups);
-
-                                    // let unknown_player = Player::new("알 수 없음".to_string(), "unknown".to_string(), "未知".to_string(), NaiveDate::from_ymd_opt(2000, 1, 1).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new());
-
-                                    // let mut team2_combination: Vec<&Player> = Vec::new();
-                                    // println!("\n{} 팀의 스쿼드:", selected_teams[1].team_name());
-                                    // let mut last_index = 0;
-                                    // for (index, player) in selected_teams[1].players().iter().enumerate() {
-                                    //     println!("{}. {} (elo: {:.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", index + 1, player.korean_name(), player.elo_rating(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight());
-                                    //     last_index = index;
-                                    // }
-                                    // println!("{}. 알 수 없음", last_index + 2);
-                                    // for i in 0..4 {
-                                    //     loop {
-                                    //         let mut input = String::new();
-                                    //         println!("\n{} 팀의 {}국 {} 기사 번호를 입력하세요:", selected_teams[1].team_name(), i + 1, if i == 0 { "장고(rapid)" } else { "속기(blitz)" });
-                                    //         io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
-                                    //         match input.trim().parse::<usize>() {
-                                    //             Ok(num) if num > 0 && num <= selected_teams[1].players().len() => {
-                                    //                 team2_combination.push(&selected_teams[1].players()[num - 1]);
-                                    //                 break;
-                                    //             },
-                                    //             Ok(num) if num == selected_teams[1].players().len() + 1 => {
-                                    //                 team2_combination.push(&unknown_player);
-                                    //                 break;
-                                    //             },
-                                    //             _ => println!("잘못된 입력입니다. 다시 입력해주세요."),
-                                    //         }
-                                    //     }
-                                    // }
-
-                                    // let mut best_match_result: Option<&PostMatchResult> = None;
-                                    // let mut highest_min_total_win_prob = 0.0;
-
-                                    // for lineup in &team1_filtered_lineups {
-                                    //     let mut min_total_win_prob = std::f64::MAX;
-                                    //     for match_result in &match_results_matrix {
-                                    //         let filtered_results = match_result.iter().filter(|result| 
-                                    //             team2_combination.iter().enumerate().all(|(index, player)| {
-                                    //                 match index {
-                                    //                     0 => player.english_name() == "unknown" || player.korean_name() == result.first_rapid().player2().korean_name(),
-                                    //                     1 => player.english_name() == "unknown" || player.korean_name() == result.second_blitz().player2().korean_name(),
-                                    //                     2 => player.english_name() == "unknown" || player.korean_name() == result.third_blitz().player2().korean_name(),
-                                    //                     3 => player.english_name() == "unknown" || player.korean_name() == result.forth_blitz().player2().korean_name(),
-                                    //                     _ => false,
-                                    //                 }
-                                    //             })
-                                    //         );
-                                    //         for result in filtered_results {
-                                    //             if result.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                    //             result.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                    //             result.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                    //             result.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name() {
-                                    //                 if result.total_win_probability() < min_total_win_prob {
-                                    //                     min_total_win_prob = result.total_win_probability();
-                                    //                 }
-                                    //             }
-                                    //         }
-                                    //     }
-
-                                    //     if min_total_win_prob != std::f64::MAX && min_total_win_prob > highest_min_total_win_prob {
-                                    //         highest_min_total_win_prob = min_total_win_prob;
-                                    //         best_match_result = match_results_matrix.iter().flatten().find(|&r| 
-                                    //             r.first_rapid().player1().korean_name() == lineup.first_rapid().korean_name() &&
-                                    //             r.second_blitz().player1().korean_name() == lineup.second_blitz().korean_name() &&
-                                    //             r.third_blitz().player1().korean_name() == lineup.third_blitz().korean_name() &&
-                                    //             r.forth_blitz().player1().korean_name() == lineup.forth_blitz().korean_name() &&
-                                    //             (r.total_win_probability() - highest_min_total_win_prob).abs() < std::f64::EPSILON
-                                    //         );
-                                    //     }
-                                    // }
-
-                                    // println!("========================");
-                                    // if let Some(best_result) = best_match_result {
-                                    //     let player1_best_tiebreaker_names: HashSet<String> = best_result.tiebreaker_relativities().iter()
-                                    //         .filter_map(|detail| detail.as_ref())
-                                    //         .map(|detail| detail.player1().korean_name().to_string())
-                                    //         .collect();
-                                    //     let player2_best_tiebreaker_names: HashSet<String> = best_result.tiebreaker_relativities().iter()
-                                    //         .filter_map(|detail| detail.as_ref())
-                                    //         .map(|detail| detail.player2().korean_name().to_string())
-                                    //         .collect();
-                                    //     println!("1국 장고(rapid): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.first_rapid().player1().korean_name(), best_result.first_rapid().player2().korean_name(), best_result.first_rapid().player1_wins(), best_result.first_rapid().player2_wins(), best_result.first_rapid_win_probability());
-                                    //     println!("2국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.second_blitz().player1().korean_name(), best_result.second_blitz().player2().korean_name(), best_result.second_blitz().player1_wins(), best_result.second_blitz().player2_wins(), best_result.second_blitz_win_probability());
-                                    //     println!("3국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.third_blitz().player1().korean_name(), best_result.third_blitz().player2().korean_name(), best_result.third_blitz().player1_wins(), best_result.third_blitz().player2_wins(), best_result.third_blitz_win_probability());
-                                    //     println!("4국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", best_result.forth_blitz().player1().korean_name(), best_result.forth_blitz().player2().korean_name(), best_result.forth_blitz().player1_wins(), best_result.forth_blitz().player2_wins(), best_result.forth_blitz_win_probability());
-                                    //     println!("\n4-0: {:.2}%", best_result.four_zero_probability());
-                                    //     println!("3-1: {:.2}%", best_result.three_one_probability());
-                                    //     println!("2-2: {:.2}% => ({}) vs ({}): {:.2}%", best_result.two_two_probability(), player1_best_tiebreaker_names.iter().cloned().collect::<Vec<_>>().join(", "), player2_best_tiebreaker_names.iter().cloned().collect::<Vec<_>>().join(", "), best_result.tiebreaker_win_probability());
-                                    //     println!("1-3: {:.2}%", best_result.one_three_probability());
-                                    //     println!("0-4: {:.2}%", best_result.zero_four_probability());
-                                    //     println!("\n총 승리확률: {:.2}%", best_result.total_win_probability());
-                                    // } else {
-                                    //     println!("적합한 매치 결과를 찾을 수 없습니다.");
-                                    // }
-                                    // println!("========================");
-
-
-                                    // println!("\n계속하려면 엔터를 누르세요.");
-                                    // let mut pause = String::new();
-                                    // io::stdin().read_line(&mut pause).expect("입력을 읽는 데 실패했습니다.");
+                                    // 상대 예상 라인업은 라인업 조건으로 나타내므로, 4번의 미니맥스가 곧 예상 라인업에 대한 카운터픽입니다.
+                                    println!("{} 예상라인업은 4번에서 {}의 라인업 조건으로 입력해주세요.", selected_teams[1].team_name(), selected_teams[1].team_name());
                                 },
                                 "6" => {
-                                    let mut team1_random_lineup_min_win_probs: HashMap<String, (f64, f64)> = HashMap::new(); // 라인업 이름을 키로, (최소 백 승리 확률, 최소 흑 승리 확률)을 값으로 저장
-                                    let mut team1_lineup_min_win_probs: HashMap<String, (f64, f64)> = HashMap::new(); // 라인업 이름을 키로, (최소 백 승리 확률, 최소 흑 승리 확률)을 값으로 저장
-                                    let mut team1_lineup_random_min_win_probs: HashMap<String, f64> = HashMap::new(); // 라인업 이름을 키로, 승리 확률을 값으로 저장
-                                    let mut team1_lineup_best_match_results: HashMap<String, &PostMatchResult> = HashMap::new();
-                                    let mut team1_lineup_random_white_best_match_results: HashMap<String, &PostMatchResult> = HashMap::new();
-                                    let mut team1_lineup_random_black_best_match_results: HashMap<String, &PostMatchResult> = HashMap::new();
-                                    let mut team2_random_lineup_min_win_probs: HashMap<String, (f64, f64)> = HashMap::new(); // 라인업 이름을 키로, (최소 백 승리 확률, 최소 흑 승리 확률)을 값으로 저장
-                                    let mut team2_lineup_min_win_probs: HashMap<String, (f64, f64)> = HashMap::new(); // 라인업 이름을 키로, (최소 백 승리 확률, 최소 흑 승리 확률)을 값으로 저장
-                                    let mut team2_lineup_random_min_win_probs: HashMap<String, f64> = HashMap::new(); // 라인업 이름을 키로, 승리 확률을 값으로 저장
-                                    let mut team2_lineup_best_match_results: HashMap<String, &PostMatchResult> = HashMap::new();
-                                    let mut team2_lineup_random_white_best_match_results: HashMap<String, &PostMatchResult> = HashMap::new();
-                                    let mut team2_lineup_random_black_best_match_results: HashMap<String, &PostMatchResult> = HashMap::new();
-
-                                    for match_result in match_results_matrix.iter().flatten() {
-                                        let lineup_key = format!("{}{}{}{}{}", 
-                                            match_result.first_rapid().player2().korean_name(),
-                                            match_result.second_blitz().player2().korean_name(),
-                                            match_result.third_blitz().player2().korean_name(),
-                                            match_result.forth_blitz().player2().korean_name(),
-                                            match_result.fifth_bullet().player2().korean_name()
-                                        );
-
-                                        let entry = team2_lineup_min_win_probs.entry(lineup_key.clone()).or_insert((std::f64::MIN, std::f64::MIN));
-                                        let is_new_min_white = entry.1 < match_result.black_started_total_win_probability();
-                                        let is_new_min_black = entry.0 < match_result.white_started_total_win_probability();
-                                        entry.0 = entry.0.max(match_result.white_started_total_win_probability());
-                                        entry.1 = entry.1.max(match_result.black_started_total_win_probability());
-
-                                        // 새로운 최소값이 발견되면 해당 match_result를 저장
-                                        if is_new_min_white || is_new_min_black {
-                                            team2_lineup_best_match_results.insert(lineup_key, match_result);
-                                        }
-                                    }
-                                    for match_result in match_results_matrix.iter().flatten() {
-                                        let lineup_key = format!("{}{}{}{}{}", 
-                                            match_result.first_rapid().player1().korean_name(),
-                                            match_result.second_blitz().player1().korean_name(),
-                                            match_result.third_blitz().player1().korean_name(),
-                                            match_result.forth_blitz().player1().korean_name(),
-                                            match_result.fifth_bullet().player1().korean_name()
-                                        );
-
-                                        let entry = team1_lineup_min_win_probs.entry(lineup_key.clone()).or_insert((std::f64::MAX, std::f64::MAX));
-                                        let is_new_min_white = entry.0 > match_result.white_started_total_win_probability();
-                                        let is_new_min_black = entry.1 > match_result.black_started_total_win_probability();
-                                        entry.0 = entry.0.min(match_result.white_started_total_win_probability());
-                                        entry.1 = entry.1.min(match_result.black_started_total_win_probability());
-
-                                        // 새로운 최소값이 발견되면 해당 match_result를 저장
-                                        if is_new_min_white || is_new_min_black {
-                                            team1_lineup_best_match_results.insert(lineup_key, match_result);
-                                        }
-                                    }
-                                    let mut team1_best_random_black_lineup_key_temp = &String::new();
-                                    let mut team2_best_random_black_lineup_key_temp = &String::new();
-
-                                    let (team2_best_white_lineup_key, team2_best_black_lineup_key, team2_best_random_white_lineup_key, team2_best_random_black_lineup_key) = team2_lineup_min_win_probs.iter().fold(
-                                        (None, None, None, None),
-                                        |(best_white, best_black, best_random_white, best_random_black), (key, &(white_prob, black_prob))| {
-                                            let best_white = best_white
-                                                .map_or(Some((key, white_prob)), |(current_best_key, current_best_prob): (&String, f64)| {
-                                                    if white_prob < current_best_prob {
-                                                        Some((key, white_prob))
-                                                    } else {
-                                                        Some((current_best_key, current_best_prob))
-                                                    }
-                                                });
-                                            let best_black = best_black
-                                                .map_or(Some((key, black_prob)), |(current_best_key, current_best_prob): (&String, f64)| {
-                                                    if black_prob < current_best_prob {
-                                                        Some((key, black_prob))
-                                                    } else {
-                                                        Some((current_best_key, current_best_prob))
-                                                    }
-                                                });
-                                            let (best_white_random, best_black_random) = best_random_black
-                                                .map_or((Some((key, white_prob)), Some((key, black_prob))), |(current_best_key, current_best_prob): (&String, f64)| {
-                                                    let white_key = key;
-                                                    let team2_best_random_black_lineup_key = team2_lineup_min_win_probs.iter().fold(
-                                                        None,
-                                                        |best_random_white, (key, &(white_prob, black_prob))| {
-                                                            let best_random_white = best_random_white
-                                                                .map_or(Some((key, black_prob)), |(random_black_current_best_key, random_black_current_best_prob): (&String, f64)| {
-                                                                    let black_key = key;
-                                                                    if let Some(best_white_match_result) = team2_lineup_best_match_results.get(white_key) {
-                                                                        if let Some(best_black_match_result) = team2_lineup_best_match_results.get(black_key) {
-                                                                            if best_white_match_result.first_rapid().player2().korean_name() == best_black_match_result.first_rapid().player2().korean_name() &&
-                                                                                best_white_match_result.second_blitz().player2().korean_name() == best_black_match_result.second_blitz().player2().korean_name() &&
-                                                                                best_white_match_result.third_blitz().player2().korean_name() == best_black_match_result.third_blitz().player2().korean_name() && 
-                                                                                black_prob < random_black_current_best_prob {
-                                                                                Some((black_key, black_prob))
-                                                                            } else {
-                                                                                Some((random_black_current_best_key, random_black_current_best_prob))
-                                                                            }
-                                                                        } else {
-                                                                            Some((random_black_current_best_key, random_black_current_best_prob))
-                                                                        }
-                                                                    } else {
-                                                                        Some((random_black_current_best_key, random_black_current_best_prob))
-                                                                    }
-                                                                });
-                                                            best_random_white
-                                                        },
-                                                    );
-
-                                                    if let Some((team2_best_random_black_lineup_key, best_black_prob)) = team2_best_random_black_lineup_key {
-                                                        // 백과 흑의 최선의 대진을 업데이트
-                                                        if ((best_black_prob + white_prob) / 2.0) < current_best_prob {
-                                                            team2_best_random_black_lineup_key_temp = team2_best_random_black_lineup_key;
-                                                            (Some((key, (best_black_prob + white_prob) / 2.0)), Some((team2_best_random_black_lineup_key, (best_black_prob + white_prob) / 2.0)))
-                                                        } else {
-                                                            (Some((current_best_key, current_best_prob)), Some((team2_best_random_black_lineup_key_temp, current_best_prob)))
-                                                        }
-                                                    } else {
-                                                        (Some((current_best_key, current_best_prob)), Some((team2_best_random_black_lineup_key_temp, current_best_prob)))
-                                                    }
-                                                });
-                                            (best_white, best_black, best_white_random, best_black_random)
-                                        },
-                                    );
-                                    let (team1_best_white_lineup_key, team1_best_black_lineup_key, team1_best_random_white_lineup_key, team1_best_random_black_lineup_key) = team1_lineup_min_win_probs.iter().fold(
-                                        (None, None, None, None),
-                                        |(best_white, best_black, best_random_white, best_random_black), (key, &(white_prob, black_prob))| {
-                                            let best_white = best_white
-                                                .map_or(Some((key, white_prob)), |(current_best_key, current_best_prob): (&String, f64)| {
-                                                    if white_prob > current_best_prob {
-                                                        Some((key, white_prob))
-                                                    } else {
-                                                        Some((current_best_key, current_best_prob))
-                                                    }
-                                                });
-                                            let best_black = best_black
-                                                .map_or(Some((key, black_prob)), |(current_best_key, current_best_prob): (&String, f64)| {
-                                                    if black_prob > current_best_prob {
-                                                        Some((key, black_prob))
-                                                    } else {
-                                                        Some((current_best_key, current_best_prob))
-                                                    }
-                                                });
-                                            let (best_white_random, best_black_random) = best_random_white
-                                                .map_or((Some((key, white_prob)), Some((key, black_prob))), |(current_best_key, current_best_prob): (&String, f64)| {
-                                                    let white_key = key;
-                                                    let team1_best_random_black_lineup_key = team1_lineup_min_win_probs.iter().fold(
-                                                        None,
-                                                        |best_random_black, (key, &(white_prob, black_prob))| {
-                                                            let best_random_black = best_random_black
-                                                                .map_or(Some((key, black_prob)), |(random_black_current_best_key, random_black_current_best_prob): (&String, f64)| {
-                                                                    let black_key = key;
-                                                                    if let Some(best_white_match_result) = team1_lineup_best_match_results.get(white_key) {
-                                                                        if let Some(best_black_match_result) = team1_lineup_best_match_results.get(black_key) {
-                                                                            if best_white_match_result.first_rapid().player1().korean_name() == best_black_match_result.first_rapid().player1().korean_name() &&
-                                                                                best_white_match_result.second_blitz().player1().korean_name() == best_black_match_result.second_blitz().player1().korean_name() &&
-                                                                                best_white_match_result.third_blitz().player1().korean_name() == best_black_match_result.third_blitz().player1().korean_name() && 
-                                                                                black_prob > random_black_current_best_prob {
-                                                                                Some((black_key, black_prob))
-                                                                            } else {
-                                                                                Some((random_black_current_best_key, random_black_current_best_prob))
-                                                                            }
-                                                                        } else {
-                                                                            Some((random_black_current_best_key, random_black_current_best_prob))
-                                                                        }
-                                                                    } else {
-                                                                        Some((random_black_current_best_key, random_black_current_best_prob))
-                                                                    }
-                                                                });
-                                                            best_random_black
-                                                        },
-                                                    );
-
-                                                    if let Some((team1_best_random_black_lineup_key, best_black_prob)) = team1_best_random_black_lineup_key {
-                                                        // 백과 흑의 최선의 대진을 업데이트
-                                                        if ((best_black_prob + white_prob) / 2.0) > current_best_prob {
-                                                            team1_best_random_black_lineup_key_temp = team1_best_random_black_lineup_key;
-                                                            (Some((key, (best_black_prob + white_prob) / 2.0)), Some((team1_best_random_black_lineup_key, (best_black_prob + white_prob) / 2.0)))
-                                                        } else {
-                                                            (Some((current_best_key, current_best_prob)), Some((team1_best_random_black_lineup_key_temp, current_best_prob)))
-                                                        }
-                                                    } else {
-                                                        (Some((current_best_key, current_best_prob)), Some((team1_best_random_black_lineup_key_temp, current_best_prob)))
-                                                    }
-                                                });
-                                            (best_white, best_black, best_white_random, best_black_random)
-                                        },
-                                    );
-
-                                    if let Some((team1_best_random_white_lineup_key, _)) = team1_best_random_white_lineup_key {
-                                        if let Some(team1_best_white_match_result) = team1_lineup_best_match_results.get(team1_best_random_white_lineup_key) {
-                                            if let Some((team1_best_random_black_lineup_key, avg_win_prob)) = team1_best_random_black_lineup_key {
-                                                if let Some(team1_best_black_match_result) = team1_lineup_best_match_results.get(team1_best_random_black_lineup_key) {
-                                                    if let Some((team2_best_random_white_lineup_key, _)) = team2_best_random_white_lineup_key {
-                                                        if let Some(team2_best_white_match_result) = team2_lineup_best_match_results.get(team2_best_random_white_lineup_key) {
-                                                            if let Some((team2_best_random_black_lineup_key, avg_win_prob)) = team2_best_random_black_lineup_key {
-                                                                if let Some(team2_best_black_match_result) = team2_lineup_best_match_results.get(team2_best_random_black_lineup_key) {
-                                                                    let black_matching_result = match_results_matrix.iter().flatten().find(|&match_result| {
-                                                                        match_result.first_rapid().player1().korean_name() == team1_best_black_match_result.first_rapid().player1().korean_name() &&
-                                                                        match_result.first_rapid().player2().korean_name() == team2_best_white_match_result.first_rapid().player2().korean_name() &&
-                                                                        match_result.second_blitz().player1().korean_name() == team1_best_black_match_result.second_blitz().player1().korean_name() &&
-                                                                        match_result.second_blitz().player2().korean_name() == team2_best_white_match_result.second_blitz().player2().korean_name() &&
-                                                                        match_result.third_blitz().player1().korean_name() == team1_best_black_match_result.third_blitz().player1().korean_name() &&
-                                                                        match_result.third_blitz().player2().korean_name() == team2_best_white_match_result.third_blitz().player2().korean_name() &&
-                                                                        match_result.forth_blitz().player1().korean_name() == team1_best_black_match_result.forth_blitz().player1().korean_name() &&
-                                                                        match_result.forth_blitz().player2().korean_name() == team2_best_white_match_result.forth_blitz().player2().korean_name() &&
-                                                                        match_result.fifth_bullet().player1().korean_name() == team1_best_black_match_result.fifth_bullet().player1().korean_name() &&
-                                                                        match_result.fifth_bullet().player2().korean_name() == team2_best_white_match_result.fifth_bullet().player2().korean_name()
-                                                                    }).expect("매치 결과를 찾을 수 없습니다.");
-                                                                    let white_matching_result = match_results_matrix.iter().flatten().find(|&match_result| {
-                                                                        match_result.first_rapid().player1().korean_name() == team1_best_white_match_result.first_rapid().player1().korean_name() &&
-                                                                        match_result.first_rapid().player2().korean_name() == team2_best_black_match_result.first_rapid().player2().korean_name() &&
-                                                                        match_result.second_blitz().player1().korean_name() == team1_best_white_match_result.second_blitz().player1().korean_name() &&
-                                                                        match_result.second_blitz().player2().korean_name() == team2_best_black_match_result.second_blitz().player2().korean_name() &&
-                                                                        match_result.third_blitz().player1().korean_name() == team1_best_white_match_result.third_blitz().player1().korean_name() &&
-                                                                        match_result.third_blitz().player2().korean_name() == team2_best_black_match_result.third_blitz().player2().korean_name() &&
-                                                                        match_result.forth_blitz().player1().korean_name() == team1_best_white_match_result.forth_blitz().player1().korean_name() &&
-                                                                        match_result.forth_blitz().player2().korean_name() == team2_best_black_match_result.forth_blitz().player2().korean_name() &&
-                                                                        match_result.fifth_bullet().player1().korean_name() == team1_best_white_match_result.fifth_bullet().player1().korean_name() &&
-                                                                        match_result.fifth_bullet().player2().korean_name() == team2_best_black_match_result.fifth_bullet().player2().korean_name()
-                                                                    }).expect("매치 결과를 찾을 수 없습니다.");
-
-                                                                    println!("흑백을 모르는 경우의 양측최선 오더(내쉬균형)");
-                                                                    println!("1국 장고(rapid): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: 흑:{:.2}%, 백:{:.2}%)",
-                                                                        black_matching_result.first_rapid().player1().korean_name(),
-                                                                        black_matching_result.first_rapid().player2().korean_name(),
-                                                                        black_matching_result.first_rapid().player1_wins(), 
-                                                                        black_matching_result.first_rapid().player2_wins(), 
-                                                                        black_matching_result.first_rapid_black_win_probability(), 
-                                                                        white_matching_result.first_rapid_white_win_probability(), 
-                                                                    );
-                                                                    println!("2국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: 백:{:.2}%, 흑:{:.2}%)",
-                                                                        black_matching_result.second_blitz().player1().korean_name(),
-                                                                        black_matching_result.second_blitz().player2().korean_name(),
-                                                                        black_matching_result.second_blitz().player1_wins(), 
-                                                                        black_matching_result.second_blitz().player2_wins(), 
-                                                                        black_matching_result.second_blitz_white_win_probability(), 
-                                                                        white_matching_result.second_blitz_black_win_probability(), 
-                                                                    );
-                                                                    println!("3국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: 흑:{:.2}%, 백:{:.2}%)",
-                                                                        black_matching_result.third_blitz().player1().korean_name(),
-                                                                        black_matching_result.third_blitz().player2().korean_name(),
-                                                                        black_matching_result.third_blitz().player1_wins(), 
-                                                                        black_matching_result.third_blitz().player2_wins(), 
-                                                                        black_matching_result.third_blitz_black_win_probability(), 
-                                                                        white_matching_result.third_blitz_white_win_probability(), 
-                                                                    );
-                                                                    println!("좌측팀 기준 흑백흑백흑");
-                                                                    println!("4국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)",
-                                                                        black_matching_result.forth_blitz().player1().korean_name(),
-                                                                        black_matching_result.forth_blitz().player2().korean_name(),
-                                                                        black_matching_result.forth_blitz().player1_wins(), 
-                                                                        black_matching_result.forth_blitz().player2_wins(), 
-                                                                        black_matching_result.forth_blitz_white_win_probability(), 
-                                                                    );
-                                                                    println!("5국 초속기(bullet): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)",
-                                                                        black_matching_result.fifth_bullet().player1().korean_name(),
-                                                                        black_matching_result.fifth_bullet().player2().korean_name(),
-                                                                        black_matching_result.fifth_bullet().player1_wins(), 
-                                                                        black_matching_result.fifth_bullet().player2_wins(), 
-                                                                        black_matching_result.fifth_bullet_black_win_probability(), 
-                                                                    );
-                                                                    println!("좌측팀 기준 백흑백흑백");
-                                                                    println!("4국 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)",
-                                                                        white_matching_result.forth_blitz().player1().korean_name(),
-                                                                        white_matching_result.forth_blitz().player2().korean_name(),
-                                                                        white_matching_result.forth_blitz().player1_wins(), 
-                                                                        white_matching_result.forth_blitz().player2_wins(), 
-                                                                        white_matching_result.forth_blitz_black_win_probability(), 
-                                                                    );
-                                                                    println!("5국 초속기(bullet): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)",
-                                                                        white_matching_result.fifth_bullet().player1().korean_name(),
-                                                                        white_matching_result.fifth_bullet().player2().korean_name(),
-                                                                        white_matching_result.fifth_bullet().player1_wins(), 
-                                                                        white_matching_result.fifth_bullet().player2_wins(), 
-                                                                        white_matching_result.fifth_bullet_white_win_probability(), 
-                                                                    );
-                                                                    println!("내쉬균형 일 때 흑백흑백흑 총 승리확률: {:.2}%", black_matching_result.black_started_total_win_probability());
-                                                                    println!("내쉬균형 일 때 백흑백흑백 총 승리확률: {:.2}%\n", white_matching_result.white_started_total_win_probability());
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
+                                    let (team1_constraints, team2_constraints) = match read_post_constraints(&selected_teams, &match_results_matrix) {
+                                        Some(constraints) => constraints,
+                                        None => continue,
+                                    };
+                                    // 흑백을 모르는 경우에는 두 경우가 같은 확률이라고 봅니다.
+                                    let scenarios: [(&str, &[StartingColour]); 3] = [
+                                        ("흑백흑백흑(좌측팀 기준)", &[StartingColour::Black]),
+                                        ("백흑백흑백(좌측팀 기준)", &[StartingColour::White]),
+                                        ("흑백을 모르는 경우", &[StartingColour::Black, StartingColour::White]),
+                                    ];
+                                    for (label, colours) in scenarios {
+                                        // 흑백을 모르는 경우에는 두 경우 모두 조건을 만족하는 라인업만 씁니다.
+                                        let rows: Vec<usize> = (0..match_results_matrix.len()).filter(|&row| {
+                                            let (team1_boards, _) = lineup_names(&match_results_matrix[row][0]);
+                                            colours.iter().all(|colour| team1_constraints.allows(&team1_boards, Some(matches!(colour, StartingColour::Black))))
+                                        }).collect();
+                                        let columns: Vec<usize> = (0..match_results_matrix[0].len()).filter(|&column| {
+                                            let (_, team2_boards) = lineup_names(&match_results_matrix[0][column]);
+                                            colours.iter().all(|colour| team2_constraints.allows(&team2_boards, Some(matches!(colour, StartingColour::White))))
+                                        }).collect();
+                                        if rows.is_empty() || columns.is_empty() {
+                                            println!("\n{}: 양팀의 조건을 모두 만족하는 라인업이 없어 건너뜁니다.", label);
+                                            continue;
                                         }
-                                    }
-
-                                    println!("흑백을 아는 경우");
-                                    if let Some((team1_best_black_lineup_key, _)) = team1_best_black_lineup_key {
-                                        if let Some(team1_best_match_result) = team1_lineup_best_match_results.get(team1_best_black_lineup_key) {
-                                            if let Some((team2_best_black_lineup_key, _)) = team2_best_black_lineup_key {
-                                                if let Some(team2_best_match_result) = team2_lineup_best_match_results.get(team2_best_black_lineup_key) {
-                                                    let black_matching_result = match_results_matrix.iter().flatten().find(|&match_result| {
-                                                        match_result.first_rapid().player1().korean_name() == team1_best_match_result.first_rapid().player1().korean_name() &&
-                                                        match_result.first_rapid().player2().korean_name() == team2_best_match_result.first_rapid().player2().korean_name() &&
-                                                        match_result.second_blitz().player1().korean_name() == team1_best_match_result.second_blitz().player1().korean_name() &&
-                                                        match_result.second_blitz().player2().korean_name() == team2_best_match_result.second_blitz().player2().korean_name() &&
-                                                        match_result.third_blitz().player1().korean_name() == team1_best_match_result.third_blitz().player1().korean_name() &&
-                                                        match_result.third_blitz().player2().korean_name() == team2_best_match_result.third_blitz().player2().korean_name() &&
-                                                        match_result.forth_blitz().player1().korean_name() == team1_best_match_result.forth_blitz().player1().korean_name() &&
-                                                        match_result.forth_blitz().player2().korean_name() == team2_best_match_result.forth_blitz().player2().korean_name() &&
-                                                        match_result.fifth_bullet().player1().korean_name() == team1_best_match_result.fifth_bullet().player1().korean_name() &&
-                                                        match_result.fifth_bullet().player2().korean_name() == team2_best_match_result.fifth_bullet().player2().korean_name()
-                                                    }).expect("매치 결과를 찾을 수 없습니다.");
 
-                                                    println!("흑백흑백흑에서 양측최선 오더(내쉬균형)");
-                                                    println!("1국 흑 장고(rapid): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", 
-                                                        black_matching_result.first_rapid().player1().korean_name(), 
-                                                        black_matching_result.first_rapid().player2().korean_name(), 
-                                                        black_matching_result.first_rapid().player1_wins(), 
-                                                        black_matching_result.first_rapid().player2_wins(), 
-                                                        black_matching_result.first_rapid_black_win_probability());
-                                                    println!("2국 백 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", 
-                                                        black_matching_result.second_blitz().player1().korean_name(), 
-                                                        black_matching_result.second_blitz().player2().korean_name(), 
-                                                        black_matching_result.second_blitz().player1_wins(), 
-                                                        black_matching_result.second_blitz().player2_wins(), 
-                                                        black_matching_result.second_blitz_white_win_probability());
-                                                    println!("3국 흑 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", 
-                                                        black_matching_result.third_blitz().player1().korean_name(), 
-                                                        black_matching_result.third_blitz().player2().korean_name(), 
-                                                        black_matching_result.third_blitz().player1_wins(), 
-                                                        black_matching_result.third_blitz().player2_wins(), 
-                                                        black_matching_result.third_blitz_black_win_probability());
-                                                    println!("4국 백 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", 
-                                                        black_matching_result.forth_blitz().player1().korean_name(), 
-                                                        black_matching_result.forth_blitz().player2().korean_name(), 
-                                                        black_matching_result.forth_blitz().player1_wins(), 
-                                                        black_matching_result.forth_blitz().player2_wins(), 
-                                                        black_matching_result.forth_blitz_white_win_probability());
-                                                    println!("5국 흑 초속기(bullet): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", 
-                                                        black_matching_result.fifth_bullet().player1().korean_name(), 
-                                                        black_matching_result.fifth_bullet().player2().korean_name(), 
-                                                        black_matching_result.fifth_bullet().player1_wins(), 
-                                                        black_matching_result.fifth_bullet().player2_wins(), 
-                                                        black_matching_result.fifth_bullet_black_win_probability());
-                                                    println!("총 승리확률: {:.2}%", black_matching_result.black_started_total_win_probability());
-                                                }
-                                            }
+                                        println!("\n{} 혼합전략 균형 계산 중...", label);
+                                        let payoff: Vec<Vec<f64>> = rows.iter().map(|&row| columns.iter().map(|&column| colours.iter().map(|colour| objective.value_post(&match_results_matrix[row][column], *colour)).sum::<f64>() / colours.len() as f64).collect()).collect();
+                                        let equilibrium = equilibrium::solve_zero_sum(&payoff);
+
+                                        println!("========================");
+                                        println!("{}의 양측최선 오더({})", label, if objective.is_constant_sum() { "내쉬균형" } else { "최대최소" });
+                                        objective.print_mixed_value(selected_teams[0].team_name(), equilibrium.value);
+                                        println!("\n{} 혼합전략 라인업 분포:", selected_teams[0].team_name());
+                                        for (index, probability) in equilibrium::support(&equilibrium.row_strategy) {
+                                            let result = &match_results_matrix[rows[index]][0];
+                                            println!("{:.1}%: 1국 {}, 2국 {}, 3국 {}, 4국 {}, 5국 {}", probability * 100.0, result.first_rapid().player1().korean_name(), result.second_blitz().player1().korean_name(), result.third_blitz().player1().korean_name(), result.forth_blitz().player1().korean_name(), result.fifth_bullet().player1().korean_name());
                                         }
-                                    }
-
-                                    if let Some((team1_best_white_lineup_key, _)) = team1_best_white_lineup_key {
-                                        if let Some(team1_best_match_result) = team1_lineup_best_match_results.get(team1_best_white_lineup_key) {
-                                            if let Some((team2_best_white_lineup_key, _)) = team2_best_white_lineup_key {
-                                                if let Some(team2_best_match_result) = team2_lineup_best_match_results.get(team2_best_white_lineup_key) {
-                                                    let white_matching_result = match_results_matrix.iter().flatten().find(|&match_result| {
-                                                        match_result.first_rapid().player1().korean_name() == team1_best_match_result.first_rapid().player1().korean_name() &&
-                                                        match_result.first_rapid().player2().korean_name() == team2_best_match_result.first_rapid().player2().korean_name() &&
-                                                        match_result.second_blitz().player1().korean_name() == team1_best_match_result.second_blitz().player1().korean_name() &&
-                                                        match_result.second_blitz().player2().korean_name() == team2_best_match_result.second_blitz().player2().korean_name() &&
-                                                        match_result.third_blitz().player1().korean_name() == team1_best_match_result.third_blitz().player1().korean_name() &&
-                                                        match_result.third_blitz().player2().korean_name() == team2_best_match_result.third_blitz().player2().korean_name() &&
-                                                        match_result.forth_blitz().player1().korean_name() == team1_best_match_result.forth_blitz().player1().korean_name() &&
-                                                        match_result.forth_blitz().player2().korean_name() == team2_best_match_result.forth_blitz().player2().korean_name() &&
-                                                        match_result.fifth_bullet().player1().korean_name() == team1_best_match_result.fifth_bullet().player1().korean_name() &&
-                                                        match_result.fifth_bullet().player2().korean_name() == team2_best_match_result.fifth_bullet().player2().korean_name()
-                                                    }).expect("매치 결과를 찾을 수 없습니다.");
-
-                                                    println!("백흑백흑백에서 양측최선 오더(내쉬균형)");
-                                                    println!("1국 백 장고(rapid): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", 
-                                                        white_matching_result.first_rapid().player1().korean_name(), 
-                                                        white_matching_result.first_rapid().player2().korean_name(), 
-                                                        white_matching_result.first_rapid().player1_wins(), 
-                                                        white_matching_result.first_rapid().player2_wins(), 
-                                                        white_matching_result.first_rapid_white_win_probability());
-                                                    println!("2국 흑 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", 
-                                                        white_matching_result.second_blitz().player1().korean_name(), 
-                                                        white_matching_result.second_blitz().player2().korean_name(), 
-                                                        white_matching_result.second_blitz().player1_wins(), 
-                                                        white_matching_result.second_blitz().player2_wins(), 
-                                                        white_matching_result.second_blitz_black_win_probability());
-                                                    println!("3국 백 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", 
-                                                        white_matching_result.third_blitz().player1().korean_name(), 
-                                                        white_matching_result.third_blitz().player2().korean_name(), 
-                                                        white_matching_result.third_blitz().player1_wins(), 
-                                                        white_matching_result.third_blitz().player2_wins(), 
-                                                        white_matching_result.third_blitz_white_win_probability());
-                                                    println!("4국 흑 속기(blitz): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", 
-                                                        white_matching_result.forth_blitz().player1().korean_name(), 
-                                                        white_matching_result.forth_blitz().player2().korean_name(), 
-                                                        white_matching_result.forth_blitz().player1_wins(), 
-                                                        white_matching_result.forth_blitz().player2_wins(), 
-                                                        white_matching_result.forth_blitz_black_win_probability());
-                                                    println!("5국 백 초속기(bullet): {} vs {} (최근3년 상대전적: {}-{}) (승리확률: {:.2}%)", 
-                                                        white_matching_result.fifth_bullet().player1().korean_name(), 
-                                                        white_matching_result.fifth_bullet().player2().korean_name(), 
-                                                        white_matching_result.fifth_bullet().player1_wins(), 
-                                                        white_matching_result.fifth_bullet().player2_wins(), 
-                                                        white_matching_result.fifth_bullet_white_win_probability());
-                                                    println!("총 승리확률: {:.2}%\n", white_matching_result.white_started_total_win_probability());
-                                                }
-                                            }
+                                        println!("\n{}", objective.column_strategy_title(selected_teams[1].team_name()));
+                                        for (index, probability) in equilibrium::support(&equilibrium.column_strategy) {
+                                            let result = &match_results_matrix[0][columns[index]];
+                                            println!("{:.1}%: 1국 {}, 2국 {}, 3국 {}, 4국 {}, 5국 {}", probability * 100.0, result.first_rapid().player2().korean_name(), result.second_blitz().player2().korean_name(), result.third_blitz().player2().korean_name(), result.forth_blitz().player2().korean_name(), result.fifth_bullet().player2().korean_name());
                                         }
+                                        println!("========================");
                                     }
 
                                     println!("\n계속하려면 엔터를 누르세요.");
@@ -1110,20 +677,16 @@ pub fn execute_kbleague_post() {
                                     io::stdin().read_line(&mut pause).expect("입력을 읽는 데 실패했습니다.");
                                 },
                                 "7" => {
-                                    let team1_combination = utils::get_team_combination_post(&selected_teams[0]);
-                                    let team2_combination = utils::get_team_combination_post(&selected_teams[1]);
+                                    let (team1_constraints, team2_constraints) = match read_post_constraints(&selected_teams, &match_results_matrix) {
+                                        Some(constraints) => constraints,
+                                        None => continue,
+                                    };
 
+                                    // 흑백 가운데 한 경우라도 양팀의 조건을 만족하는 대진만 남기고, 흑백별로 맞지 않는 대진은 constrained_value가 거릅니다.
                                     let match_results = match_results_matrix.iter().flatten().filter(|&result| {
-                                        (team1_combination[0].english_name() == "unknown" || result.first_rapid().player1().korean_name() == team1_combination[0].korean_name()) && 
-                                        (team1_combination[1].english_name() == "unknown" || result.second_blitz().player1().korean_name() == team1_combination[1].korean_name()) && 
-                                        (team1_combination[2].english_name() == "unknown" || result.third_blitz().player1().korean_name() == team1_combination[2].korean_name()) && 
-                                        (team1_combination[3].english_name() == "unknown" || result.forth_blitz().player1().korean_name() == team1_combination[3].korean_name()) && 
-                                        (team1_combination[4].english_name() == "unknown" || result.fifth_bullet().player1().korean_name() == team1_combination[4].korean_name()) && 
-                                        (team2_combination[0].english_name() == "unknown" || result.first_rapid().player2().korean_name() == team2_combination[0].korean_name()) && 
-                                        (team2_combination[1].english_name() == "unknown" || result.second_blitz().player2().korean_name() == team2_combination[1].korean_name()) && 
-                                        (team2_combination[2].english_name() == "unknown" || result.third_blitz().player2().korean_name() == team2_combination[2].korean_name()) && 
-                                        (team2_combination[3].english_name() == "unknown" || result.forth_blitz().player2().korean_name() == team2_combination[3].korean_name()) &&
-                                        (team2_combination[4].english_name() == "unknown" || result.fifth_bullet().player2().korean_name() == team2_combination[4].korean_name())
+                                        let (team1_boards, team2_boards) = lineup_names(result);
+                                        [true, false].iter().any(|&black| team1_constraints.allows(&team1_boards, Some(black))) &&
+                                        [true, false].iter().any(|&black| team2_constraints.allows(&team2_boards, Some(black)))
                                     }).collect::<Vec<_>>();
 
                                     let mut team1_random_lineup_min_win_probs: HashMap<String, (f64, f64)> = HashMap::new(); // 라인업 이름을 키로, (최소 백 승리 확률, 최소 흑 승리 확률)을 값으로 저장
@@ -1149,10 +712,12 @@ pub fn execute_kbleague_post() {
                                         );
 
                                         let entry = team2_lineup_min_win_probs.entry(lineup_key.clone()).or_insert((std::f64::MIN, std::f64::MIN));
-                                        let is_new_min_white = entry.1 < match_result.black_started_total_win_probability();
-                                        let is_new_min_black = entry.0 < match_result.white_started_total_win_probability();
-                                        entry.0 = entry.0.max(match_result.white_started_total_win_probability());
-                                        entry.1 = entry.1.max(match_result.black_started_total_win_probability());
+                                        let white_value = constrained_value(&objective, match_result, StartingColour::White, &team1_constraints, &team2_constraints);
+                                        let black_value = constrained_value(&objective, match_result, StartingColour::Black, &team1_constraints, &team2_constraints);
+                                        let is_new_min_white = entry.1 < black_value;
+                                        let is_new_min_black = entry.0 < white_value;
+                                        entry.0 = entry.0.max(white_value);
+                                        entry.1 = entry.1.max(black_value);
 
                                         // 새로운 최소값이 발견되면 해당 match_result를 저장
                                         if is_new_min_white || is_new_min_black {
@@ -1169,10 +734,12 @@ pub fn execute_kbleague_post() {
                                         );
 
                                         let entry = team1_lineup_min_win_probs.entry(lineup_key.clone()).or_insert((std::f64::MAX, std::f64::MAX));
-                                        let is_new_min_white = entry.0 > match_result.white_started_total_win_probability();
-                                        let is_new_min_black = entry.1 > match_result.black_started_total_win_probability();
-                                        entry.0 = entry.0.min(match_result.white_started_total_win_probability());
-                                        entry.1 = entry.1.min(match_result.black_started_total_win_probability());
+                                        let white_value = constrained_value(&objective, match_result, StartingColour::White, &team1_constraints, &team2_constraints);
+                                        let black_value = constrained_value(&objective, match_result, StartingColour::Black, &team1_constraints, &team2_constraints);
+                                        let is_new_min_white = entry.0 > white_value;
+                                        let is_new_min_black = entry.1 > black_value;
+                                        entry.0 = entry.0.min(white_value);
+                                        entry.1 = entry.1.min(black_value);
 
                                         // 새로운 최소값이 발견되면 해당 match_result를 저장
                                         if is_new_min_white || is_new_min_black {
@@ -2425,28 +1992,7 @@ pub fn execute_kbleague_post() {
                                     // }
                                 },
                                 "9" => {
-                                    // let team1_combination = utils::select_team_combination(&selected_teams[0]);
-                                    // let team2_combination = utils::select_team_combination(&selected_teams[1]);
-
-                                    // let match_result = match_results_matrix.iter().flatten().find(|&result| {
-                                    //     result.first_rapid().player1().korean_name() == team1_combination[0].korean_name() && 
-                                    //     result.second_blitz().player1().korean_name() == team1_combination[1].korean_name() && 
-                                    //     result.third_blitz().player1().korean_name() == team1_combination[2].korean_name() && 
-                                    //     result.forth_blitz().player1().korean_name() == team1_combination[3].korean_name() && 
-                                    //     result.first_rapid().player2().korean_name() == team2_combination[0].korean_name() && 
-                                    //     result.second_blitz().player2().korean_name() == team2_combination[1].korean_name() && 
-                                    //     result.third_blitz().player2().korean_name() == team2_combination[2].korean_name() && 
-                                    //     result.forth_blitz().player2().korean_name() == team2_combination[3].korean_name()
-                                    // }).expect("매치 결과를 찾을 수 없습니다.");
-
-                                    // let rt = tokio::runtime::Builder::new_multi_thread()
-                                    //     .enable_all()
-                                    //     .build()
-                                    //     .unwrap();
-
-                                    // rt.block_on(async {
-                                    //     utils::live_win_ratings(match_result.clone(), player_relativities.clone()).await;
-                                    // });
+                                    println!("포스트시즌 실시간 팀 승률은 아직 지원하지 않습니다.");
                                 },
                                 "exit" => break,
                                 _ => println!("잘못된 입력입니다. 다시 입력해주세요."),
@@ -2466,7 +2012,14 @@ pub fn execute_kbleague_post() {
 }
 
 pub fn execute_kbleague_power_ranking() {
-    let teams = init_teams();
+    let teams = match init_teams() {
+        Ok(teams) => teams,
+        Err(e) => {
+            println!("시즌 파일을 불러오는 동안 오류가 발생했습니다: {}", e);
+            return;
+        }
+    };
+    let objective = Objective::current();
     let mut team_relativities_matrix: Vec<Vec<TeamRelativity>> = Vec::new();
 
     for (index1, team1) in teams.iter().enumerate() {
@@ -2510,266 +2063,26 @@ pub fn execute_kbleague_power_ranking() {
             match utils::generate_player_relativities_post(&selected_teams) {
                 Ok(player_relativities) => {
                     println!("라인업 메트릭스 생성 중...");
-                    let mut match_results_matrix: Vec<Vec<PostMatchResult>> = Vec::new();
-                    let mut team1_lineups_with_avg: Vec<(PostLineup, f64)> = team1_all_lineups.iter().map(|lineup| {
-                        let avg_total_win_probability: f64 = team2_all_lineups.iter().map(|opponent_lineup| {
-                            let match_result = utils::calculate_match_result_post(lineup.clone(), opponent_lineup.clone(), player_relativities.clone());
-                            match_result.black_started_total_win_probability() + match_result.white_started_total_win_probability()
-                        }).sum::<f64>() / team2_all_lineups.len() as f64;
-                        (lineup.clone(), avg_total_win_probability)
-                    }).collect();
-
-                    let mut team2_lineups_with_avg: Vec<(PostLineup, f64)> = team2_all_lineups.iter().map(|lineup| {
-                        let avg_total_win_probability: f64 = team1_all_lineups.iter().map(|opponent_lineup| {
-                            let match_result = utils::calculate_match_result_post(opponent_lineup.clone(), lineup.clone(), player_relativities.clone());
-                            match_result.black_started_total_win_probability() + match_result.white_started_total_win_probability()
-                        }).sum::<f64>() / team1_all_lineups.len() as f64;
-                        (lineup.clone(), avg_total_win_probability)
+                    let match_results_matrix: Vec<Vec<PostMatchResult>> = team1_all_lineups.iter().map(|team1_lineup| {
+                        team2_all_lineups.iter().map(|team2_lineup| utils::calculate_match_result_post(team1_lineup.clone(), team2_lineup.clone(), player_relativities.clone())).collect()
                     }).collect();
 
-                    team1_lineups_with_avg.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-                    team2_lineups_with_avg.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-
-                    for (team1_lineup, _) in team1_lineups_with_avg {
-                        let mut row: Vec<PostMatchResult> = Vec::new();
-                        for (team2_lineup, _) in &team2_lineups_with_avg {
-                            let match_result = utils::calculate_match_result_post(team1_lineup.clone(), team2_lineup.clone(), player_relativities.clone());
-                            row.push(match_result);
-                        }
-                        match_results_matrix.push(row);
-                    }
-
-                    let mut team1_random_lineup_min_win_probs: HashMap<String, (f64, f64)> = HashMap::new(); // 라인업 이름을 키로, (최소 백 승리 확률, 최소 흑 승리 확률)을 값으로 저장
-                    let mut team1_lineup_min_win_probs: HashMap<String, (f64, f64)> = HashMap::new(); // 라인업 이름을 키로, (최소 백 승리 확률, 최소 흑 승리 확률)을 값으로 저장
-                    let mut team1_lineup_random_min_win_probs: HashMap<String, f64> = HashMap::new(); // 라인업 이름을 키로, 승리 확률을 값으로 저장
-                    let mut team1_lineup_best_match_results: HashMap<String, &PostMatchResult> = HashMap::new();
-                    let mut team1_lineup_random_white_best_match_results: HashMap<String, &PostMatchResult> = HashMap::new();
-                    let mut team1_lineup_random_black_best_match_results: HashMap<String, &PostMatchResult> = HashMap::new();
-                    let mut team2_random_lineup_min_win_probs: HashMap<String, (f64, f64)> = HashMap::new(); // 라인업 이름을 키로, (최소 백 승리 확률, 최소 흑 승리 확률)을 값으로 저장
-                    let mut team2_lineup_min_win_probs: HashMap<String, (f64, f64)> = HashMap::new(); // 라인업 이름을 키로, (최소 백 승리 확률, 최소 흑 승리 확률)을 값으로 저장
-                    let mut team2_lineup_random_min_win_probs: HashMap<String, f64> = HashMap::new(); // 라인업 이름을 키로, 승리 확률을 값으로 저장
-                    let mut team2_lineup_best_match_results: HashMap<String, &PostMatchResult> = HashMap::new();
-                    let mut team2_lineup_random_white_best_match_results: HashMap<String, &PostMatchResult> = HashMap::new();
-                    let mut team2_lineup_random_black_best_match_results: HashMap<String, &PostMatchResult> = HashMap::new();
-
-                    for match_result in match_results_matrix.iter().flatten() {
-                        let lineup_key = format!("{}{}{}{}{}", 
-                            match_result.first_rapid().player2().korean_name(),
-                            match_result.second_blitz().player2().korean_name(),
-                            match_result.third_blitz().player2().korean_name(),
-                            match_result.forth_blitz().player2().korean_name(),
-                            match_result.fifth_bullet().player2().korean_name()
-                        );
-
-                        let entry = team2_lineup_min_win_probs.entry(lineup_key.clone()).or_insert((std::f64::MIN, std::f64::MIN));
-                        let is_new_min_white = entry.1 < match_result.black_started_total_win_probability();
-                        let is_new_min_black = entry.0 < match_result.white_started_total_win_probability();
-                        entry.0 = entry.0.max(match_result.white_started_total_win_probability());
-                        entry.1 = entry.1.max(match_result.black_started_total_win_probability());
-
-                        // 새로운 최소값이 발견되면 해당 match_result를 저장
-                        if is_new_min_white || is_new_min_black {
-                            team2_lineup_best_match_results.insert(lineup_key, match_result);
-                        }
-                    }
-                    for match_result in match_results_matrix.iter().flatten() {
-                        let lineup_key = format!("{}{}{}{}{}", 
-                            match_result.first_rapid().player1().korean_name(),
-                            match_result.second_blitz().player1().korean_name(),
-                            match_result.third_blitz().player1().korean_name(),
-                            match_result.forth_blitz().player1().korean_name(),
-                            match_result.fifth_bullet().player1().korean_name()
-                        );
-
-                        let entry = team1_lineup_min_win_probs.entry(lineup_key.clone()).or_insert((std::f64::MAX, std::f64::MAX));
-                        let is_new_min_white = entry.0 > match_result.white_started_total_win_probability();
-                        let is_new_min_black = entry.1 > match_result.black_started_total_win_probability();
-                        entry.0 = entry.0.min(match_result.white_started_total_win_probability());
-                        entry.1 = entry.1.min(match_result.black_started_total_win_probability());
-
-                        // 새로운 최소값이 발견되면 해당 match_result를 저장
-                        if is_new_min_white || is_new_min_black {
-                            team1_lineup_best_match_results.insert(lineup_key, match_result);
-                        }
-                    }
-                    let mut team1_best_random_black_lineup_key_temp = &String::new();
-                    let mut team2_best_random_black_lineup_key_temp = &String::new();
-
-                    let (team2_best_white_lineup_key, team2_best_black_lineup_key, team2_best_random_white_lineup_key, team2_best_random_black_lineup_key) = team2_lineup_min_win_probs.iter().fold(
-                        (None, None, None, None),
-                        |(best_white, best_black, best_random_white, best_random_black), (key, &(white_prob, black_prob))| {
-                            let best_white = best_white
-                                .map_or(Some((key, white_prob)), |(current_best_key, current_best_prob): (&String, f64)| {
-                                    if white_prob < current_best_prob {
-                                        Some((key, white_prob))
-                                    } else {
-                                        Some((current_best_key, current_best_prob))
-                                    }
-                                });
-                            let best_black = best_black
-                                .map_or(Some((key, black_prob)), |(current_best_key, current_best_prob): (&String, f64)| {
-                                    if black_prob < current_best_prob {
-                                        Some((key, black_prob))
-                                    } else {
-                                        Some((current_best_key, current_best_prob))
-                                    }
-                                });
-                            let (best_white_random, best_black_random) = best_random_black
-                                .map_or((Some((key, white_prob)), Some((key, black_prob))), |(current_best_key, current_best_prob): (&String, f64)| {
-                                    let white_key = key;
-                                    let team2_best_random_black_lineup_key = team2_lineup_min_win_probs.iter().fold(
-                                        None,
-                                        |best_random_white, (key, &(white_prob, black_prob))| {
-                                            let best_random_white = best_random_white
-                                                .map_or(Some((key, black_prob)), |(random_black_current_best_key, random_black_current_best_prob): (&String, f64)| {
-                                                    let black_key = key;
-                                                    if let Some(best_white_match_result) = team2_lineup_best_match_results.get(white_key) {
-                                                        if let Some(best_black_match_result) = team2_lineup_best_match_results.get(black_key) {
-                                                            if best_white_match_result.first_rapid().player2().korean_name() == best_black_match_result.first_rapid().player2().korean_name() &&
-                                                                best_white_match_result.second_blitz().player2().korean_name() == best_black_match_result.second_blitz().player2().korean_name() &&
-                                                                best_white_match_result.third_blitz().player2().korean_name() == best_black_match_result.third_blitz().player2().korean_name() && 
-                                                                black_prob < random_black_current_best_prob {
-                                                                Some((black_key, black_prob))
-                                                            } else {
-                                                                Some((random_black_current_best_key, random_black_current_best_prob))
-                                                            }
-                                                        } else {
-                                                            Some((random_black_current_best_key, random_black_current_best_prob))
-                                                        }
-                                                    } else {
-                                                        Some((random_black_current_best_key, random_black_current_best_prob))
-                                                    }
-                                                });
-                                            best_random_white
-                                        },
-                                    );
-
-                                    if let Some((team2_best_random_black_lineup_key, best_black_prob)) = team2_best_random_black_lineup_key {
-                                        // 백과 흑의 최선의 대진을 업데이트
-                                        if ((best_black_prob + white_prob) / 2.0) < current_best_prob {
-                                            team2_best_random_black_lineup_key_temp = team2_best_random_black_lineup_key;
-                                            (Some((key, (best_black_prob + white_prob) / 2.0)), Some((team2_best_random_black_lineup_key, (best_black_prob + white_prob) / 2.0)))
-                                        } else {
-                                            (Some((current_best_key, current_best_prob)), Some((team2_best_random_black_lineup_key_temp, current_best_prob)))
-                                        }
-                                    } else {
-                                        (Some((current_best_key, current_best_prob)), Some((team2_best_random_black_lineup_key_temp, current_best_prob)))
-                                    }
-                                });
-                            (best_white, best_black, best_white_random, best_black_random)
+                    // 흑백을 모르므로 두 경우를 같은 확률로 보고, 상대가 가장 불리한 라인업을 낸다고 볼 때 평가 기준으로 가장 좋은 라인업의 대진으로 팀 승률을 정합니다.
+                    let value = |result: &PostMatchResult| (objective.value_post(result, StartingColour::Black) + objective.value_post(result, StartingColour::White)) / 2.0;
+                    let best = match_results_matrix.iter().filter_map(|results| {
+                        results.iter().map(|result| (result, value(result))).min_by(|a, b| a.1.total_cmp(&b.1))
+                    }).max_by(|a, b| a.1.total_cmp(&b.1));
+                    match best {
+                        Some((result, objective_value)) => {
+                            let total_win_probability = (result.black_started_total_win_probability() + result.white_started_total_win_probability()) / 2.0;
+                            println!("{}: {}, 총 승리확률: {:.2}%", objective.label(), objective.format(objective_value), total_win_probability);
+                            row.push(TeamRelativity::new(
+                                selected_teams[0].clone(),
+                                selected_teams[1].clone(),
+                                total_win_probability
+                            ));
                         },
-                    );
-                    let (team1_best_white_lineup_key, team1_best_black_lineup_key, team1_best_random_white_lineup_key, team1_best_random_black_lineup_key) = team1_lineup_min_win_probs.iter().fold(
-                        (None, None, None, None),
-                        |(best_white, best_black, best_random_white, best_random_black), (key, &(white_prob, black_prob))| {
-                            let best_white = best_white
-                                .map_or(Some((key, white_prob)), |(current_best_key, current_best_prob): (&String, f64)| {
-                                    if white_prob > current_best_prob {
-                                        Some((key, white_prob))
-                                    } else {
-                                        Some((current_best_key, current_best_prob))
-                                    }
-                                });
-                            let best_black = best_black
-                                .map_or(Some((key, black_prob)), |(current_best_key, current_best_prob): (&String, f64)| {
-                                    if black_prob > current_best_prob {
-                                        Some((key, black_prob))
-                                    } else {
-                                        Some((current_best_key, current_best_prob))
-                                    }
-                                });
-                            let (best_white_random, best_black_random) = best_random_white
-                                .map_or((Some((key, white_prob)), Some((key, black_prob))), |(current_best_key, current_best_prob): (&String, f64)| {
-                                    let white_key = key;
-                                    let team1_best_random_black_lineup_key = team1_lineup_min_win_probs.iter().fold(
-                                        None,
-                                        |best_random_black, (key, &(white_prob, black_prob))| {
-                                            let best_random_black = best_random_black
-                                                .map_or(Some((key, black_prob)), |(random_black_current_best_key, random_black_current_best_prob): (&String, f64)| {
-                                                    let black_key = key;
-                                                    if let Some(best_white_match_result) = team1_lineup_best_match_results.get(white_key) {
-                                                        if let Some(best_black_match_result) = team1_lineup_best_match_results.get(black_key) {
-                                                            if best_white_match_result.first_rapid().player1().korean_name() == best_black_match_result.first_rapid().player1().korean_name() &&
-                                                                best_white_match_result.second_blitz().player1().korean_name() == best_black_match_result.second_blitz().player1().korean_name() &&
-                                                                best_white_match_result.third_blitz().player1().korean_name() == best_black_match_result.third_blitz().player1().korean_name() && 
-                                                                black_prob > random_black_current_best_prob {
-                                                                Some((black_key, black_prob))
-                                                            } else {
-                                                                Some((random_black_current_best_key, random_black_current_best_prob))
-                                                            }
-                                                        } else {
-                                                            Some((random_black_current_best_key, random_black_current_best_prob))
-                                                        }
-                                                    } else {
-                                                        Some((random_black_current_best_key, random_black_current_best_prob))
-                                                    }
-                                                });
-                                            best_random_black
-                                        },
-                                    );
-
-                                    if let Some((team1_best_random_black_lineup_key, best_black_prob)) = team1_best_random_black_lineup_key {
-                                        // 백과 흑의 최선의 대진을 업데이트
-                                        if ((best_black_prob + white_prob) / 2.0) > current_best_prob {
-                                            team1_best_random_black_lineup_key_temp = team1_best_random_black_lineup_key;
-                                            (Some((key, (best_black_prob + white_prob) / 2.0)), Some((team1_best_random_black_lineup_key, (best_black_prob + white_prob) / 2.0)))
-                                        } else {
-                                            (Some((current_best_key, current_best_prob)), Some((team1_best_random_black_lineup_key_temp, current_best_prob)))
-                                        }
-                                    } else {
-                                        (Some((current_best_key, current_best_prob)), Some((team1_best_random_black_lineup_key_temp, current_best_prob)))
-                                    }
-                                });
-                            (best_white, best_black, best_white_random, best_black_random)
-                        },
-                    );
-
-                    if let Some((team1_best_random_white_lineup_key, _)) = team1_best_random_white_lineup_key {
-                        if let Some(team1_best_white_match_result) = team1_lineup_best_match_results.get(team1_best_random_white_lineup_key) {
-                            if let Some((team1_best_random_black_lineup_key, avg_win_prob)) = team1_best_random_black_lineup_key {
-                                if let Some(team1_best_black_match_result) = team1_lineup_best_match_results.get(team1_best_random_black_lineup_key) {
-                                    if let Some((team2_best_random_white_lineup_key, _)) = team2_best_random_white_lineup_key {
-                                        if let Some(team2_best_white_match_result) = team2_lineup_best_match_results.get(team2_best_random_white_lineup_key) {
-                                            if let Some((team2_best_random_black_lineup_key, avg_win_prob)) = team2_best_random_black_lineup_key {
-                                                if let Some(team2_best_black_match_result) = team2_lineup_best_match_results.get(team2_best_random_black_lineup_key) {
-                                                    let black_matching_result = match_results_matrix.iter().flatten().find(|&match_result| {
-                                                        match_result.first_rapid().player1().korean_name() == team1_best_black_match_result.first_rapid().player1().korean_name() &&
-                                                        match_result.first_rapid().player2().korean_name() == team2_best_white_match_result.first_rapid().player2().korean_name() &&
-                                                        match_result.second_blitz().player1().korean_name() == team1_best_black_match_result.second_blitz().player1().korean_name() &&
-                                                        match_result.second_blitz().player2().korean_name() == team2_best_white_match_result.second_blitz().player2().korean_name() &&
-                                                        match_result.third_blitz().player1().korean_name() == team1_best_black_match_result.third_blitz().player1().korean_name() &&
-                                                        match_result.third_blitz().player2().korean_name() == team2_best_white_match_result.third_blitz().player2().korean_name() &&
-                                                        match_result.forth_blitz().player1().korean_name() == team1_best_black_match_result.forth_blitz().player1().korean_name() &&
-                                                        match_result.forth_blitz().player2().korean_name() == team2_best_white_match_result.forth_blitz().player2().korean_name() &&
-                                                        match_result.fifth_bullet().player1().korean_name() == team1_best_black_match_result.fifth_bullet().player1().korean_name() &&
-                                                        match_result.fifth_bullet().player2().korean_name() == team2_best_white_match_result.fifth_bullet().player2().korean_name()
-                                                    }).expect("매치 결과를 찾을 수 없습니다.");
-                                                    let white_matching_result = match_results_matrix.iter().flatten().find(|&match_result| {
-                                                        match_result.first_rapid().player1().korean_name() == team1_best_white_match_result.first_rapid().player1().korean_name() &&
-                                                        match_result.first_rapid().player2().korean_name() == team2_best_black_match_result.first_rapid().player2().korean_name() &&
-                                                        match_result.second_blitz().player1().korean_name() == team1_best_white_match_result.second_blitz().player1().korean_name() &&
-                                                        match_result.second_blitz().player2().korean_name() == team2_best_black_match_result.second_blitz().player2().korean_name() &&
-                                                        match_result.third_blitz().player1().korean_name() == team1_best_white_match_result.third_blitz().player1().korean_name() &&
-                                                        match_result.third_blitz().player2().korean_name() == team2_best_black_match_result.third_blitz().player2().korean_name() &&
-                                                        match_result.forth_blitz().player1().korean_name() == team1_best_white_match_result.forth_blitz().player1().korean_name() &&
-                                                        match_result.forth_blitz().player2().korean_name() == team2_best_black_match_result.forth_blitz().player2().korean_name() &&
-                                                        match_result.fifth_bullet().player1().korean_name() == team1_best_white_match_result.fifth_bullet().player1().korean_name() &&
-                                                        match_result.fifth_bullet().player2().korean_name() == team2_best_black_match_result.fifth_bullet().player2().korean_name()
-                                                    }).expect("매치 결과를 찾을 수 없습니다.");
-
-                                                    println!("총 승리확률: {:.2}%", (black_matching_result.black_started_total_win_probability() + white_matching_result.white_started_total_win_probability()) / 2.0);
-                                                    row.push(TeamRelativity::new(
-                                                        selected_teams[0].clone(),
-                                                        selected_teams[1].clone(),
-                                                        (black_matching_result.black_started_total_win_probability() + white_matching_result.white_started_total_win_probability()) / 2.0
-                                                    ));
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
+                        None => println!("적합한 매치 결과를 찾을 수 없습니다."),
                     }
                 },
                 Err(e) => println!("상대전적을 생성하는 동안 오류가 발생했습니다: {}", e),
@@ -0,0 +1,144 @@
+use crate::backtest;
+use crate::models::{Player, Team};
+use crate::season;
+use std::collections::HashMap;
+
+// 1~4국과 1~4국에 나오지 않는 경우(에이스 결정전을 위해 아끼거나 쉬는 경우)입니다.
+const SLOTS: usize = 5;
+const BENCH: usize = 4;
+// 기사 기록과 팀 기록에 각각 이 경기 수만큼의 사전분포를 섞습니다. 기록이 적을수록 팀의 지명 순서별 습관과 리그 전체 습관을 따릅니다.
+const PRIOR_MATCHES: f64 = 4.0;
+
+type SlotCounts = [f64; SLOTS];
+
+fn smoothed(counts: &SlotCounts, prior: &SlotCounts) -> SlotCounts {
+    let total: f64 = counts.iter().sum();
+    let mut rates = [0.0; SLOTS];
+    for slot in 0..SLOTS {
+        rates[slot] = (counts[slot] + PRIOR_MATCHES * prior[slot]) / (total + PRIOR_MATCHES);
+    }
+    rates
+}
+
+fn slot_label(slot: usize) -> &'static str {
+    ["1국", "2국", "3국", "4국", "미출전"][slot]
+}
+
+// 기사마다 각 판에 나올 확률이며, 라인업의 확률은 네 판에 나온 기사와 나오지 않은 기사의 확률을 곱한 값에 비례합니다.
+pub struct LineupModel {
+    team_matches: usize,
+    players: Vec<(String, SlotCounts, usize)>,
+    normaliser: f64,
+}
+
+impl LineupModel {
+    fn weight(&self, boards: [&str; 4]) -> f64 {
+        self.players
+            .iter()
+            .map(|(name, rates, _)| match boards.iter().position(|board| board == name) {
+                Some(slot) => rates[slot],
+                None => rates[BENCH],
+            })
+            .product()
+    }
+
+    pub fn probability(&self, boards: [&str; 4]) -> f64 {
+        if self.normaliser > 0.0 {
+            self.weight(boards) / self.normaliser
+        } else {
+            0.0
+        }
+    }
+
+    pub fn team_matches(&self) -> usize {
+        self.team_matches
+    }
+
+    pub fn describe(&self) -> Vec<String> {
+        self.players
+            .iter()
+            .map(|(name, rates, matches)| {
+                let slots: Vec<String> = (0..SLOTS).map(|slot| format!("{} {:.0}%", slot_label(slot), rates[slot] * 100.0)).collect();
+                format!("{}: {} (지난 {}경기)", name, slots.join(", "), matches)
+            })
+            .collect()
+    }
+}
+
+fn is_player(player: &Player, name: &str) -> bool {
+    player.korean_name() == name || player.aliases().iter().any(|alias| alias == name)
+}
+
+// 모든 시즌 파일의 백테스트 경기 파일에서 기사별, 팀의 지명 순서별, 리그 전체의 지명 순서별로 각 판에 나온 횟수를 세어
+// 현재 팀의 명단에 맞춘 모델을 만듭니다. 지명 순서는 시즌 파일의 팀 명단 순서입니다. 읽지 못한 파일은 알리고 건너뜁니다.
+pub fn fit(team: &Team) -> LineupModel {
+    let mut player_counts: HashMap<String, SlotCounts> = HashMap::new();
+    let mut team_rank_counts: HashMap<usize, SlotCounts> = HashMap::new();
+    let mut league_rank_counts: HashMap<usize, SlotCounts> = HashMap::new();
+    let mut team_matches = 0;
+
+    for path in season::list_season_files() {
+        let past_season = match season::load_season(&path) {
+            Ok(past_season) => past_season,
+            Err(e) => {
+                println!("{} 파일을 건너뜁니다: {}", path, e);
+                continue;
+            }
+        };
+        let recorded_lineups = match backtest::recorded_lineups(&past_season) {
+            Ok(recorded_lineups) => recorded_lineups,
+            Err(e) => {
+                println!("{} {} 시즌의 백테스트 경기 파일을 건너뜁니다: {}", past_season.league_name(), past_season.season(), e);
+                continue;
+            }
+        };
+        for (team_name, boards) in recorded_lineups {
+            let roster = match past_season.teams().iter().find(|past_team| past_team.team_name() == &team_name) {
+                Some(past_team) => past_team.players(),
+                None => continue,
+            };
+            let same_team = &team_name == team.team_name();
+            if same_team {
+                team_matches += 1;
+            }
+            for (rank, player) in roster.iter().enumerate() {
+                let slot = boards.iter().position(|board| is_player(player, board)).unwrap_or(BENCH);
+                player_counts.entry(player.korean_name().clone()).or_default()[slot] += 1.0;
+                league_rank_counts.entry(rank).or_default()[slot] += 1.0;
+                if same_team {
+                    team_rank_counts.entry(rank).or_default()[slot] += 1.0;
+                }
+            }
+        }
+    }
+
+    // 명단이 네 명보다 적으면 미출전 확률이 음수가 되지 않도록 네 명으로 봅니다.
+    let roster_size = (team.players().len() as f64).max(4.0);
+    let uniform: SlotCounts = [1.0 / roster_size, 1.0 / roster_size, 1.0 / roster_size, 1.0 / roster_size, (roster_size - 4.0) / roster_size];
+    let players: Vec<(String, SlotCounts, usize)> = team
+        .players()
+        .iter()
+        .enumerate()
+        .map(|(rank, player)| {
+            let league_rates = smoothed(league_rank_counts.get(&rank).unwrap_or(&[0.0; SLOTS]), &uniform);
+            let team_rates = smoothed(team_rank_counts.get(&rank).unwrap_or(&[0.0; SLOTS]), &league_rates);
+            let counts = player_counts.get(player.korean_name()).copied().unwrap_or([0.0; SLOTS]);
+            (player.korean_name().clone(), smoothed(&counts, &team_rates), counts.iter().sum::<f64>() as usize)
+        })
+        .collect();
+
+    let mut model = LineupModel { team_matches, players, normaliser: 0.0 };
+    let names: Vec<&str> = team.players().iter().map(|player| player.korean_name().as_str()).collect();
+    let mut normaliser = 0.0;
+    for first in &names {
+        for second in names.iter().filter(|name| *name != first) {
+            for third in names.iter().filter(|name| *name != first && *name != second) {
+                for forth in names.iter().filter(|name| *name != first && *name != second && *name != third) {
+                    normaliser += model.weight([first, second, third, forth]);
+                }
+            }
+        }
+    }
+    model.normaliser = normaliser;
+    model
+}
@@ -1,11 +1,36 @@
+mod backtest;
+mod best_response;
+mod cache;
+mod config;
+mod constraints;
+mod conversion;
+mod equilibrium;
+mod error;
+mod events;
+mod fixtures;
+mod form;
 mod kbleague;
 mod kbleague_post;
+mod lineup_prediction;
+mod model_params;
 mod models;
+mod objective;
+mod pipeline;
+mod projection;
+mod rating_archive;
+mod rating_source;
+mod records;
+mod registry;
+mod season;
+mod uncertainty;
 mod utils;
+mod whr;
 
 use std::io;
 
 fn main() {
+    // 설정 파일에 문제가 있으면 메뉴보다 먼저 알립니다.
+    config::get();
     loop {
         println!("1. KB바둑리그");
         println!("2. KB바둑리그 포스트시즌");
@@ -13,6 +38,14 @@ fn main() {
         println!("4. 여자바둑리그 파워랭킹(개발중)");
         println!("5. 시니어바둑리그(개발중)");
         println!("6. 시니어바둑리그 파워랭킹(개발중)");
+        println!("7. 시즌 파일 선택 (현재: {})", config::get().season_file);
+        println!("8. HTTP 캐시 관리");
+        println!("9. 대국 기록 데이터베이스");
+        println!("10. 레이팅 아카이브");
+        println!("11. 기사 식별 정보 관리");
+        println!("12. 오프라인 재현 데이터");
+        println!("13. 백테스트");
+        println!("14. 라인업 평가 기준 (현재: {})", objective::Objective::current().label());
         println!("exit. 종료");
 
         let mut option = String::new();
@@ -26,6 +59,30 @@ fn main() {
             "2" => {
                 kbleague_post::execute_kbleague_post();
             },
+            "7" => {
+                season::select_season_file();
+            },
+            "8" => {
+                cache::execute_cache_menu();
+            },
+            "9" => {
+                records::execute_records_menu();
+            },
+            "10" => {
+                rating_archive::execute_rating_archive_menu();
+            },
+            "11" => {
+                registry::execute_registry_menu();
+            },
+            "12" => {
+                fixtures::execute_fixture_menu();
+            },
+            "13" => {
+                backtest::execute_backtest_menu();
+            },
+            "14" => {
+                objective::select_objective();
+            },
             "exit" => break,
             _ => println!("잘못된 입력입니다. 다시 입력해주세요."),
         }
@@ -0,0 +1,200 @@
+use crate::config;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+// 모델 상수입니다. 기본값은 손으로 정한 값이며, 백테스트 메뉴의 '모델 상수 맞추기'로 지난 경기 결과에 맞춘 값을 파일로 저장합니다.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelParams {
+    // 맞출 때마다 1씩 올라갑니다. 0은 기본값입니다.
+    pub version: u32,
+    pub fitted_season: Option<String>,
+    pub fitted_at: Option<String>,
+    pub log_loss: Option<f64>,
+    // nowcast_rating의 기본 K 계수
+    pub recent_record_k: f64,
+    pub team_league_recent_record_k: f64,
+    // KB리그 가중치의 K 계수
+    pub kb_league_k: f64,
+    // get_relative_and_color_weight의 경과일당 가중치
+    pub speed_slope: f64,
+    pub color_slope: f64,
+    pub team_league_color_slope: f64,
+    pub speed_color_slope: f64,
+    pub team_league_speed_color_slope: f64,
+    pub relative_slope: f64,
+    pub team_league_relative_slope: f64,
+    // 배태일 랭킹에 없는 기사의 상대 가중치 배율
+    pub unrated_relative_multiplier: f64,
+    // 1국(장고)과 5국(초속기)의 흑백 가중치 배율
+    pub rapid_color_multiplier: f64,
+    pub bullet_color_multiplier: f64,
+    // 1~4국에 출전한 기사가 에이스 결정전에 나올 때의 불이익
+    pub tiebreaker_penalties: [f64; 4],
+    pub tiebreaker_defeated_penalty: f64,
+    // baeteil_to_goratings 환산식
+    pub baeteil_offset: f64,
+    pub baeteil_curve_start: f64,
+    pub baeteil_curve_a: f64,
+    pub baeteil_curve_b: f64,
+    pub baeteil_curve_c: f64,
+    // 최근 경기력으로 계산하는 컨디션 가중치
+    pub form_performance_k: f64,
+    pub form_streak_points: f64,
+    pub form_activity_points: f64,
+    pub form_expected_games_per_week: f64,
+    // 나이와 레이팅 추세로 내다보는 레이팅 변화. 전성기 나이 전에는 1년에 (전성기 - 나이) * growth만큼 오르고, 이후에는 (나이 - 전성기) * decline만큼 내려갑니다.
+    pub projection_peak_age: f64,
+    pub projection_growth_per_year: f64,
+    pub projection_decline_per_year: f64,
+    // 이 개월 수만큼의 레이팅 기록이 있으면 나이 기준과 최근 추세를 반씩 반영합니다.
+    pub projection_trend_prior_months: f64,
+}
+
+impl Default for ModelParams {
+    fn default() -> Self {
+        ModelParams {
+            version: 0,
+            fitted_season: None,
+            fitted_at: None,
+            log_loss: None,
+            recent_record_k: 1.0,
+            team_league_recent_record_k: 2.0,
+            kb_league_k: 8.0,
+            speed_slope: 0.0035,
+            color_slope: 0.002,
+            team_league_color_slope: 0.004,
+            speed_color_slope: 0.004,
+            team_league_speed_color_slope: 0.002,
+            relative_slope: 0.015,
+            team_league_relative_slope: 0.025,
+            unrated_relative_multiplier: 2.0,
+            rapid_color_multiplier: 0.5,
+            bullet_color_multiplier: 1.5,
+            tiebreaker_penalties: [0.04, 0.02, 0.08, 0.08],
+            tiebreaker_defeated_penalty: 0.10,
+            baeteil_offset: 6050.0,
+            baeteil_curve_start: 9250.0,
+            baeteil_curve_a: 740.332659,
+            baeteil_curve_b: 0.0946919155,
+            baeteil_curve_c: 8456.81141,
+            form_performance_k: 4.0,
+            form_streak_points: 2.0,
+            form_activity_points: 5.0,
+            form_expected_games_per_week: 0.5,
+            projection_peak_age: 26.0,
+            projection_growth_per_year: 6.0,
+            projection_decline_per_year: 2.0,
+            projection_trend_prior_months: 12.0,
+        }
+    }
+}
+
+impl ModelParams {
+    // 맞출 수 있는 상수의 이름, 위치, 한 번에 바꾸는 폭의 배율입니다. 환산식은 값이 조금만 바뀌어도 레이팅이 크게 움직이므로 폭을 줄이고,
+    // 시작점은 곡선의 모양을 정하는 값이므로 맞추지 않습니다.
+    pub fn fit_targets(&mut self) -> Vec<(&'static str, &mut f64, f64)> {
+        let [penalty1, penalty2, penalty3, penalty4] = &mut self.tiebreaker_penalties;
+        vec![
+            ("recent_record_k", &mut self.recent_record_k, 1.0),
+            ("team_league_recent_record_k", &mut self.team_league_recent_record_k, 1.0),
+            ("kb_league_k", &mut self.kb_league_k, 1.0),
+            ("speed_slope", &mut self.speed_slope, 1.0),
+            ("color_slope", &mut self.color_slope, 1.0),
+            ("team_league_color_slope", &mut self.team_league_color_slope, 1.0),
+            ("speed_color_slope", &mut self.speed_color_slope, 1.0),
+            ("team_league_speed_color_slope", &mut self.team_league_speed_color_slope, 1.0),
+            ("relative_slope", &mut self.relative_slope, 1.0),
+            ("team_league_relative_slope", &mut self.team_league_relative_slope, 1.0),
+            ("unrated_relative_multiplier", &mut self.unrated_relative_multiplier, 1.0),
+            ("rapid_color_multiplier", &mut self.rapid_color_multiplier, 1.0),
+            ("bullet_color_multiplier", &mut self.bullet_color_multiplier, 1.0),
+            ("tiebreaker_penalties[0]", penalty1, 1.0),
+            ("tiebreaker_penalties[1]", penalty2, 1.0),
+            ("tiebreaker_penalties[2]", penalty3, 1.0),
+            ("tiebreaker_penalties[3]", penalty4, 1.0),
+            ("tiebreaker_defeated_penalty", &mut self.tiebreaker_defeated_penalty, 1.0),
+            ("baeteil_offset", &mut self.baeteil_offset, 0.05),
+            ("baeteil_curve_a", &mut self.baeteil_curve_a, 0.05),
+            ("baeteil_curve_b", &mut self.baeteil_curve_b, 0.1),
+            ("baeteil_curve_c", &mut self.baeteil_curve_c, 0.02),
+            ("form_performance_k", &mut self.form_performance_k, 1.0),
+            ("form_streak_points", &mut self.form_streak_points, 1.0),
+            ("form_activity_points", &mut self.form_activity_points, 1.0),
+            ("projection_peak_age", &mut self.projection_peak_age, 0.2),
+            ("projection_growth_per_year", &mut self.projection_growth_per_year, 1.0),
+            ("projection_decline_per_year", &mut self.projection_decline_per_year, 1.0),
+            ("projection_trend_prior_months", &mut self.projection_trend_prior_months, 1.0),
+        ]
+    }
+
+    pub fn describe(&self) -> String {
+        match (&self.fitted_season, &self.fitted_at, self.log_loss) {
+            (Some(season), Some(fitted_at), Some(log_loss)) => format!("{}번째 모델 상수 ({} 시즌으로 {}에 맞춤, log-loss {:.4})", self.version, season, fitted_at, log_loss),
+            _ => "기본 모델 상수".to_string(),
+        }
+    }
+}
+
+fn load() -> ModelParams {
+    let path = config::get().model_params_file;
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(_) => return ModelParams::default(),
+    };
+    serde_json::from_str(&text).unwrap_or_else(|e| {
+        println!("경고: 모델 상수 파일 {}의 형식이 올바르지 않아 기본 모델 상수를 사용합니다: {}", path, e);
+        ModelParams::default()
+    })
+}
+
+fn params_lock() -> &'static RwLock<ModelParams> {
+    static PARAMS: OnceLock<RwLock<ModelParams>> = OnceLock::new();
+    PARAMS.get_or_init(|| RwLock::new(load()))
+}
+
+pub fn get() -> ModelParams {
+    params_lock().read().unwrap().clone()
+}
+
+// 저장하지 않고 이번 실행에서만 사용할 상수를 바꿉니다. 상수를 맞추는 동안 후보 값을 시험할 때 사용합니다.
+pub fn set(params: ModelParams) {
+    *params_lock().write().unwrap() = params;
+}
+
+// 후보 상수를 시험하는 동안 사용합니다. 시험 도중 오류로 빠져나가더라도 사라질 때 원래 상수로 되돌립니다.
+pub struct TrialParams {
+    original: ModelParams,
+}
+
+impl TrialParams {
+    pub fn begin() -> TrialParams {
+        TrialParams { original: get() }
+    }
+
+    pub fn original(&self) -> &ModelParams {
+        &self.original
+    }
+}
+
+impl Drop for TrialParams {
+    fn drop(&mut self) {
+        set(self.original.clone());
+    }
+}
+
+// 이전 파일은 버전 번호를 붙여 남겨 두고 새 상수를 저장합니다.
+pub fn save(params: &ModelParams) -> Result<(), Box<dyn Error>> {
+    let path = config::get().model_params_file;
+    if Path::new(&path).exists() {
+        let previous = load();
+        let backup = Path::new(&path).with_extension(format!("v{}.json", previous.version));
+        fs::copy(&path, backup)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(params)?)?;
+    set(params.clone());
+    Ok(())
+}
@@ -1,6 +1,50 @@
+use crate::uncertainty;
 use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+// 판의 종류입니다. 1국은 장고, 2~4국은 속기, 에이스 결정전은 초속기입니다.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BoardKind {
+    Rapid,
+    Blitz,
+    Bullet,
+}
+
+impl BoardKind {
+    pub fn all() -> [BoardKind; 3] {
+        [BoardKind::Rapid, BoardKind::Blitz, BoardKind::Bullet]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BoardKind::Rapid => "장고(1국)",
+            BoardKind::Blitz => "속기(2~4국)",
+            BoardKind::Bullet => "초속기(에이스 결정전)",
+        }
+    }
+}
+
+// 판 순서대로의 종류입니다. 마지막은 정규시즌 에이스 결정전, 포스트시즌 5국입니다.
+pub const BOARD_KINDS: [BoardKind; 5] = [BoardKind::Rapid, BoardKind::Blitz, BoardKind::Blitz, BoardKind::Blitz, BoardKind::Bullet];
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stone {
+    Black,
+    White,
+}
+
+impl Stone {
+    pub fn opposite(&self) -> Stone {
+        match self {
+            Stone::Black => Stone::White,
+            Stone::White => Stone::Black,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq)]
 pub struct Player {
     korean_name: String,
@@ -15,6 +59,41 @@ pub struct Player {
     white_weight: f64,
     black_weight: f64,
     relative_weight: HashMap<String, f64>,
+    aliases: Vec<String>,
+    rating_source: String,
+    rating_date: Option<NaiveDate>,
+    rating_quality: DataQuality,
+    weight_quality: DataQuality,
+    rating_deviation: f64,
+    form: RecentForm,
+    projection_weight: f64,
+    projection: CareerProjection,
+    team_league_weight: f64,
+    team_league_performance: f64,
+    nowcast: RatingNowcast,
+}
+
+#[derive(Clone, PartialEq)]
+pub enum DataQuality {
+    Fresh,
+    Stale(String),
+    Fallback(String),
+    Missing(String),
+}
+
+impl DataQuality {
+    pub fn is_fresh(&self) -> bool {
+        *self == DataQuality::Fresh
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            DataQuality::Fresh => "정상".to_string(),
+            DataQuality::Stale(reason) => format!("오래됨 - {}", reason),
+            DataQuality::Fallback(reason) => format!("대체값 - {}", reason),
+            DataQuality::Missing(reason) => format!("없음 - {}", reason),
+        }
+    }
 }
 
 impl Player {
@@ -45,6 +124,18 @@ impl Player {
             white_weight,
             black_weight,
             relative_weight,
+            aliases: Vec::new(),
+            rating_source: String::new(),
+            rating_date: None,
+            rating_quality: DataQuality::Missing("아직 불러오지 않았습니다.".to_string()),
+            weight_quality: DataQuality::Missing("아직 불러오지 않았습니다.".to_string()),
+            rating_deviation: uncertainty::MAX_DEVIATION,
+            form: RecentForm::default(),
+            projection_weight: 0.0,
+            projection: CareerProjection::default(),
+            team_league_weight: 0.0,
+            team_league_performance: 0.0,
+            nowcast: RatingNowcast::default(),
         }
     }
 
@@ -60,6 +151,69 @@ impl Player {
         &self.chinese_name
     }
 
+    pub fn aliases(&self) -> &Vec<String> {
+        &self.aliases
+    }
+
+    pub fn rating_source(&self) -> &String {
+        &self.rating_source
+    }
+
+    pub fn rating_date(&self) -> Option<NaiveDate> {
+        self.rating_date
+    }
+
+    pub fn describe_rating_source(&self) -> String {
+        match self.rating_date {
+            Some(date) => format!("{} {}", self.rating_source, date),
+            None => "출처 없음".to_string(),
+        }
+    }
+
+    pub fn rating_deviation(&self) -> f64 {
+        self.rating_deviation
+    }
+
+    pub fn rating_quality(&self) -> &DataQuality {
+        &self.rating_quality
+    }
+
+    pub fn form(&self) -> &RecentForm {
+        &self.form
+    }
+
+    pub fn projection(&self) -> &CareerProjection {
+        &self.projection
+    }
+
+    pub fn team_league_performance(&self) -> f64 {
+        self.team_league_performance
+    }
+
+    pub fn nowcast(&self) -> &RatingNowcast {
+        &self.nowcast
+    }
+
+    pub fn weight_quality(&self) -> &DataQuality {
+        &self.weight_quality
+    }
+
+    // 스쿼드 목록에서 기사 이름 옆에 붙여 보여줄 데이터 상태입니다. 모두 정상이면 빈 문자열입니다.
+    pub fn data_quality_note(&self) -> String {
+        let mut notes = Vec::new();
+        if !self.rating_quality.is_fresh() {
+            notes.push(format!("레이팅: {}", self.rating_quality.describe()));
+        }
+        if !self.weight_quality.is_fresh() {
+            notes.push(format!("가중치: {}", self.weight_quality.describe()));
+        }
+        if notes.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", notes.join(", "))
+        }
+    }
+
     pub fn elo_rating(&self) -> f64 {
         self.elo_rating
     }
@@ -68,6 +222,14 @@ impl Player {
         self.condition_weight
     }
 
+    pub fn projection_weight(&self) -> f64 {
+        self.projection_weight
+    }
+
+    pub fn team_league_weight(&self) -> f64 {
+        self.team_league_weight
+    }
+
     pub fn rapid_weight(&self) -> f64 {
         self.rapid_weight
     }
@@ -124,9 +286,60 @@ impl Player {
         self.relative_weight = relative_weight;
     }
 
+    pub fn set_aliases(&mut self, aliases: Vec<String>) {
+        self.aliases = aliases;
+    }
+
+    pub fn set_rating_source(&mut self, rating_source: String) {
+        self.rating_source = rating_source;
+    }
+
+    pub fn set_rating_date(&mut self, rating_date: Option<NaiveDate>) {
+        self.rating_date = rating_date;
+    }
+
+    pub fn set_rating_deviation(&mut self, rating_deviation: f64) {
+        self.rating_deviation = rating_deviation;
+    }
+
+    pub fn set_rating_quality(&mut self, rating_quality: DataQuality) {
+        self.rating_quality = rating_quality;
+    }
+
+    pub fn set_weight_quality(&mut self, weight_quality: DataQuality) {
+        self.weight_quality = weight_quality;
+    }
+
+    pub fn set_form(&mut self, form: RecentForm) {
+        self.form = form;
+    }
+
+    pub fn set_projection_weight(&mut self, projection_weight: f64) {
+        self.projection_weight = projection_weight;
+    }
+
+    pub fn set_projection(&mut self, projection: CareerProjection) {
+        self.projection = projection;
+    }
+
+    pub fn set_team_league_weight(&mut self, team_league_weight: f64) {
+        self.team_league_weight = team_league_weight;
+    }
+
+    pub fn set_team_league_performance(&mut self, team_league_performance: f64) {
+        self.team_league_performance = team_league_performance;
+    }
+
+    pub fn set_nowcast(&mut self, nowcast: RatingNowcast) {
+        self.nowcast = nowcast;
+    }
+
     pub fn get_days_since_birth(&self) -> f64 {
-        let today = chrono::Utc::now().date_naive();
-        let duration_since_birth = today.signed_duration_since(self.birthday);
+        self.get_days_since_birth_on(chrono::Utc::now().date_naive())
+    }
+
+    pub fn get_days_since_birth_on(&self, date: NaiveDate) -> f64 {
+        let duration_since_birth = date.signed_duration_since(self.birthday);
         duration_since_birth.num_seconds() as f64 / 86_400.0
     }
 }
@@ -195,6 +408,7 @@ pub struct PlayerRelativity {
     third_blitz_win_probability: f64,
     forth_blitz_win_probability: f64,
     fifth_bullet_win_probability: f64,
+    head_to_head_adjustment: f64,
 }
 
 impl PlayerRelativity {
@@ -219,6 +433,7 @@ impl PlayerRelativity {
             third_blitz_win_probability,
             forth_blitz_win_probability,
             fifth_bullet_win_probability,
+            head_to_head_adjustment: 0.0,
         }
     }
 
@@ -238,6 +453,23 @@ impl PlayerRelativity {
         self.player2_wins
     }
 
+    // 두 기사의 레이팅 편차를 합친 값으로, 승률의 신뢰구간을 구할 때 사용합니다.
+    pub fn rating_deviation(&self) -> f64 {
+        uncertainty::combined_deviation(self.player1.rating_deviation(), self.player2.rating_deviation())
+    }
+
+    pub fn win_interval(&self, win_probability: f64) -> (f64, f64) {
+        uncertainty::credible_interval(win_probability, self.rating_deviation())
+    }
+
+    pub fn head_to_head_adjustment(&self) -> f64 {
+        self.head_to_head_adjustment
+    }
+
+    pub fn set_head_to_head_adjustment(&mut self, head_to_head_adjustment: f64) {
+        self.head_to_head_adjustment = head_to_head_adjustment;
+    }
+
     pub fn first_rapid_win_probability(&self) -> f64 {
         self.first_rapid_win_probability
     }
@@ -315,6 +547,7 @@ pub struct MatchResult {
     one_three_probability: f64,
     zero_four_probability: f64,
     total_win_probability: f64,
+    total_win_interval: (f64, f64),
     tiebreaker_relativities: Vec<Option<TiebreakerRelativity>>,
     tiebreaker_win_probability: f64,
 }
@@ -335,6 +568,7 @@ impl MatchResult {
         one_three_probability: f64,
         zero_four_probability: f64,
         total_win_probability: f64,
+        total_win_interval: (f64, f64),
         tiebreaker_relativities: Vec<Option<TiebreakerRelativity>>,
         tiebreaker_win_probability: f64,
     ) -> Self {
@@ -353,6 +587,7 @@ impl MatchResult {
             one_three_probability,
             zero_four_probability,
             total_win_probability,
+            total_win_interval,
             tiebreaker_relativities,
             tiebreaker_win_probability,
         }
@@ -390,6 +625,22 @@ impl MatchResult {
         self.forth_blitz_win_probability
     }
 
+    pub fn first_rapid_win_interval(&self) -> (f64, f64) {
+        self.first_rapid.win_interval(self.first_rapid_win_probability)
+    }
+
+    pub fn second_blitz_win_interval(&self) -> (f64, f64) {
+        self.second_blitz.win_interval(self.second_blitz_win_probability)
+    }
+
+    pub fn third_blitz_win_interval(&self) -> (f64, f64) {
+        self.third_blitz.win_interval(self.third_blitz_win_probability)
+    }
+
+    pub fn forth_blitz_win_interval(&self) -> (f64, f64) {
+        self.forth_blitz.win_interval(self.forth_blitz_win_probability)
+    }
+
     pub fn four_zero_probability(&self) -> f64 {
         self.four_zero_probability
     }
@@ -414,6 +665,10 @@ impl MatchResult {
         self.total_win_probability
     }
 
+    pub fn total_win_interval(&self) -> (f64, f64) {
+        self.total_win_interval
+    }
+
     pub fn tiebreaker_relativities(&self) -> &Vec<Option<TiebreakerRelativity>> {
         &self.tiebreaker_relativities
     }
@@ -671,6 +926,7 @@ pub struct PostPlayerRelativity {
     third_blitz_black_win_probability: f64,
     forth_blitz_black_win_probability: f64,
     fifth_bullet_black_win_probability: f64,
+    head_to_head_adjustment: f64,
 }
 
 #[derive(Clone)]
@@ -1007,6 +1263,7 @@ impl PostPlayerRelativity {
             third_blitz_black_win_probability,
             forth_blitz_black_win_probability,
             fifth_bullet_black_win_probability,
+            head_to_head_adjustment: 0.0,
         }
     }
 
@@ -1026,6 +1283,23 @@ impl PostPlayerRelativity {
         self.player2_wins
     }
 
+    // 두 기사의 레이팅 편차를 합친 값으로, 승률의 신뢰구간을 구할 때 사용합니다.
+    pub fn rating_deviation(&self) -> f64 {
+        uncertainty::combined_deviation(self.player1.rating_deviation(), self.player2.rating_deviation())
+    }
+
+    pub fn win_interval(&self, win_probability: f64) -> (f64, f64) {
+        uncertainty::credible_interval(win_probability, self.rating_deviation())
+    }
+
+    pub fn head_to_head_adjustment(&self) -> f64 {
+        self.head_to_head_adjustment
+    }
+
+    pub fn set_head_to_head_adjustment(&mut self, head_to_head_adjustment: f64) {
+        self.head_to_head_adjustment = head_to_head_adjustment;
+    }
+
     pub fn first_rapid_white_win_probability(&self) -> f64 {
         self.first_rapid_white_win_probability
     }
@@ -1297,4 +1571,198 @@ impl PostRAXResult {
     pub fn fifth_bullet_black_rax(&self) -> f64 {
         self.fifth_bullet_black_rax
     }
-}
\ No newline at end of file
+}
+#[derive(Clone)]
+pub struct GameRecord {
+    date: NaiveDate,
+    event_name: String,
+    winner_name: String,
+    loser_name: String,
+    winner_color: String,
+}
+
+impl GameRecord {
+    pub fn new(date: NaiveDate, event_name: String, winner_name: String, loser_name: String, winner_color: String) -> GameRecord {
+        GameRecord { date, event_name, winner_name, loser_name, winner_color }
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn event_name(&self) -> &String {
+        &self.event_name
+    }
+
+    pub fn winner_name(&self) -> &String {
+        &self.winner_name
+    }
+
+    pub fn loser_name(&self) -> &String {
+        &self.loser_name
+    }
+
+    pub fn winner_color(&self) -> &String {
+        &self.winner_color
+    }
+
+    pub fn opponent_of(&self, name: &str) -> &String {
+        if self.winner_name == name { &self.loser_name } else { &self.winner_name }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct FormGame {
+    date: NaiveDate,
+    opponent: String,
+    won: bool,
+    expected: f64,
+    points: f64,
+}
+
+impl FormGame {
+    pub fn new(date: NaiveDate, opponent: String, won: bool, expected: f64, points: f64) -> FormGame {
+        FormGame { date, opponent, won, expected, points }
+    }
+
+    pub fn describe(&self) -> String {
+        format!("{} vs {} {} (기대 승률 {:.1}%, {:+.2})", self.date, self.opponent, if self.won { "승" } else { "패" }, self.expected * 100.0, self.points)
+    }
+}
+
+// 최근 대국으로 계산한 컨디션 가중치와 그 내역입니다.
+#[derive(Clone, PartialEq, Default)]
+pub struct RecentForm {
+    games: Vec<FormGame>,
+    game_count: usize,
+    streak: i32,
+    performance_points: f64,
+    streak_points: f64,
+    activity_points: f64,
+}
+
+impl RecentForm {
+    pub fn new(games: Vec<FormGame>, game_count: usize, streak: i32, performance_points: f64, streak_points: f64, activity_points: f64) -> RecentForm {
+        RecentForm { games, game_count, streak, performance_points, streak_points, activity_points }
+    }
+
+    pub fn weight(&self) -> f64 {
+        self.performance_points + self.streak_points + self.activity_points
+    }
+
+    pub fn games(&self) -> &Vec<FormGame> {
+        &self.games
+    }
+
+    pub fn describe(&self) -> String {
+        let streak = match self.streak {
+            streak if streak > 0 => format!("{}연승", streak),
+            streak if streak < 0 => format!("{}연패", -streak),
+            _ => "대국 없음".to_string(),
+        };
+        format!(
+            "기대 대비 성적 {:+.2}, {} {:+.2}, 대국 {}판 {:+.2}",
+            self.performance_points, streak, self.streak_points, self.game_count, self.activity_points
+        )
+    }
+}
+
+// 나이와 최근 레이팅 추세로 내다본 레이팅 변화입니다. 변화량은 1년 기준이며, change는 전망 기간 동안의 변화입니다.
+#[derive(Clone, PartialEq, Default)]
+pub struct CareerProjection {
+    age: f64,
+    age_trend: f64,
+    rating_trend: Option<f64>,
+    history_months: usize,
+    horizon_months: u32,
+    change: f64,
+}
+
+impl CareerProjection {
+    pub fn new(age: f64, age_trend: f64, rating_trend: Option<f64>, history_months: usize, horizon_months: u32, change: f64) -> CareerProjection {
+        CareerProjection { age, age_trend, rating_trend, history_months, horizon_months, change }
+    }
+
+    pub fn change(&self) -> f64 {
+        self.change
+    }
+
+    pub fn describe(&self) -> String {
+        let rating_trend = match self.rating_trend {
+            Some(rating_trend) => format!("최근 {}개월 추세 {:+.1}/년", self.history_months, rating_trend),
+            None => "레이팅 기록 부족".to_string(),
+        };
+        format!("만 {:.0}세 나이 기준 {:+.1}/년, {}, {}개월 전망 {:+.2}", self.age.floor(), self.age_trend, rating_trend, self.horizon_months, self.change)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct NowcastGame {
+    date: NaiveDate,
+    event_name: String,
+    opponent: String,
+    won: bool,
+    expected: f64,
+    k: f64,
+    change: f64,
+}
+
+impl NowcastGame {
+    pub fn new(date: NaiveDate, event_name: String, opponent: String, won: bool, expected: f64, k: f64, change: f64) -> NowcastGame {
+        NowcastGame { date, event_name, opponent, won, expected, k, change }
+    }
+
+    pub fn describe(&self) -> String {
+        format!("{} {} vs {} {} (기대 승률 {:.1}%, K {:.1}, {:+.2})", self.date, self.event_name, self.opponent, if self.won { "승" } else { "패" }, self.expected * 100.0, self.k, self.change)
+    }
+}
+
+// 마지막으로 발표된 레이팅에 그 이후의 대국을 차례로 반영한 현재 레이팅입니다.
+#[derive(Clone, PartialEq, Default)]
+pub struct RatingNowcast {
+    base_rating: f64,
+    games: Vec<NowcastGame>,
+    skipped_games: usize,
+}
+
+impl RatingNowcast {
+    pub fn new(base_rating: f64) -> RatingNowcast {
+        RatingNowcast { base_rating, games: Vec::new(), skipped_games: 0 }
+    }
+
+    pub fn base_rating(&self) -> f64 {
+        self.base_rating
+    }
+
+    pub fn rating(&self) -> f64 {
+        self.base_rating + self.games.iter().map(|game| game.change).sum::<f64>()
+    }
+
+    pub fn games(&self) -> &Vec<NowcastGame> {
+        &self.games
+    }
+
+    pub fn push_game(&mut self, game: NowcastGame) {
+        self.games.push(game);
+    }
+
+    pub fn skip_game(&mut self) {
+        self.skipped_games += 1;
+    }
+
+    pub fn describe(&self) -> String {
+        let mut description = format!("기준 {:.2}, 최근 대국 {}판 {:+.2} → {:.2}", self.base_rating, self.games.len(), self.rating() - self.base_rating, self.rating());
+        if self.skipped_games > 0 {
+            description.push_str(&format!(" (상대 레이팅을 몰라 {}판 제외)", self.skipped_games));
+        }
+        description
+    }
+}
+
+#[cfg(test)]
+impl Player {
+    // 이름만 있는 기사입니다. 가중치는 모두 0입니다.
+    pub fn named(name: &str) -> Player {
+        Player::new(name.to_string(), String::new(), String::new(), NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new())
+    }
+}
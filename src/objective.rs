@@ -0,0 +1,220 @@
+use crate::config::{self, LeaguePoints};
+use crate::models::{MatchResult, PostMatchResult};
+use std::io;
+
+// 포스트시즌에서 좌측팀이 1국에 드는 돌의 색입니다. 흑이면 흑백흑백흑, 백이면 백흑백흑백입니다.
+#[derive(Clone, Copy)]
+pub enum StartingColour {
+    Black,
+    White,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ObjectiveKind {
+    TotalWin,
+    BoardWins,
+    Shutout,
+    LeaguePoints,
+}
+
+const KINDS: [(ObjectiveKind, &str, &str); 4] = [
+    (ObjectiveKind::TotalWin, "total_win", "총 승리확률"),
+    (ObjectiveKind::BoardWins, "board_wins", "기대 개인 승수"),
+    (ObjectiveKind::Shutout, "shutout", "완봉승 확률"),
+    (ObjectiveKind::LeaguePoints, "league_points", "승점"),
+];
+
+// 라인업을 고를 때 무엇을 크게 할지입니다. risk가 0보다 작으면 상대 라인업에 따라 결과가 크게 흔들리는 라인업을 불리하게(위험회피),
+// 0보다 크면 유리하게(위험추구) 봅니다.
+#[derive(Clone)]
+pub struct Objective {
+    kind: ObjectiveKind,
+    risk: f64,
+    league_points: LeaguePoints,
+}
+
+impl Objective {
+    #[cfg(test)]
+    pub fn new(kind: ObjectiveKind, risk: f64) -> Objective {
+        Objective { kind, risk, league_points: LeaguePoints::default() }
+    }
+
+    pub fn current() -> Objective {
+        let config = config::get();
+        let kind = KINDS.iter().find(|(_, name, _)| *name == config.lineup_objective).map_or(ObjectiveKind::TotalWin, |(kind, _, _)| *kind);
+        Objective { kind, risk: config.lineup_risk, league_points: config.league_points }
+    }
+
+    // 위험 성향을 뺀 평가 기준의 이름입니다.
+    pub fn name(&self) -> String {
+        let name = KINDS.iter().find(|(kind, _, _)| *kind == self.kind).map_or("", |(_, _, label)| label);
+        if self.kind == ObjectiveKind::LeaguePoints {
+            format!("{}(승리 {} + 개인 승 {} + 완봉승 {})", name, self.league_points.win, self.league_points.board_win, self.league_points.shutout)
+        } else {
+            name.to_string()
+        }
+    }
+
+    pub fn label(&self) -> String {
+        let name = self.name();
+        if self.risk < 0.0 {
+            format!("{}, 위험회피 {:.2}", name, -self.risk)
+        } else if self.risk > 0.0 {
+            format!("{}, 위험추구 {:.2}", name, self.risk)
+        } else {
+            name
+        }
+    }
+
+    // 총 승리확률과 기대 개인 승수는 한 팀이 얻는 만큼 상대가 잃으므로 혼합전략 게임의 값이 내쉬균형입니다.
+    // 완봉승 확률과 승점은 그렇지 않아서, 같은 계산이 상대가 좌측팀의 값을 가장 낮춘다고 볼 때의 최대최소 값이 됩니다.
+    pub fn is_constant_sum(&self) -> bool {
+        matches!(self.kind, ObjectiveKind::TotalWin | ObjectiveKind::BoardWins)
+    }
+
+    // 혼합전략 게임의 값을 보여줍니다. 위험 성향은 상대 라인업 분포에 대한 평균에만 쓰므로 여기에는 쓰지 않습니다.
+    pub fn print_mixed_value(&self, team_name: &str, value: f64) {
+        if self.is_constant_sum() {
+            println!("균형 {}(게임 값): {}", self.name(), self.format(value));
+        } else {
+            println!("{}은(는) 한 팀이 얻는 만큼 상대가 잃는 기준이 아니므로 내쉬균형이 아니라 {}의 최대최소 혼합전략입니다.", self.name(), team_name);
+            println!("최대최소 {}: {}", self.name(), self.format(value));
+        }
+        if self.risk != 0.0 {
+            println!("위험 성향({:+.2})은 혼합전략 계산에 쓰지 않습니다.", self.risk);
+        }
+    }
+
+    // 혼합전략에서 우측팀 분포의 제목입니다. 상수합이 아니면 우측팀의 최선이 아니라 좌측팀의 값을 가장 낮추는 분포입니다.
+    pub fn column_strategy_title(&self, team_name: &str) -> String {
+        if self.is_constant_sum() {
+            format!("{} 균형 라인업 분포:", team_name)
+        } else {
+            format!("{} 라인업 분포(좌측팀의 {}을(를) 가장 낮추는 경우):", team_name, self.name())
+        }
+    }
+
+    pub fn format(&self, value: f64) -> String {
+        match self.kind {
+            ObjectiveKind::TotalWin | ObjectiveKind::Shutout => format!("{:.2}%", value * 100.0),
+            ObjectiveKind::BoardWins => format!("{:.2}승", value),
+            ObjectiveKind::LeaguePoints => format!("{:.3}점", value),
+        }
+    }
+
+    fn combine(&self, win: f64, board_wins: f64, shutout: f64) -> f64 {
+        match self.kind {
+            ObjectiveKind::TotalWin => win,
+            ObjectiveKind::BoardWins => board_wins,
+            ObjectiveKind::Shutout => shutout,
+            ObjectiveKind::LeaguePoints => self.league_points.win * win + self.league_points.board_win * board_wins + self.league_points.shutout * shutout,
+        }
+    }
+
+    // 한 대진의 기댓값입니다. 기대 개인 승수는 get_total_win_probability의 팀 점수와 같은 식입니다.
+    pub fn value(&self, result: &MatchResult) -> f64 {
+        let board_wins = (4.0 * result.four_zero_probability() + 3.0 * result.three_one_probability() + 2.0 * result.two_two_probability() + result.one_three_probability()) / 100.0;
+        self.combine(result.total_win_probability() / 100.0, board_wins, result.four_zero_probability() / 100.0)
+    }
+
+    pub fn value_post(&self, result: &PostMatchResult, colour: StartingColour) -> f64 {
+        let (scores, win) = match colour {
+            StartingColour::Black => (
+                [result.black_started_five_zero_probability(), result.black_started_four_one_probability(), result.black_started_three_two_probability(), result.black_started_two_three_probability(), result.black_started_one_four_probability()],
+                result.black_started_total_win_probability(),
+            ),
+            StartingColour::White => (
+                [result.white_started_five_zero_probability(), result.white_started_four_one_probability(), result.white_started_three_two_probability(), result.white_started_two_three_probability(), result.white_started_one_four_probability()],
+                result.white_started_total_win_probability(),
+            ),
+        };
+        let board_wins = scores.iter().zip([5.0, 4.0, 3.0, 2.0, 1.0]).map(|(probability, wins)| probability * wins).sum::<f64>() / 100.0;
+        self.combine(win / 100.0, board_wins, scores[0] / 100.0)
+    }
+
+    // 상대 라인업 분포에 대한 평균과 분산으로 라인업의 값을 정합니다. 상대 라인업이 하나로 정해지면 분산이 0이므로 평균과 같습니다.
+    pub fn risk_adjusted(&self, mean: f64, variance: f64) -> f64 {
+        mean + self.risk * variance.max(0.0).sqrt()
+    }
+}
+
+pub fn select_objective() {
+    let objective = Objective::current();
+    println!("\n현재 라인업 평가 기준: {}", objective.label());
+    for (index, (_, _, label)) in KINDS.iter().enumerate() {
+        println!("{}. {}", index + 1, label);
+    }
+    println!("평가 기준 번호를 입력하세요 (변경하지 않으려면 엔터): ");
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
+    let input = input.trim();
+    if input.is_empty() {
+        return;
+    }
+    let (kind, name, _) = match input.parse::<usize>() {
+        Ok(num) if num > 0 && num <= KINDS.len() => KINDS[num - 1],
+        _ => {
+            println!("잘못된 입력입니다. 다시 입력해주세요.");
+            return;
+        }
+    };
+
+    let mut league_points = objective.league_points.clone();
+    if kind == ObjectiveKind::LeaguePoints {
+        for (label, value) in [("승리", &mut league_points.win), ("개인 승 1판", &mut league_points.board_win), ("완봉승", &mut league_points.shutout)] {
+            println!("{} 점수를 입력하세요 (현재: {}, 변경하지 않으려면 엔터): ", label, value);
+            let mut input = String::new();
+            io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
+            if let Ok(points) = input.trim().parse::<f64>() {
+                *value = points;
+            }
+        }
+    }
+
+    println!("위험 성향을 입력하세요 (0: 중립, 음수: 위험회피, 양수: 위험추구, 예: -0.5, 현재: {}): ", objective.risk);
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
+    let risk = input.trim().parse::<f64>().unwrap_or(objective.risk);
+
+    let result = config::update(|config| {
+        config.lineup_objective = name.to_string();
+        config.lineup_risk = risk;
+        config.league_points = league_points;
+    });
+    match result {
+        Ok(_) => println!("라인업 평가 기준을 {}(으)로 바꿨습니다.", Objective::current().label()),
+        Err(e) => println!("설정을 저장하는 동안 오류가 발생했습니다: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combine_picks_the_selected_quantity() {
+        assert_eq!(Objective::new(ObjectiveKind::TotalWin, 0.0).combine(0.6, 2.5, 0.1), 0.6);
+        assert_eq!(Objective::new(ObjectiveKind::BoardWins, 0.0).combine(0.6, 2.5, 0.1), 2.5);
+        assert_eq!(Objective::new(ObjectiveKind::Shutout, 0.0).combine(0.6, 2.5, 0.1), 0.1);
+        let objective = Objective { kind: ObjectiveKind::LeaguePoints, risk: 0.0, league_points: LeaguePoints { win: 3.0, board_win: 0.5, shutout: 1.0 } };
+        assert!((objective.combine(0.6, 2.5, 0.1) - (1.8 + 1.25 + 0.1)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn risk_moves_the_value_by_the_standard_deviation() {
+        assert_eq!(Objective::new(ObjectiveKind::TotalWin, 0.0).risk_adjusted(0.5, 0.04), 0.5);
+        assert!((Objective::new(ObjectiveKind::TotalWin, -0.5).risk_adjusted(0.5, 0.04) - 0.4).abs() < 1e-9);
+        assert!((Objective::new(ObjectiveKind::TotalWin, 0.5).risk_adjusted(0.5, 0.04) - 0.6).abs() < 1e-9);
+        // 반올림으로 음수가 된 분산은 0으로 봅니다.
+        assert_eq!(Objective::new(ObjectiveKind::TotalWin, -1.0).risk_adjusted(0.5, -1e-12), 0.5);
+    }
+
+    #[test]
+    fn only_total_win_and_board_wins_are_constant_sum() {
+        assert!(Objective::new(ObjectiveKind::TotalWin, 0.0).is_constant_sum());
+        assert!(Objective::new(ObjectiveKind::BoardWins, 0.0).is_constant_sum());
+        assert!(!Objective::new(ObjectiveKind::Shutout, 0.0).is_constant_sum());
+        assert!(!Objective::new(ObjectiveKind::LeaguePoints, 0.0).is_constant_sum());
+    }
+}
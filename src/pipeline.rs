@@ -0,0 +1,181 @@
+use crate::cache;
+use crate::config;
+use crate::error::DataError;
+use crate::models::Team;
+use crate::rating_archive;
+use crate::records;
+use futures::future::join_all;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::collections::HashMap;
+use std::error::Error;
+use std::future::Future;
+use std::sync::OnceLock;
+use tokio::runtime::Runtime;
+use tokio::sync::{Mutex, Semaphore};
+use tokio::time::{sleep, sleep_until, Duration, Instant};
+
+pub struct Pipeline {
+    client: reqwest::Client,
+    semaphore: Semaphore,
+    next_request_at: Mutex<Instant>,
+    delay: Duration,
+    retries: u32,
+    backoff: Duration,
+    progress: ProgressBar,
+}
+
+impl Pipeline {
+    pub fn new() -> Pipeline {
+        Pipeline::with_progress(ProgressBar::hidden())
+    }
+
+    pub fn with_progress(progress: ProgressBar) -> Pipeline {
+        let config = config::get();
+        Pipeline {
+            client: reqwest::Client::new(),
+            semaphore: Semaphore::new(config.fetch_concurrency.max(1)),
+            next_request_at: Mutex::new(Instant::now()),
+            delay: Duration::from_millis(config.fetch_delay_ms),
+            retries: config.fetch_retries,
+            backoff: Duration::from_millis(config.fetch_backoff_ms),
+            progress,
+        }
+    }
+
+    pub fn progress(&self) -> &ProgressBar {
+        &self.progress
+    }
+
+    pub async fn get_text(&self, url: &str) -> Result<String, DataError> {
+        self.fetch("GET", url, &[]).await
+    }
+
+    pub async fn post_form_text(&self, url: &str, form: &[(&str, &str)]) -> Result<String, DataError> {
+        self.fetch("POST", url, form).await
+    }
+
+    async fn fetch(&self, method: &str, url: &str, form: &[(&str, &str)]) -> Result<String, DataError> {
+        if let Some(body) = cache::lookup(method, url, form) {
+            if let Some(warning) = cache::save_response(method, url, form, &body, false) {
+                self.report(warning);
+            }
+            return Ok(body);
+        }
+
+        let _permit = self.semaphore.acquire().await.map_err(|e| DataError::Network { url: url.to_string(), message: e.to_string() })?;
+        let mut attempt = 0;
+        loop {
+            self.wait_for_turn().await;
+            match self.send(method, url, form).await {
+                Ok(body) => {
+                    if let Some(warning) = cache::save_response(method, url, form, &body, true) {
+                        self.report(warning);
+                    }
+                    return Ok(body);
+                },
+                Err(e) if attempt < self.retries && is_retryable(&e) => {
+                    attempt += 1;
+                    self.report(format!("요청 실패 ({}), {}번째 재시도: {}", e, attempt, url));
+                    sleep(self.backoff * 2u32.pow(attempt - 1)).await;
+                },
+                Err(e) => return Err(DataError::Network { url: url.to_string(), message: e.to_string() }),
+            }
+        }
+    }
+
+    // 진행 표시줄이 있으면 그 위에, 없으면 그냥 출력합니다.
+    fn report(&self, message: String) {
+        if self.progress.is_hidden() {
+            println!("{}", message);
+        } else {
+            self.progress.println(message);
+        }
+    }
+
+    // 동시에 여러 요청을 보내더라도 요청 사이의 간격은 delay 이상으로 유지합니다.
+    async fn wait_for_turn(&self) {
+        let turn = {
+            let mut next_request_at = self.next_request_at.lock().await;
+            let turn = (*next_request_at).max(Instant::now());
+            *next_request_at = turn + self.delay;
+            turn
+        };
+        sleep_until(turn).await;
+    }
+
+    async fn send(&self, method: &str, url: &str, form: &[(&str, &str)]) -> Result<String, reqwest::Error> {
+        let request = if method == "POST" {
+            self.client.post(url).form(form)
+        } else {
+            self.client.get(url)
+        };
+        request.send().await?.error_for_status()?.text().await
+    }
+}
+
+// 연결 실패, 시간 초과, 서버 오류(5xx), 요청 제한(429)만 다시 시도합니다. 4xx는 다시 보내도 같은 결과입니다.
+fn is_retryable(e: &reqwest::Error) -> bool {
+    match e.status() {
+        Some(status) => status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS,
+        None => e.is_connect() || e.is_timeout(),
+    }
+}
+
+// 런타임은 한 번만 만들어 모든 호출에서 함께 씁니다.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME
+        .get_or_init(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .expect("tokio 런타임을 생성하는 데 실패했습니다.")
+        })
+        .block_on(future)
+}
+
+pub fn progress_bar(length: u64) -> ProgressBar {
+    let progress = ProgressBar::new(length);
+    progress.set_style(
+        ProgressStyle::with_template("{spinner} [{elapsed_precise}] {bar:40} {pos}/{len} {msg}")
+            .unwrap()
+            .progress_chars("##-"),
+    );
+    progress
+}
+
+// 동기화에 실패한 기사와 그 이유를 돌려줍니다. 실패하더라도 이미 저장된 기록으로 분석을 계속할 수 있습니다.
+pub fn prefetch_team_data(teams: &[Team]) -> Result<HashMap<String, DataError>, Box<dyn Error>> {
+    let names: Vec<String> = teams.iter().flat_map(|team| team.players().iter().map(|player| player.korean_name().clone())).collect();
+    let months = rating_archive::missing_baeteil_months(records::history_start_date())?;
+
+    let progress = progress_bar((names.len() + months.len()) as u64);
+    let pipeline = Pipeline::with_progress(progress.clone());
+
+    let pipeline = &pipeline;
+    let (player_failures, month_failures): (HashMap<String, DataError>, Vec<String>) = block_on(async {
+        let player_tasks = names.iter().map(|name| async move {
+            let result = records::ensure_synced_async(pipeline, name).await;
+            pipeline.progress().set_message(name.clone());
+            pipeline.progress().inc(1);
+            result.err().map(|e| (name.clone(), e))
+        });
+        let month_tasks = months.iter().map(|(year, month)| async move {
+            let result = rating_archive::prefetch_baeteil_month(pipeline, *year, *month).await;
+            pipeline.progress().set_message(format!("{}년 {}월 랭킹", year, month));
+            pipeline.progress().inc(1);
+            result.err().map(|e| format!("{}년 {}월 랭킹: {}", year, month, e))
+        });
+        let (player_results, month_results) = futures::join!(join_all(player_tasks), join_all(month_tasks));
+        (player_results.into_iter().flatten().collect(), month_results.into_iter().flatten().collect())
+    });
+
+    progress.finish_with_message("데이터를 모두 불러왔습니다.");
+    for (name, e) in &player_failures {
+        println!("데이터를 불러오는 중 오류가 발생했습니다: {}: {}", name, e);
+    }
+    for failure in &month_failures {
+        println!("데이터를 불러오는 중 오류가 발생했습니다: {}", failure);
+    }
+    Ok(player_failures)
+}
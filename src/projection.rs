@@ -0,0 +1,57 @@
+use crate::config;
+use crate::model_params;
+use crate::models::{CareerProjection, Player};
+use crate::rating_archive;
+use crate::utils::baeteil_to_goratings;
+use chrono::{Months, NaiveDate};
+
+// 추세를 구하려면 월별 레이팅이 최소 이만큼 있어야 합니다.
+const MIN_HISTORY_MONTHS: usize = 3;
+
+// 나이로 본 1년 동안의 레이팅 변화입니다.
+fn age_trend(age: f64) -> f64 {
+    let params = model_params::get();
+    if age < params.projection_peak_age {
+        (params.projection_peak_age - age) * params.projection_growth_per_year
+    } else {
+        -(age - params.projection_peak_age) * params.projection_decline_per_year
+    }
+}
+
+// 월별 레이팅의 최소제곱 기울기를 1년 단위로 돌려줍니다.
+fn rating_trend(history: &[(NaiveDate, f64)]) -> Option<f64> {
+    if history.len() < MIN_HISTORY_MONTHS {
+        return None;
+    }
+    let first_date = history[0].0;
+    let points: Vec<(f64, f64)> = history.iter().map(|(date, rating)| ((*date - first_date).num_days() as f64 / 365.25, baeteil_to_goratings(*rating))).collect();
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+    let covariance: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x) * (x - mean_x)).sum();
+    if variance == 0.0 { None } else { Some(covariance / variance) }
+}
+
+// 나이로 본 변화를 기준으로 두고, 보관된 배태일 랭킹의 월별 기록이 많을수록 최근 추세 쪽으로 옮겨 전망 기간 동안의 변화를 구합니다.
+pub fn career_projection(player: &Player, as_of: NaiveDate) -> CareerProjection {
+    let params = model_params::get();
+    let config = config::get();
+    let age = player.get_days_since_birth_on(as_of) / 365.25;
+    let age_trend = age_trend(age);
+
+    let since = as_of.checked_sub_months(Months::new(config.projection_history_months)).unwrap_or(as_of);
+    let history = rating_archive::archived_baeteil_history(player.korean_name(), since, as_of).unwrap_or_default();
+    let rating_trend = rating_trend(&history);
+
+    let annual_change = match rating_trend {
+        Some(rating_trend) => {
+            let trend_share = history.len() as f64 / (history.len() as f64 + params.projection_trend_prior_months);
+            trend_share * rating_trend + (1.0 - trend_share) * age_trend
+        },
+        None => age_trend,
+    };
+    let change = annual_change * config.projection_horizon_months as f64 / 12.0;
+
+    CareerProjection::new(age, age_trend, rating_trend, history.len(), config.projection_horizon_months, change)
+}
@@ -0,0 +1,357 @@
+use crate::cache;
+use crate::conversion;
+use crate::fixtures;
+use crate::pipeline::{self, Pipeline};
+use crate::records;
+use crate::utils::{fetch_player_ratings_on_baeteil, parse_player_ratings_on_baeteil, parse_player_ratings_on_goratings};
+use crate::whr;
+use chrono::{Datelike, NaiveDate, Utc};
+use futures::future::join_all;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::error::Error;
+use std::io;
+use std::sync::{Mutex, OnceLock};
+
+// 기사 이름별 레이팅과, 배태일 랭킹이면 그 랭킹의 기준 월입니다.
+type Ratings = HashMap<String, f64>;
+type BaeteilList = (Ratings, String);
+type DatedBaeteilList = (i32, u32, Ratings, String);
+// goratings 기사 번호(없으면 None)와 영문 이름입니다.
+type GoratingsIds = Vec<(Option<u32>, String)>;
+
+fn open_archive() -> Result<Connection, Box<dyn Error>> {
+    let conn = records::open_database()?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS baeteil_lists (
+            year INTEGER NOT NULL,
+            month INTEGER NOT NULL,
+            ranking_month TEXT NOT NULL,
+            fetched_at INTEGER NOT NULL,
+            PRIMARY KEY (year, month)
+        );
+        CREATE TABLE IF NOT EXISTS baeteil_ratings (
+            year INTEGER NOT NULL,
+            month INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            rating REAL NOT NULL,
+            PRIMARY KEY (year, month, name)
+        );
+        CREATE TABLE IF NOT EXISTS goratings_snapshots (
+            snapshot_date TEXT NOT NULL,
+            name TEXT NOT NULL,
+            rating REAL NOT NULL,
+            PRIMARY KEY (snapshot_date, name)
+        );
+        CREATE TABLE IF NOT EXISTS goratings_ids (
+            goratings_id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL
+        );",
+    )?;
+    Ok(conn)
+}
+
+fn is_published(year: i32, month: u32) -> bool {
+    let today = Utc::now().date_naive();
+    (year, month) < (today.year(), today.month())
+}
+
+fn load_baeteil_list(conn: &Connection, year: i32, month: u32) -> Result<Option<BaeteilList>, Box<dyn Error>> {
+    let ranking_month: Option<String> = conn
+        .query_row("SELECT ranking_month FROM baeteil_lists WHERE year = ?1 AND month = ?2", params![year, month], |row| row.get(0))
+        .optional()?;
+    let ranking_month = match ranking_month {
+        Some(ranking_month) => ranking_month,
+        None => return Ok(None),
+    };
+
+    let mut stmt = conn.prepare("SELECT name, rating FROM baeteil_ratings WHERE year = ?1 AND month = ?2")?;
+    let ratings = stmt.query_map(params![year, month], |row| Ok((row.get(0)?, row.get(1)?)))?.collect::<Result<HashMap<String, f64>, _>>()?;
+    Ok(Some((ratings, ranking_month)))
+}
+
+fn store_baeteil_list(conn: &mut Connection, year: i32, month: u32, ratings: &HashMap<String, f64>, ranking_month: &str) -> Result<(), Box<dyn Error>> {
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM baeteil_ratings WHERE year = ?1 AND month = ?2", params![year, month])?;
+    for (name, rating) in ratings {
+        tx.execute("INSERT INTO baeteil_ratings (year, month, name, rating) VALUES (?1, ?2, ?3, ?4)", params![year, month, name, rating])?;
+    }
+    tx.execute(
+        "INSERT OR REPLACE INTO baeteil_lists (year, month, ranking_month, fetched_at) VALUES (?1, ?2, ?3, ?4)",
+        params![year, month, ranking_month, Utc::now().timestamp()],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+// 한 번 읽은 목록은 메모리에 둡니다. 기사마다, 대국마다 같은 달의 목록을 읽으며, 백테스트에서는 같은 달을 여러 번 읽습니다.
+fn loaded_lists() -> &'static Mutex<HashMap<(i32, u32), BaeteilList>> {
+    static LOADED_LISTS: OnceLock<Mutex<HashMap<(i32, u32), BaeteilList>>> = OnceLock::new();
+    LOADED_LISTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// 지난 달의 랭킹은 한 번만 내려받아 보관하고, 이번 달 랭킹은 실행할 때마다 한 번 새로 받아 갱신합니다.
+pub fn baeteil_list(year: i32, month: u32) -> Result<BaeteilList, Box<dyn Error>> {
+    if let Some(list) = loaded_lists().lock().unwrap().get(&(year, month)) {
+        return Ok(list.clone());
+    }
+
+    let mut conn = open_archive()?;
+    if is_published(year, month) {
+        if let Some(archived) = load_baeteil_list(&conn, year, month)? {
+            loaded_lists().lock().unwrap().insert((year, month), archived.clone());
+            return Ok(archived);
+        }
+    }
+
+    let (ratings, ranking_month) = fetch_player_ratings_on_baeteil(&year.to_string(), &month.to_string())?;
+    store_baeteil_list(&mut conn, year, month, &ratings, &ranking_month)?;
+    loaded_lists().lock().unwrap().insert((year, month), (ratings.clone(), ranking_month.clone()));
+    Ok((ratings, ranking_month))
+}
+
+// 이번 달 랭킹을 받지 못했을 때 대신 쓸 수 있도록 보관된 가장 최근 랭킹을 돌려줍니다.
+pub fn latest_baeteil_list() -> Result<Option<DatedBaeteilList>, Box<dyn Error>> {
+    let conn = open_archive()?;
+    let latest: Option<(i32, u32)> = conn
+        .query_row("SELECT year, month FROM baeteil_lists ORDER BY year DESC, month DESC LIMIT 1", [], |row| Ok((row.get(0)?, row.get(1)?)))
+        .optional()?;
+    match latest {
+        Some((year, month)) => Ok(load_baeteil_list(&conn, year, month)?.map(|(ratings, ranking_month)| (year, month, ratings, ranking_month))),
+        None => Ok(None),
+    }
+}
+
+pub fn missing_baeteil_months(since: NaiveDate) -> Result<Vec<(i32, u32)>, Box<dyn Error>> {
+    let conn = open_archive()?;
+    let mut missing_months = Vec::new();
+    for (year, month) in months_since(since) {
+        if !is_published(year, month) || load_baeteil_list(&conn, year, month)?.is_none() {
+            missing_months.push((year, month));
+        }
+    }
+    Ok(missing_months)
+}
+
+pub async fn prefetch_baeteil_month(pipeline: &Pipeline, year: i32, month: u32) -> Result<(), Box<dyn Error>> {
+    let year_text = year.to_string();
+    let month_text = month.to_string();
+    let body = pipeline
+        .post_form_text(&fixtures::baduk_url("/record/rankingPlayer_in.asp"), &[("pageNo", "1"), ("keyColumn", &year_text), ("keyWord", &month_text), ("etcKey1", "1")])
+        .await?;
+    let (ratings, ranking_month) = parse_player_ratings_on_baeteil(&body)?;
+    store_baeteil_list(&mut open_archive()?, year, month, &ratings, &ranking_month)
+}
+
+// goratings 첫 페이지는 현재 값만 제공하므로 받을 때마다 날짜별 스냅샷으로 보관합니다.
+pub fn snapshot_goratings() -> Result<HashMap<String, f64>, Box<dyn Error>> {
+    let mut conn = open_archive()?;
+    let goratings_players = parse_player_ratings_on_goratings(&cache::get_text(&fixtures::goratings_url("/en/"))?);
+    let today = Utc::now().date_naive().to_string();
+
+    let tx = conn.transaction()?;
+    for (id, name, rating) in &goratings_players {
+        tx.execute("INSERT OR REPLACE INTO goratings_snapshots (snapshot_date, name, rating) VALUES (?1, ?2, ?3)", params![today, name, rating])?;
+        if let Some(id) = id {
+            tx.execute("INSERT OR REPLACE INTO goratings_ids (goratings_id, name) VALUES (?1, ?2)", params![id, name])?;
+        }
+    }
+    tx.commit()?;
+    Ok(goratings_players.into_iter().map(|(_, name, rating)| (name, rating)).collect())
+}
+
+pub fn latest_goratings_snapshot() -> Result<Option<(String, Ratings)>, Box<dyn Error>> {
+    let conn = open_archive()?;
+    let snapshot_date: Option<String> = conn.query_row("SELECT MAX(snapshot_date) FROM goratings_snapshots", [], |row| row.get(0))?;
+    let snapshot_date = match snapshot_date {
+        Some(snapshot_date) => snapshot_date,
+        None => return Ok(None),
+    };
+
+    let mut stmt = conn.prepare("SELECT name, rating FROM goratings_snapshots WHERE snapshot_date = ?1")?;
+    let ratings = stmt.query_map(params![snapshot_date], |row| Ok((row.get(0)?, row.get(1)?)))?.collect::<Result<HashMap<String, f64>, _>>()?;
+    Ok(Some((snapshot_date, ratings)))
+}
+
+// 내려받지 않고 보관된 랭킹만 돌려줍니다.
+pub fn archived_baeteil_list(year: i32, month: u32) -> Result<Option<HashMap<String, f64>>, Box<dyn Error>> {
+    let conn = open_archive()?;
+    Ok(load_baeteil_list(&conn, year, month)?.map(|(ratings, _)| ratings))
+}
+
+pub fn archived_baeteil_months() -> Result<Vec<(i32, u32)>, Box<dyn Error>> {
+    let conn = open_archive()?;
+    let mut stmt = conn.prepare("SELECT year, month FROM baeteil_lists ORDER BY year ASC, month ASC")?;
+    let months = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect::<Result<Vec<_>, _>>()?;
+    Ok(months)
+}
+
+// 해당 월에 저장한 goratings 스냅샷 중 가장 늦은 것을 돌려줍니다.
+pub fn goratings_snapshot_in_month(year: i32, month: u32) -> Result<Option<HashMap<String, f64>>, Box<dyn Error>> {
+    let conn = open_archive()?;
+    let snapshot_date: Option<String> = conn.query_row(
+        "SELECT MAX(snapshot_date) FROM goratings_snapshots WHERE snapshot_date LIKE ?1 || '%'",
+        params![format!("{:04}-{:02}", year, month)],
+        |row| row.get(0),
+    )?;
+    let snapshot_date = match snapshot_date {
+        Some(snapshot_date) => snapshot_date,
+        None => return Ok(None),
+    };
+
+    let mut stmt = conn.prepare("SELECT name, rating FROM goratings_snapshots WHERE snapshot_date = ?1")?;
+    let ratings = stmt.query_map(params![snapshot_date], |row| Ok((row.get(0)?, row.get(1)?)))?.collect::<Result<HashMap<String, f64>, _>>()?;
+    Ok(Some(ratings))
+}
+
+pub fn goratings_players() -> Result<GoratingsIds, Box<dyn Error>> {
+    let conn = open_archive()?;
+    let mut stmt = conn.prepare("SELECT goratings_id, name FROM goratings_ids")?;
+    let players = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect::<Result<Vec<_>, _>>()?;
+    Ok(players)
+}
+
+// 해당 날짜 이전의 가장 최근 스냅샷을 사용합니다. 그런 스냅샷이 없으면 기준일 이후의 레이팅을 쓰지 않도록 빈 목록을 돌려주며,
+// 기준일이 오늘이면 지금 스냅샷을 저장해 사용합니다.
+pub fn goratings_list_as_of(date: NaiveDate) -> Result<Ratings, Box<dyn Error>> {
+    let conn = open_archive()?;
+    let snapshot_date: Option<String> =
+        conn.query_row("SELECT MAX(snapshot_date) FROM goratings_snapshots WHERE snapshot_date <= ?1", params![date.to_string()], |row| row.get(0))?;
+    let snapshot_date = match snapshot_date {
+        Some(snapshot_date) => snapshot_date,
+        None if date >= Utc::now().date_naive() => return snapshot_goratings(),
+        None => return Ok(HashMap::new()),
+    };
+
+    let mut stmt = conn.prepare("SELECT name, rating FROM goratings_snapshots WHERE snapshot_date = ?1")?;
+    let ratings = stmt.query_map(params![snapshot_date], |row| Ok((row.get(0)?, row.get(1)?)))?.collect::<Result<Ratings, _>>()?;
+    Ok(ratings)
+}
+
+pub fn baeteil_rating_as_of(name: &str, date: NaiveDate) -> Result<Option<f64>, Box<dyn Error>> {
+    let (ratings, _) = baeteil_list(date.year(), date.month())?;
+    Ok(ratings.get(name).copied())
+}
+
+pub fn goratings_rating_as_of(name: &str, date: NaiveDate) -> Result<Option<f64>, Box<dyn Error>> {
+    Ok(goratings_list_as_of(date)?.get(name).copied())
+}
+
+// 보관된 배태일 랭킹에서 기사의 월별 레이팅을 오래된 순서로 돌려줍니다. 내려받지 않은 달은 건너뜁니다.
+pub fn archived_baeteil_history(name: &str, since: NaiveDate, until: NaiveDate) -> Result<Vec<(NaiveDate, f64)>, Box<dyn Error>> {
+    let conn = open_archive()?;
+    let mut stmt = conn.prepare(
+        "SELECT year, month, rating FROM baeteil_ratings
+         WHERE name = ?1 AND (year * 12 + month) BETWEEN ?2 AND ?3
+         ORDER BY year ASC, month ASC",
+    )?;
+    let history = stmt
+        .query_map(params![name, since.year() * 12 + since.month() as i32, until.year() * 12 + until.month() as i32], |row| {
+            Ok((row.get::<_, i32>(0)?, row.get::<_, u32>(1)?, row.get::<_, f64>(2)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(history.into_iter().filter_map(|(year, month, rating)| NaiveDate::from_ymd_opt(year, month, 1).map(|date| (date, rating))).collect())
+}
+
+fn months_since(since: NaiveDate) -> Vec<(i32, u32)> {
+    let today = Utc::now().date_naive();
+    let mut months = Vec::new();
+    let mut year = since.year();
+    let mut month = since.month();
+    while (year, month) <= (today.year(), today.month()) {
+        months.push((year, month));
+        if month == 12 {
+            year += 1;
+            month = 1;
+        } else {
+            month += 1;
+        }
+    }
+    months
+}
+
+pub fn download_baeteil_history(since: NaiveDate) -> Result<usize, Box<dyn Error>> {
+    let months = missing_baeteil_months(since)?;
+    let pipeline = Pipeline::new();
+    let results = pipeline::block_on(join_all(months.iter().map(|(year, month)| prefetch_baeteil_month(&pipeline, *year, *month))));
+    for result in &results {
+        if let Err(e) = result {
+            println!("랭킹을 내려받는 중 오류가 발생했습니다: {}", e);
+        }
+    }
+    Ok(results.iter().filter(|result| result.is_ok()).count())
+}
+
+fn print_archive_stats() -> Result<(), Box<dyn Error>> {
+    let conn = open_archive()?;
+    let (month_count, first_month, last_month): (i64, Option<String>, Option<String>) = conn.query_row(
+        "SELECT COUNT(*), MIN(printf('%04d-%02d', year, month)), MAX(printf('%04d-%02d', year, month)) FROM baeteil_lists",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )?;
+    let (snapshot_count, first_snapshot, last_snapshot): (i64, Option<String>, Option<String>) = conn.query_row(
+        "SELECT COUNT(DISTINCT snapshot_date), MIN(snapshot_date), MAX(snapshot_date) FROM goratings_snapshots",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )?;
+
+    println!("\n배태일 월별 랭킹: {}개월 ({} ~ {})", month_count, first_month.unwrap_or_default(), last_month.unwrap_or_default());
+    println!("goratings 스냅샷: {}개 ({} ~ {})", snapshot_count, first_snapshot.unwrap_or_default(), last_snapshot.unwrap_or_default());
+    Ok(())
+}
+
+fn lookup_rating_as_of() -> Result<(), Box<dyn Error>> {
+    println!("기사 이름(한글 또는 영문)을 입력하세요: ");
+    let mut name = String::new();
+    io::stdin().read_line(&mut name).expect("입력을 읽는 데 실패했습니다.");
+    let name = name.trim();
+
+    println!("기준 날짜를 입력하세요 (YYYY-MM-DD): ");
+    let mut date = String::new();
+    io::stdin().read_line(&mut date).expect("입력을 읽는 데 실패했습니다.");
+    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").map_err(|_| "날짜 형식이 올바르지 않습니다.")?;
+
+    match baeteil_rating_as_of(name, date)? {
+        Some(rating) => println!("{} 기준 배태일 레이팅: {}", date, rating),
+        None => println!("{} 기준 배태일 랭킹에 {} 기사가 없습니다.", date, name),
+    }
+    match goratings_rating_as_of(name, date)? {
+        Some(rating) => println!("{} 기준 goratings 레이팅: {}", date, rating),
+        None => println!("{} 기준 goratings 스냅샷에 {} 기사가 없습니다.", date, name),
+    }
+    Ok(())
+}
+
+pub fn execute_rating_archive_menu() {
+    loop {
+        println!("\n선택할 옵션:");
+        println!("1. 배태일 월별 랭킹 내려받기");
+        println!("2. goratings 스냅샷 저장");
+        println!("3. 아카이브 현황");
+        println!("4. 날짜 기준 레이팅 조회");
+        println!("5. 대국 기록 레이팅(WHR)과 비교");
+        println!("6. 레이팅 환산 곡선 맞추기");
+        println!("exit. 돌아가기");
+
+        let mut option = String::new();
+        io::stdin().read_line(&mut option).expect("입력을 읽는 데 실패했습니다.");
+
+        let result = match option.trim() {
+            "1" => download_baeteil_history(records::history_start_date()).map(|downloaded| println!("배태일 랭킹 {}개월을 내려받았습니다.", downloaded)),
+            "2" => snapshot_goratings().map(|ratings| println!("goratings 기사 {}명의 레이팅을 저장했습니다.", ratings.len())),
+            "3" => print_archive_stats(),
+            "4" => lookup_rating_as_of(),
+            "5" => whr::compare_with_federation_lists(),
+            "6" => conversion::fit_conversions(),
+            "exit" => break,
+            _ => {
+                println!("잘못된 입력입니다. 다시 입력해주세요.");
+                Ok(())
+            }
+        };
+
+        if let Err(e) = result {
+            println!("오류가 발생했습니다: {}", e);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use crate::config;
+use crate::conversion;
+use crate::models::{DataQuality, Player};
+use crate::registry::Registry;
+use crate::utils::baeteil_to_goratings;
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+
+// 레이팅은 모두 goratings 기준으로 환산하여 돌려줍니다.
+pub struct SourcedRating {
+    pub rating: f64,
+    pub source: &'static str,
+    pub date: NaiveDate,
+    pub quality: DataQuality,
+}
+
+pub trait RatingSource {
+    fn name(&self) -> &'static str;
+    fn lookup(&self, player: &Player) -> Option<SourcedRating>;
+    fn is_empty(&self) -> bool;
+}
+
+pub fn source_label(name: &str) -> &str {
+    match name {
+        "baeteil" => "배태일",
+        "goratings" => "goratings",
+        "manual" => "수동 입력",
+        "whr" => "대국 기록(WHR)",
+        "federation" => "다른 기원 목록",
+        _ => name,
+    }
+}
+
+// 기본 순서나 기사별 순서에 들어 있는 출처만 준비합니다.
+pub fn is_in_use(source: &str) -> bool {
+    config::get().rating_source_order.iter().any(|name| name == source) || Registry::load().uses_rating_source(source)
+}
+
+fn find_rating<V: Copy>(ratings: &HashMap<String, V>, name: &str, aliases: &[String]) -> Option<V> {
+    ratings.get(name).or_else(|| aliases.iter().find_map(|alias| ratings.get(alias))).copied()
+}
+
+pub struct BaeteilSource {
+    ratings: HashMap<String, f64>,
+    date: NaiveDate,
+    quality: DataQuality,
+}
+
+impl BaeteilSource {
+    pub fn new(ratings: HashMap<String, f64>, date: NaiveDate, quality: DataQuality) -> BaeteilSource {
+        BaeteilSource { ratings, date, quality }
+    }
+}
+
+impl RatingSource for BaeteilSource {
+    fn name(&self) -> &'static str {
+        "baeteil"
+    }
+
+    fn lookup(&self, player: &Player) -> Option<SourcedRating> {
+        find_rating(&self.ratings, player.korean_name(), player.aliases()).map(|rating| SourcedRating {
+            rating: baeteil_to_goratings(rating),
+            source: self.name(),
+            date: self.date,
+            quality: self.quality.clone(),
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.ratings.is_empty()
+    }
+}
+
+pub struct GoratingsSource {
+    ratings: HashMap<String, f64>,
+    date: NaiveDate,
+    quality: DataQuality,
+}
+
+impl GoratingsSource {
+    pub fn new(ratings: HashMap<String, f64>, date: NaiveDate, quality: DataQuality) -> GoratingsSource {
+        GoratingsSource { ratings, date, quality }
+    }
+}
+
+impl RatingSource for GoratingsSource {
+    fn name(&self) -> &'static str {
+        "goratings"
+    }
+
+    fn lookup(&self, player: &Player) -> Option<SourcedRating> {
+        find_rating(&self.ratings, player.english_name(), player.aliases()).map(|rating| SourcedRating {
+            rating,
+            source: self.name(),
+            date: self.date,
+            quality: self.quality.clone(),
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.ratings.is_empty()
+    }
+}
+
+// 저장된 대국 기록으로 직접 계산한 레이팅입니다. 외국 기사나 은퇴한 기사처럼 랭킹에 없는 기사도 대국 기록이 있으면 찾을 수 있습니다.
+pub struct WholeHistorySource {
+    ratings: HashMap<String, f64>,
+    date: NaiveDate,
+}
+
+impl WholeHistorySource {
+    pub fn new(ratings: HashMap<String, f64>, date: NaiveDate) -> WholeHistorySource {
+        WholeHistorySource { ratings, date }
+    }
+}
+
+impl RatingSource for WholeHistorySource {
+    fn name(&self) -> &'static str {
+        "whr"
+    }
+
+    fn lookup(&self, player: &Player) -> Option<SourcedRating> {
+        find_rating(&self.ratings, player.korean_name(), player.aliases()).map(|rating| SourcedRating {
+            rating,
+            source: self.name(),
+            date: self.date,
+            quality: DataQuality::Fresh,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.ratings.is_empty()
+    }
+}
+
+// rating_lists 폴더에서 가져온 다른 기원의 레이팅을 맞춘 환산 곡선으로 goratings에 옮긴 것입니다. goratings 영문 이름으로 찾습니다.
+#[derive(Default)]
+pub struct FederationSource {
+    ratings: HashMap<String, (f64, NaiveDate)>,
+}
+
+impl FederationSource {
+    pub fn load(as_of: NaiveDate) -> Result<FederationSource, Box<dyn Error>> {
+        Ok(FederationSource { ratings: conversion::imported_ratings(as_of)? })
+    }
+}
+
+impl RatingSource for FederationSource {
+    fn name(&self) -> &'static str {
+        "federation"
+    }
+
+    fn lookup(&self, player: &Player) -> Option<SourcedRating> {
+        find_rating(&self.ratings, player.english_name(), player.aliases()).map(|(rating, date)| SourcedRating {
+            rating,
+            source: self.name(),
+            date,
+            quality: DataQuality::Fresh,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.ratings.is_empty()
+    }
+}
+
+#[derive(Default)]
+pub struct ManualSource {
+    ratings: HashMap<String, (f64, NaiveDate)>,
+}
+
+impl ManualSource {
+    // korean_name,rating,scale,date 형식의 CSV를 읽습니다. scale은 baeteil, goratings 또는 환산 곡선을 맞춘 척도입니다.
+    pub fn load() -> Result<ManualSource, Box<dyn Error>> {
+        let path = config::get().manual_ratings_file;
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(_) => return Ok(ManualSource { ratings: HashMap::new() }),
+        };
+
+        let mut ratings = HashMap::new();
+        for (line_no, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with("korean_name") {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').map(|field| field.trim()).collect();
+            if fields.len() < 4 {
+                return Err(format!("{} {}번째 줄의 형식이 올바르지 않습니다: {}", path, line_no + 1, line).into());
+            }
+            let rating = fields[1].parse::<f64>().map_err(|_| format!("{} {}번째 줄의 레이팅이 올바르지 않습니다: {}", path, line_no + 1, fields[1]))?;
+            let rating = conversion::scale_to_goratings(fields[2], rating).map_err(|e| format!("{} {}번째 줄: {}", path, line_no + 1, e))?;
+            let date = NaiveDate::parse_from_str(fields[3], "%Y-%m-%d").map_err(|_| format!("{} {}번째 줄의 날짜가 올바르지 않습니다: {}", path, line_no + 1, fields[3]))?;
+            ratings.insert(fields[0].to_string(), (rating, date));
+        }
+        Ok(ManualSource { ratings })
+    }
+}
+
+impl RatingSource for ManualSource {
+    fn name(&self) -> &'static str {
+        "manual"
+    }
+
+    fn lookup(&self, player: &Player) -> Option<SourcedRating> {
+        find_rating(&self.ratings, player.korean_name(), player.aliases()).map(|(rating, date)| SourcedRating {
+            rating,
+            source: self.name(),
+            date,
+            quality: DataQuality::Fresh,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.ratings.is_empty()
+    }
+}
+
+// 기사별로 지정한 순서가 있으면 그 순서를, 없으면 설정의 기본 순서를 따라 처음 찾은 레이팅을 사용합니다.
+pub struct RatingChain {
+    sources: Vec<Box<dyn RatingSource>>,
+    registry: Registry,
+}
+
+impl RatingChain {
+    pub fn new(sources: Vec<Box<dyn RatingSource>>) -> RatingChain {
+        RatingChain { sources, registry: Registry::load() }
+    }
+
+    pub fn order_for(&self, player: &Player) -> Vec<String> {
+        match self.registry.get(player.korean_name()) {
+            Some(entry) if !entry.rating_sources.is_empty() => entry.rating_sources.clone(),
+            _ => config::get().rating_source_order,
+        }
+    }
+
+    pub fn lookup(&self, player: &Player) -> Option<SourcedRating> {
+        self.order_for(player)
+            .iter()
+            .filter_map(|name| self.sources.iter().find(|source| source.name() == name))
+            .find_map(|source| source.lookup(player))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sources.iter().all(|source| source.is_empty())
+    }
+}
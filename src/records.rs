@@ -0,0 +1,399 @@
+use crate::config;
+use crate::error::DataError;
+use crate::events;
+use crate::fixtures;
+use crate::models::GameRecord;
+use crate::pipeline::{self, Pipeline};
+use crate::registry::{self, Registry};
+use crate::season;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use futures::future::join_all;
+use rusqlite::{params, Connection, OptionalExtension};
+use scraper::{Html, Selector};
+use std::collections::HashMap;
+use std::error::Error;
+use std::io;
+use std::sync::{Mutex, OnceLock};
+
+struct DiaryRow {
+    date: NaiveDate,
+    event_name: String,
+    winner_name: String,
+    loser_name: String,
+    winner_color: String,
+    result: String,
+}
+
+pub fn open_database() -> Result<Connection, DataError> {
+    let conn = Connection::open(&config::get().records_db)?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS players (
+            name TEXT PRIMARY KEY,
+            code INTEGER NOT NULL,
+            synced_at INTEGER,
+            last_game_date TEXT
+        );
+        CREATE TABLE IF NOT EXISTS games (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            date TEXT NOT NULL,
+            event_name TEXT NOT NULL,
+            winner_name TEXT NOT NULL,
+            loser_name TEXT NOT NULL,
+            winner_color TEXT NOT NULL,
+            result TEXT NOT NULL,
+            winner_code INTEGER,
+            loser_code INTEGER,
+            seq INTEGER NOT NULL,
+            UNIQUE (date, event_name, winner_name, loser_name, seq)
+        );
+        CREATE INDEX IF NOT EXISTS games_winner ON games (winner_name, date);
+        CREATE INDEX IF NOT EXISTS games_loser ON games (loser_name, date);",
+    )?;
+    Ok(conn)
+}
+
+fn parse_diary_page(body: &str) -> Vec<DiaryRow> {
+    let document = Html::parse_document(body);
+    let match_selector = Selector::parse("tbody>tr").unwrap();
+    let date_selector = Selector::parse("td.no").unwrap();
+    let td_selector = Selector::parse("td").unwrap();
+
+    let mut rows = Vec::new();
+    for selected_match in document.select(&match_selector) {
+        if let Some(date_element) = selected_match.select(&date_selector).next() {
+            let date_text = date_element.text().collect::<String>();
+            if let Ok(date) = NaiveDate::parse_from_str(date_text.trim(), "%Y-%m-%d") {
+                let td_texts: Vec<String> = selected_match.select(&td_selector).map(|element| element.text().collect::<String>().trim().to_string()).collect();
+                if td_texts.len() < 5 {
+                    continue;
+                }
+                let winner_color = if td_texts[4].contains("백") { "백" } else { "흑" };
+                rows.push(DiaryRow {
+                    date,
+                    event_name: td_texts[1].clone(),
+                    winner_name: td_texts[2].clone(),
+                    loser_name: td_texts[3].clone(),
+                    winner_color: winner_color.to_string(),
+                    result: td_texts[4].clone(),
+                });
+            }
+        }
+    }
+    rows
+}
+
+pub fn history_start_date() -> NaiveDate {
+    let start_date = Utc::now().date_naive() - Duration::try_days(config::get().records_history_days as i64).unwrap();
+    NaiveDate::from_ymd_opt(start_date.year(), start_date.month(), 1).unwrap()
+}
+
+pub fn sync_player(name: &str) -> Result<usize, DataError> {
+    pipeline::block_on(sync_player_async(&Pipeline::new(), name))
+}
+
+pub async fn sync_player_async(pipeline: &Pipeline, name: &str) -> Result<usize, DataError> {
+    let code = registry::resolve_federation_code(pipeline, name).await?;
+    let known: Option<(i32, Option<String>)> = open_database()?
+        .query_row("SELECT code, last_game_date FROM players WHERE name = ?1", params![name], |row| Ok((row.get(0)?, row.get(1)?)))
+        .optional()?;
+
+    // 기사 코드가 바뀌었다면(동명이인을 다시 지정한 경우) 처음부터 다시 읽습니다.
+    let last_game_date = match known {
+        Some((known_code, last_game_date)) if known_code == code => last_game_date.and_then(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok()),
+        _ => None,
+    };
+
+    // 마지막으로 저장된 날짜의 대국은 일부만 저장되었을 수 있으므로 그 날짜부터 다시 읽습니다.
+    let stop_date = last_game_date.unwrap_or_else(history_start_date);
+
+    let mut rows = Vec::new();
+    let mut page_no = 1;
+    loop {
+        let url = fixtures::baduk_url(&format!("/record/diary_in.asp?foreignKey=&pageNo={}&keyWord={}&etcKey=&etc2=1", page_no, code));
+        let page_rows = parse_diary_page(&pipeline.get_text(&url).await?);
+        if page_rows.is_empty() {
+            break;
+        }
+        let reached_stop_date = page_rows.iter().any(|row| row.date < stop_date);
+        rows.extend(page_rows.into_iter().filter(|row| row.date >= stop_date));
+        if reached_stop_date {
+            break;
+        }
+        page_no += 1;
+    }
+
+    let registry = Registry::load();
+    let own_names = registry.own_korean_names(name);
+    for row in rows.iter_mut() {
+        if own_names.contains(&row.winner_name) {
+            row.winner_name = name.to_string();
+            row.loser_name = registry.canonical_korean_name(&row.loser_name);
+        } else if own_names.contains(&row.loser_name) {
+            row.loser_name = name.to_string();
+            row.winner_name = registry.canonical_korean_name(&row.winner_name);
+        }
+    }
+
+    let mut conn = open_database()?;
+    let codes: HashMap<String, i32> = {
+        let mut stmt = conn.prepare("SELECT name, code FROM players")?;
+        let codes = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect::<Result<_, _>>()?;
+        codes
+    };
+
+    let tx = conn.transaction()?;
+    let mut inserted = 0;
+    let mut seqs: HashMap<(NaiveDate, String, String, String), i32> = HashMap::new();
+    for row in &rows {
+        // 같은 날 같은 대국자끼리 여러 판을 둔 경우를 구분합니다.
+        let seq = seqs.entry((row.date, row.event_name.clone(), row.winner_name.clone(), row.loser_name.clone())).or_insert(0);
+        let winner_code = if row.winner_name == name { Some(code) } else { codes.get(&row.winner_name).copied() };
+        let loser_code = if row.loser_name == name { Some(code) } else { codes.get(&row.loser_name).copied() };
+        inserted += tx.execute(
+            "INSERT OR IGNORE INTO games (date, event_name, winner_name, loser_name, winner_color, result, winner_code, loser_code, seq)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![row.date.to_string(), row.event_name, row.winner_name, row.loser_name, row.winner_color, row.result, winner_code, loser_code, *seq],
+        )?;
+        *seq += 1;
+    }
+
+    tx.execute("UPDATE games SET winner_code = ?1 WHERE winner_name = ?2 AND winner_code IS NULL", params![code, name])?;
+    tx.execute("UPDATE games SET loser_code = ?1 WHERE loser_name = ?2 AND loser_code IS NULL", params![code, name])?;
+
+    let newest_date = rows.iter().map(|row| row.date).max().or(last_game_date);
+    tx.execute(
+        "INSERT INTO players (name, code, synced_at, last_game_date) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(name) DO UPDATE SET code = excluded.code, synced_at = excluded.synced_at, last_game_date = excluded.last_game_date",
+        params![name, code, Utc::now().timestamp(), newest_date.map(|date| date.to_string())],
+    )?;
+    tx.commit()?;
+
+    Ok(inserted)
+}
+
+fn synced_at(name: &str) -> Result<Option<i64>, DataError> {
+    Ok(open_database()?
+        .query_row("SELECT synced_at FROM players WHERE name = ?1", params![name], |row| row.get(0))
+        .optional()?
+        .flatten())
+}
+
+pub fn last_synced_date(name: &str) -> Result<Option<NaiveDate>, DataError> {
+    Ok(synced_at(name)?.and_then(|synced_at| DateTime::from_timestamp(synced_at, 0)).map(|synced_at| synced_at.date_naive()))
+}
+
+fn needs_sync(name: &str) -> Result<bool, DataError> {
+    let stale_after = config::get().records_sync_hours as i64 * 3600;
+    Ok(synced_at(name)?.is_none_or(|synced_at| Utc::now().timestamp() - synced_at >= stale_after))
+}
+
+// 이번 실행에서 동기화에 실패한 기사는 조회할 때마다 다시 요청하지 않고 저장된 기록을 그대로 사용합니다.
+// 실패한 기사마다 그때의 오류 메시지를 남겨 두어 다시 조회할 때도 원인을 보여줍니다.
+fn failed_syncs() -> &'static Mutex<HashMap<String, String>> {
+    static FAILED_SYNCS: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    FAILED_SYNCS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn sync_error(name: &str, message: String) -> DataError {
+    DataError::Sync { name: name.to_string(), message }
+}
+
+pub fn ensure_synced(name: &str) -> Result<(), DataError> {
+    if !needs_sync(name)? {
+        return Ok(());
+    }
+    let previous_failure = failed_syncs().lock().unwrap().get(name).cloned();
+    let result = match previous_failure {
+        Some(message) => Err(message),
+        None => sync_player(name).map(|_| ()).map_err(|e| e.to_string()),
+    };
+    match result {
+        Ok(()) => Ok(()),
+        Err(message) => {
+            failed_syncs().lock().unwrap().insert(name.to_string(), message.clone());
+            if synced_at(name)?.is_some() {
+                Ok(())
+            } else {
+                Err(sync_error(name, message))
+            }
+        }
+    }
+}
+
+pub async fn ensure_synced_async(pipeline: &Pipeline, name: &str) -> Result<usize, DataError> {
+    if !needs_sync(name)? {
+        return Ok(0);
+    }
+    match sync_player_async(pipeline, name).await {
+        Ok(count) => {
+            failed_syncs().lock().unwrap().remove(name);
+            Ok(count)
+        },
+        Err(e) => {
+            failed_syncs().lock().unwrap().insert(name.to_string(), e.to_string());
+            Err(sync_error(name, e.to_string()))
+        }
+    }
+}
+
+pub fn sync_players(names: &[String]) -> Vec<Result<usize, DataError>> {
+    let progress = pipeline::progress_bar(names.len() as u64);
+    let pipeline = Pipeline::with_progress(progress.clone());
+    let pipeline = &pipeline;
+    let results = pipeline::block_on(join_all(names.iter().map(|name| async move {
+        let result = sync_player_async(pipeline, name).await;
+        pipeline.progress().set_message(name.clone());
+        pipeline.progress().inc(1);
+        result
+    })));
+    progress.finish_and_clear();
+    results
+}
+
+fn query_games(sql: &str, params: &[&dyn rusqlite::ToSql]) -> Result<Vec<GameRecord>, Box<dyn Error>> {
+    let conn = open_database()?;
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(params, |row| {
+        let date: String = row.get(0)?;
+        Ok((date, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?))
+    })?;
+
+    let mut games = Vec::new();
+    for row in rows {
+        let (date, event_name, winner_name, loser_name, winner_color) = row?;
+        let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")?;
+        games.push(GameRecord::new(date, event_name, winner_name, loser_name, winner_color));
+    }
+    Ok(games)
+}
+
+// 오래된 대국부터 반환합니다. 같은 날의 대국은 기보 목록의 역순(먼저 둔 대국부터)입니다.
+// 백테스트에서 경기 전날까지의 상태를 다시 만들 수 있도록 기간의 끝(until)까지 포함하여 조회합니다.
+pub fn player_games_between(name: &str, since: NaiveDate, until: NaiveDate) -> Result<Vec<GameRecord>, Box<dyn Error>> {
+    ensure_synced(name)?;
+    query_games(
+        "SELECT date, event_name, winner_name, loser_name, winner_color FROM games
+         WHERE (winner_name = ?1 OR loser_name = ?1) AND date >= ?2 AND date <= ?3
+         ORDER BY date ASC, id DESC",
+        &[&name, &since.to_string(), &until.to_string()],
+    )
+}
+
+pub fn head_to_head_games_between(name1: &str, name2: &str, since: NaiveDate, until: NaiveDate) -> Result<Vec<GameRecord>, Box<dyn Error>> {
+    ensure_synced(name1)?;
+    query_games(
+        "SELECT date, event_name, winner_name, loser_name, winner_color FROM games
+         WHERE ((winner_name = ?1 AND loser_name = ?2) OR (winner_name = ?2 AND loser_name = ?1)) AND date >= ?3 AND date <= ?4
+         ORDER BY date ASC, id DESC",
+        &[&name1, &name2, &since.to_string(), &until.to_string()],
+    )
+}
+
+// 기간 안의 저장된 대국을 모두 날짜순으로 돌려줍니다. 동기화된 기사의 대국만 저장되어 있습니다.
+pub fn games_between(since: NaiveDate, until: NaiveDate) -> Result<Vec<GameRecord>, Box<dyn Error>> {
+    query_games(
+        "SELECT date, event_name, winner_name, loser_name, winner_color FROM games
+         WHERE date >= ?1 AND date <= ?2
+         ORDER BY date ASC, id DESC",
+        &[&since.to_string(), &until.to_string()],
+    )
+}
+
+// 대회명에 text가 들어 있는 대국을 모두 돌려줍니다. 동기화된 기사의 대국만 저장되어 있습니다.
+pub fn games_with_event_text(text: &str) -> Result<Vec<GameRecord>, Box<dyn Error>> {
+    query_games(
+        "SELECT date, event_name, winner_name, loser_name, winner_color FROM games
+         WHERE event_name LIKE '%' || ?1 || '%'
+         ORDER BY date ASC, id DESC",
+        &[&text],
+    )
+}
+
+fn print_database_stats() -> Result<(), Box<dyn Error>> {
+    let conn = open_database()?;
+    let player_count: i64 = conn.query_row("SELECT COUNT(*) FROM players", [], |row| row.get(0))?;
+    let (game_count, first_date, last_date): (i64, Option<String>, Option<String>) =
+        conn.query_row("SELECT COUNT(*), MIN(date), MAX(date) FROM games", [], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+
+    println!("\n데이터베이스: {}", config::get().records_db);
+    println!("동기화된 기사: {}명", player_count);
+    println!("저장된 대국: {}국 ({} ~ {})", game_count, first_date.unwrap_or_default(), last_date.unwrap_or_default());
+    Ok(())
+}
+
+// 새 대회가 대회 목록에 없으면 기타 기전으로 분류되므로, 저장된 대회명의 분류를 확인할 수 있게 합니다.
+fn print_event_classification() -> Result<(), Box<dyn Error>> {
+    let conn = open_database()?;
+    let mut stmt = conn.prepare("SELECT event_name, COUNT(*) FROM games GROUP BY event_name ORDER BY MAX(date) DESC")?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+
+    let catalogue = events::catalogue();
+    println!();
+    for row in rows {
+        let (event_name, game_count) = row?;
+        println!("{} ({}국): {}", event_name, game_count, catalogue.classify(&event_name).describe());
+    }
+    Ok(())
+}
+
+pub fn execute_records_menu() {
+    loop {
+        println!("\n선택할 옵션:");
+        println!("1. 현재 시즌 전체 기사 동기화");
+        println!("2. 기사 이름으로 동기화");
+        println!("3. 저장된 기록 현황");
+        println!("4. 대회 분류 현황");
+        println!("exit. 돌아가기");
+
+        let mut option = String::new();
+        io::stdin().read_line(&mut option).expect("입력을 읽는 데 실패했습니다.");
+
+        match option.trim() {
+            "1" => {
+                let season = match season::load_current_season() {
+                    Ok(season) => season,
+                    Err(e) => {
+                        println!("시즌 파일을 불러오는 동안 오류가 발생했습니다: {}", e);
+                        continue;
+                    }
+                };
+                let players: Vec<(String, String)> = season.teams().iter()
+                    .flat_map(|team| team.players().iter().map(move |player| (player.korean_name().clone(), team.team_name().clone())))
+                    .collect();
+                let names: Vec<String> = players.iter().map(|(name, _)| name.clone()).collect();
+                for ((name, team_name), result) in players.iter().zip(sync_players(&names)) {
+                    match result {
+                        Ok(inserted) => println!("{} ({}): 새 대국 {}국", name, team_name, inserted),
+                        Err(e) => println!("{} ({}): 동기화 중 오류가 발생했습니다: {}", name, team_name, e),
+                    }
+                }
+            },
+            "2" => {
+                println!("기사 이름을 입력하세요: ");
+                let mut name = String::new();
+                io::stdin().read_line(&mut name).expect("입력을 읽는 데 실패했습니다.");
+                let name = name.trim();
+                if name.is_empty() {
+                    println!("잘못된 입력입니다. 다시 입력해주세요.");
+                    continue;
+                }
+                match sync_player(name) {
+                    Ok(inserted) => println!("{}: 새 대국 {}국", name, inserted),
+                    Err(e) => println!("{}: 동기화 중 오류가 발생했습니다: {}", name, e),
+                }
+            },
+            "3" => {
+                if let Err(e) = print_database_stats() {
+                    println!("기록을 읽는 동안 오류가 발생했습니다: {}", e);
+                }
+            },
+            "4" => {
+                if let Err(e) = print_event_classification() {
+                    println!("기록을 읽는 동안 오류가 발생했습니다: {}", e);
+                }
+            },
+            "exit" => break,
+            _ => println!("잘못된 입력입니다. 다시 입력해주세요."),
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use crate::config;
+use crate::error::DataError;
+use crate::fixtures;
+use crate::models::{Player, Team};
+use crate::pipeline::{self, Pipeline};
+use crate::season;
+use regex::Regex;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::sync::Once;
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegistryEntry {
+    pub korean_name: String,
+    pub federation_code: Option<i32>,
+    pub goratings_id: Option<u32>,
+    pub korean_aliases: Vec<String>,
+    pub english_aliases: Vec<String>,
+    pub chinese_aliases: Vec<String>,
+    pub rating_sources: Vec<String>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Registry {
+    players: Vec<RegistryEntry>,
+    // 파일을 해석하지 못했을 때의 오류입니다. 이 경우 사용자가 입력한 별칭과 동명이인 지정을 덮어쓰지 않도록 저장하지 않습니다.
+    #[serde(skip)]
+    load_error: Option<String>,
+}
+
+pub struct SearchCandidate {
+    pub name: String,
+    pub code: i32,
+    pub description: String,
+}
+
+// "김정현(大)", "이지현(남)"처럼 동명이인 구분 표기가 붙은 이름에서 검색에 쓸 이름만 남깁니다.
+pub fn base_korean_name(name: &str) -> &str {
+    name.split('(').next().unwrap_or(name).trim()
+}
+
+impl Registry {
+    pub fn load() -> Registry {
+        let registry_file = config::get().registry_file;
+        let text = match fs::read_to_string(&registry_file) {
+            Ok(text) => text,
+            Err(_) => return Registry::default(),
+        };
+        match serde_json::from_str::<Registry>(&text) {
+            Ok(registry) => registry,
+            Err(e) => {
+                static WARNED: Once = Once::new();
+                WARNED.call_once(|| println!("경고: {} 파일을 해석하지 못해 기사 식별 정보 없이 진행하며, 파일을 고치기 전까지는 저장하지 않습니다: {}", registry_file, e));
+                Registry { players: Vec::new(), load_error: Some(e.to_string()) }
+            }
+        }
+    }
+
+    pub fn is_writable(&self) -> bool {
+        self.load_error.is_none()
+    }
+
+    pub fn save(&self) -> Result<(), DataError> {
+        let registry_file = config::get().registry_file;
+        if let Some(e) = &self.load_error {
+            return Err(DataError::Storage(format!("{} 파일을 해석하지 못했으므로 덮어쓰지 않습니다: {}", registry_file, e)));
+        }
+        fs::write(&registry_file, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, korean_name: &str) -> Option<&RegistryEntry> {
+        self.players.iter().find(|entry| entry.korean_name == korean_name)
+    }
+
+    pub fn uses_rating_source(&self, source: &str) -> bool {
+        self.players.iter().any(|entry| entry.rating_sources.iter().any(|name| name == source))
+    }
+
+    pub fn entry_mut(&mut self, korean_name: &str) -> &mut RegistryEntry {
+        if let Some(index) = self.players.iter().position(|entry| entry.korean_name == korean_name) {
+            return &mut self.players[index];
+        }
+        self.players.push(RegistryEntry { korean_name: korean_name.to_string(), ..Default::default() });
+        self.players.last_mut().unwrap()
+    }
+
+    pub fn all_aliases(&self, korean_name: &str) -> Vec<String> {
+        match self.get(korean_name) {
+            Some(entry) => entry.korean_aliases.iter().chain(&entry.english_aliases).chain(&entry.chinese_aliases).cloned().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn canonical_korean_name(&self, name: &str) -> String {
+        self.players
+            .iter()
+            .find(|entry| entry.korean_aliases.iter().any(|alias| alias == name))
+            .map(|entry| entry.korean_name.clone())
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    // 기보 목록에서 해당 기사 본인으로 볼 수 있는 이름들입니다.
+    pub fn own_korean_names(&self, korean_name: &str) -> Vec<String> {
+        let mut names = vec![korean_name.to_string(), base_korean_name(korean_name).to_string()];
+        if let Some(entry) = self.get(korean_name) {
+            names.extend(entry.korean_aliases.iter().cloned());
+        }
+        names
+    }
+
+    // 새로 알게 된 goratings id나 영문 별칭이 있으면 true를 돌려줍니다.
+    pub fn learn_goratings_ids(&mut self, goratings_players: &[(Option<u32>, String)], players: &[Player]) -> bool {
+        let mut changed = false;
+        for player in players {
+            let known = self.get(player.korean_name()).cloned().unwrap_or_default();
+            match known.goratings_id {
+                Some(goratings_id) => {
+                    // goratings에서 표기가 바뀌어도 id로 찾아 새 표기를 별칭으로 추가합니다.
+                    if let Some((_, name)) = goratings_players.iter().find(|(id, _)| *id == Some(goratings_id)) {
+                        if name != player.english_name() && !known.english_aliases.contains(name) {
+                            self.entry_mut(player.korean_name()).english_aliases.push(name.clone());
+                            changed = true;
+                        }
+                    }
+                },
+                None => {
+                    let english_names: Vec<&String> = std::iter::once(player.english_name()).chain(&known.english_aliases).collect();
+                    if let Some((Some(id), _)) = goratings_players.iter().find(|(_, name)| english_names.contains(&name)) {
+                        self.entry_mut(player.korean_name()).goratings_id = Some(*id);
+                        changed = true;
+                    }
+                },
+            }
+        }
+        changed
+    }
+}
+
+pub fn apply_aliases(teams: &mut [Team]) {
+    let registry = Registry::load();
+    for team in teams.iter_mut() {
+        for player in team.players_mut().iter_mut() {
+            let mut aliases = player.aliases().clone();
+            for alias in registry.all_aliases(player.korean_name()) {
+                if !aliases.contains(&alias) {
+                    aliases.push(alias);
+                }
+            }
+            player.set_aliases(aliases);
+        }
+    }
+}
+
+pub fn parse_search_candidates(body: &str) -> Vec<SearchCandidate> {
+    let re = Regex::new(r"choice\('([^']+)', ?'(\d+)', ?'\d+'\)").unwrap();
+    let choice_selector = Selector::parse("li[onclick]").unwrap();
+
+    let document = Html::parse_document(body);
+    document
+        .select(&choice_selector)
+        .filter_map(|element| {
+            let onclick = element.value().attr("onclick").unwrap_or_default();
+            let captures = re.captures(onclick)?;
+            Some(SearchCandidate {
+                name: captures.get(1)?.as_str().trim().to_string(),
+                code: captures.get(2)?.as_str().parse::<i32>().ok()?,
+                description: element.text().collect::<String>().split_whitespace().collect::<Vec<_>>().join(" "),
+            })
+        })
+        .collect()
+}
+
+pub async fn search_candidates(pipeline: &Pipeline, korean_name: &str) -> Result<Vec<SearchCandidate>, DataError> {
+    let url = fixtures::baduk_url(&format!("/common/search_pro.asp?keyword={}&R_name=player1&R_code=player1_code", base_korean_name(korean_name)));
+    Ok(parse_search_candidates(&pipeline.get_text(&url).await?))
+}
+
+fn describe_candidates(candidates: &[SearchCandidate]) -> String {
+    candidates.iter().map(|candidate| format!("{} (코드 {})", candidate.description, candidate.code)).collect::<Vec<_>>().join(", ")
+}
+
+pub async fn resolve_federation_code(pipeline: &Pipeline, korean_name: &str) -> Result<i32, DataError> {
+    let registry = Registry::load();
+    if let Some(code) = registry.get(korean_name).and_then(|entry| entry.federation_code) {
+        return Ok(code);
+    }
+
+    let candidates = search_candidates(pipeline, korean_name).await?;
+    let korean_aliases = registry.get(korean_name).map(|entry| entry.korean_aliases.clone()).unwrap_or_default();
+    let exact_matches: Vec<&SearchCandidate> = candidates.iter().filter(|candidate| candidate.name == korean_name || korean_aliases.contains(&candidate.name)).collect();
+
+    let code = if exact_matches.len() == 1 {
+        exact_matches[0].code
+    } else if candidates.len() == 1 {
+        candidates[0].code
+    } else if candidates.is_empty() {
+        return Err(DataError::NotFound { site: "한국기원".to_string(), name: korean_name.to_string() });
+    } else {
+        return Err(DataError::Ambiguous { name: korean_name.to_string(), candidates: describe_candidates(&candidates) });
+    };
+
+    let mut registry = Registry::load();
+    registry.entry_mut(korean_name).federation_code = Some(code);
+    if registry.is_writable() {
+        registry.save()?;
+    }
+    Ok(code)
+}
+
+fn print_registry_status(teams: &[Team]) {
+    let registry = Registry::load();
+    for team in teams {
+        println!("\n[{}]", team.team_name());
+        for player in team.players() {
+            match registry.get(player.korean_name()) {
+                Some(entry) => println!(
+                    "{}: 한국기원 코드 {}, goratings id {}, 별칭 {:?}, 레이팅 출처 {}",
+                    player.korean_name(),
+                    entry.federation_code.map_or("미확인".to_string(), |code| code.to_string()),
+                    entry.goratings_id.map_or("미확인".to_string(), |id| id.to_string()),
+                    registry.all_aliases(player.korean_name()),
+                    if entry.rating_sources.is_empty() { "기본값".to_string() } else { entry.rating_sources.join(" > ") }
+                ),
+                None => println!("{}: 미확인", player.korean_name()),
+            }
+        }
+    }
+}
+
+fn resolve_interactively(korean_name: &str) -> Result<(), Box<dyn Error>> {
+    let candidates = pipeline::block_on(search_candidates(&Pipeline::new(), korean_name))?;
+    if candidates.is_empty() {
+        println!("한국기원에서 {} 기사를 찾을 수 없습니다.", korean_name);
+        return Ok(());
+    }
+    println!("{} 기사의 검색 결과:", korean_name);
+    for (index, candidate) in candidates.iter().enumerate() {
+        println!("{}. {} (코드 {})", index + 1, candidate.description, candidate.code);
+    }
+    println!("해당하는 기사 번호를 입력하세요: ");
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
+    match input.trim().parse::<usize>() {
+        Ok(num) if num > 0 && num <= candidates.len() => {
+            let mut registry = Registry::load();
+            let entry = registry.entry_mut(korean_name);
+            entry.federation_code = Some(candidates[num - 1].code);
+            if candidates[num - 1].name != korean_name && candidates[num - 1].name != base_korean_name(korean_name) && !entry.korean_aliases.contains(&candidates[num - 1].name) {
+                entry.korean_aliases.push(candidates[num - 1].name.clone());
+            }
+            registry.save()?;
+            println!("{} 기사의 한국기원 코드를 {}(으)로 저장했습니다.", korean_name, candidates[num - 1].code);
+        },
+        _ => println!("잘못된 입력입니다. 다시 입력해주세요."),
+    }
+    Ok(())
+}
+
+fn add_alias() -> Result<(), Box<dyn Error>> {
+    println!("기사 이름(시즌 파일의 korean_name)을 입력하세요: ");
+    let mut name = String::new();
+    io::stdin().read_line(&mut name).expect("입력을 읽는 데 실패했습니다.");
+
+    println!("별칭 종류를 선택하세요 (1. 한국어, 2. 영어, 3. 중국어): ");
+    let mut kind = String::new();
+    io::stdin().read_line(&mut kind).expect("입력을 읽는 데 실패했습니다.");
+
+    println!("별칭을 입력하세요: ");
+    let mut alias = String::new();
+    io::stdin().read_line(&mut alias).expect("입력을 읽는 데 실패했습니다.");
+    let alias = alias.trim().to_string();
+    if alias.is_empty() {
+        println!("잘못된 입력입니다. 다시 입력해주세요.");
+        return Ok(());
+    }
+
+    let mut registry = Registry::load();
+    let entry = registry.entry_mut(name.trim());
+    let aliases = match kind.trim() {
+        "1" => &mut entry.korean_aliases,
+        "2" => &mut entry.english_aliases,
+        "3" => &mut entry.chinese_aliases,
+        _ => {
+            println!("잘못된 입력입니다. 다시 입력해주세요.");
+            return Ok(());
+        }
+    };
+    if !aliases.contains(&alias) {
+        aliases.push(alias);
+    }
+    registry.save()?;
+    println!("별칭을 저장했습니다.");
+    Ok(())
+}
+
+fn set_rating_sources() -> Result<(), Box<dyn Error>> {
+    println!("기사 이름(시즌 파일의 korean_name)을 입력하세요: ");
+    let mut name = String::new();
+    io::stdin().read_line(&mut name).expect("입력을 읽는 데 실패했습니다.");
+
+    println!("레이팅 출처를 우선순위대로 쉼표로 구분하여 입력하세요 (baeteil, goratings, manual, whr, federation / 기본값으로 되돌리려면 엔터): ");
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
+    let sources: Vec<String> = input.split(',').map(|source| source.trim().to_string()).filter(|source| !source.is_empty()).collect();
+    if sources.iter().any(|source| !["baeteil", "goratings", "manual", "whr", "federation"].contains(&source.as_str())) {
+        println!("잘못된 입력입니다. 다시 입력해주세요.");
+        return Ok(());
+    }
+
+    let mut registry = Registry::load();
+    registry.entry_mut(name.trim()).rating_sources = sources;
+    registry.save()?;
+    println!("레이팅 출처 우선순위를 저장했습니다.");
+    Ok(())
+}
+
+pub fn execute_registry_menu() {
+    loop {
+        println!("\n선택할 옵션:");
+        println!("1. 현재 시즌 기사 식별 현황");
+        println!("2. 동명이인 지정 (한국기원 검색)");
+        println!("3. 별칭 추가");
+        println!("4. 레이팅 출처 우선순위 지정");
+        println!("exit. 돌아가기");
+
+        let mut option = String::new();
+        io::stdin().read_line(&mut option).expect("입력을 읽는 데 실패했습니다.");
+
+        let result = match option.trim() {
+            "1" => season::load_current_season().map(|season| print_registry_status(season.teams())).map_err(|e| e.into()),
+            "2" => {
+                println!("기사 이름(시즌 파일의 korean_name)을 입력하세요: ");
+                let mut name = String::new();
+                io::stdin().read_line(&mut name).expect("입력을 읽는 데 실패했습니다.");
+                resolve_interactively(name.trim())
+            },
+            "3" => add_alias(),
+            "4" => set_rating_sources(),
+            "exit" => break,
+            _ => {
+                println!("잘못된 입력입니다. 다시 입력해주세요.");
+                Ok(())
+            }
+        };
+
+        if let Err(e) = result {
+            println!("오류가 발생했습니다: {}", e);
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use crate::config;
+use crate::models::{Player, Team};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const SEASON_DIR: &str = "seasons";
+
+#[derive(Deserialize)]
+struct SeasonFile {
+    season: String,
+    #[serde(default)]
+    league_name: String,
+    teams: Vec<TeamEntry>,
+    #[serde(default)]
+    postseason_teams: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TeamEntry {
+    name: String,
+    players: Vec<PlayerEntry>,
+}
+
+#[derive(Deserialize)]
+struct PlayerEntry {
+    korean_name: String,
+    english_name: String,
+    chinese_name: String,
+    birthday: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct Season {
+    season: String,
+    league_name: String,
+    teams: Vec<Team>,
+    postseason_teams: Vec<Team>,
+}
+
+impl Season {
+    pub fn season(&self) -> &String {
+        &self.season
+    }
+
+    pub fn league_name(&self) -> &String {
+        &self.league_name
+    }
+
+    pub fn teams(&self) -> &Vec<Team> {
+        &self.teams
+    }
+
+    pub fn postseason_teams(&self) -> &Vec<Team> {
+        &self.postseason_teams
+    }
+}
+
+pub fn load_season(path: &str) -> Result<Season, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("시즌 파일 {}을(를) 읽을 수 없습니다: {}", path, e))?;
+    let season_file: SeasonFile = serde_json::from_str(&text)
+        .map_err(|e| format!("시즌 파일 {}의 형식이 올바르지 않습니다: {}", path, e))?;
+
+    if season_file.season.trim().is_empty() {
+        return Err(format!("시즌 파일 {}: season 값이 비어 있습니다.", path));
+    }
+
+    let mut teams: Vec<Team> = Vec::new();
+    let mut team_names: HashSet<String> = HashSet::new();
+    let mut player_teams: HashMap<String, String> = HashMap::new();
+
+    for (team_index, team_entry) in season_file.teams.iter().enumerate() {
+        let team_label = format!("{}번째 팀 '{}'", team_index + 1, team_entry.name);
+        if team_entry.name.trim().is_empty() {
+            return Err(format!("시즌 파일 {}: {}번째 팀의 이름이 비어 있습니다.", path, team_index + 1));
+        }
+        if !team_names.insert(team_entry.name.clone()) {
+            return Err(format!("시즌 파일 {}: {}의 이름이 중복되었습니다.", path, team_label));
+        }
+        if team_entry.players.len() < 4 {
+            return Err(format!("시즌 파일 {}: {}의 기사가 {}명뿐입니다. 최소 4명이 필요합니다.", path, team_label, team_entry.players.len()));
+        }
+
+        let mut players: Vec<Player> = Vec::new();
+        for (player_index, player_entry) in team_entry.players.iter().enumerate() {
+            let player_label = format!("{}의 {}번째 기사 '{}'", team_label, player_index + 1, player_entry.korean_name);
+            if player_entry.korean_name.trim().is_empty() {
+                return Err(format!("시즌 파일 {}: {}의 {}번째 기사의 korean_name이 비어 있습니다.", path, team_label, player_index + 1));
+            }
+            if player_entry.english_name.trim().is_empty() {
+                return Err(format!("시즌 파일 {}: {}의 english_name이 비어 있습니다.", path, player_label));
+            }
+            if player_entry.chinese_name.trim().is_empty() {
+                return Err(format!("시즌 파일 {}: {}의 chinese_name이 비어 있습니다.", path, player_label));
+            }
+            if let Some(other_team) = player_teams.insert(player_entry.korean_name.clone(), team_entry.name.clone()) {
+                return Err(format!("시즌 파일 {}: {}이(가) '{}' 팀에도 등록되어 있습니다.", path, player_label, other_team));
+            }
+            let birthday = NaiveDate::parse_from_str(&player_entry.birthday, "%Y-%m-%d")
+                .map_err(|_| format!("시즌 파일 {}: {}의 생년월일 '{}'을(를) 해석할 수 없습니다. (YYYY-MM-DD)", path, player_label, player_entry.birthday))?;
+            if let Some(alias) = player_entry.aliases.iter().find(|alias| alias.trim().is_empty()) {
+                return Err(format!("시즌 파일 {}: {}의 별칭 '{}'이(가) 비어 있습니다.", path, player_label, alias));
+            }
+
+            let mut player = Player::new(player_entry.korean_name.clone(), player_entry.english_name.clone(), player_entry.chinese_name.clone(), birthday, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new());
+            player.set_aliases(player_entry.aliases.clone());
+            players.push(player);
+        }
+        teams.push(Team::new(team_entry.name.clone(), players));
+    }
+
+    if teams.len() < 2 {
+        return Err(format!("시즌 파일 {}: 팀이 {}개뿐입니다. 최소 2개 팀이 필요합니다.", path, teams.len()));
+    }
+
+    let mut postseason_teams: Vec<Team> = Vec::new();
+    for team_name in &season_file.postseason_teams {
+        match teams.iter().find(|team| team.team_name() == team_name) {
+            Some(team) if team.players().len() < 5 => {
+                return Err(format!("시즌 파일 {}: 포스트시즌 팀 '{}'의 기사가 {}명뿐입니다. 최소 5명이 필요합니다.", path, team_name, team.players().len()));
+            },
+            Some(team) => postseason_teams.push(team.clone()),
+            None => return Err(format!("시즌 파일 {}: 포스트시즌 팀 '{}'이(가) teams에 없습니다.", path, team_name)),
+        }
+    }
+
+    Ok(Season {
+        season: season_file.season,
+        league_name: season_file.league_name,
+        teams,
+        postseason_teams,
+    })
+}
+
+pub fn load_current_season() -> Result<Season, String> {
+    load_season(&config::get().season_file)
+}
+
+pub fn list_season_files() -> Vec<String> {
+    let mut season_files: Vec<String> = match fs::read_dir(SEASON_DIR) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .map(|path| path.to_string_lossy().replace('\\', "/"))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    season_files.sort();
+    season_files
+}
+
+pub fn select_season_file() {
+    let season_files = list_season_files();
+    println!("\n현재 시즌 파일: {}", config::get().season_file);
+    for (index, season_file) in season_files.iter().enumerate() {
+        println!("{}. {}", index + 1, season_file);
+    }
+    println!("시즌 파일 번호 또는 경로를 입력하세요 (변경하지 않으려면 엔터): ");
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
+    let input = input.trim();
+    if input.is_empty() {
+        return;
+    }
+
+    let selected_file = match input.parse::<usize>() {
+        Ok(num) if num > 0 && num <= season_files.len() => season_files[num - 1].clone(),
+        Ok(_) => {
+            println!("유효한 시즌 파일 번호를 입력해주세요.");
+            return;
+        },
+        Err(_) => input.to_string(),
+    };
+
+    if !Path::new(&selected_file).exists() {
+        println!("{} 파일을 찾을 수 없습니다.", selected_file);
+        return;
+    }
+
+    match load_season(&selected_file) {
+        Ok(season) => {
+            if let Err(e) = config::update(|config| config.season_file = selected_file.clone()) {
+                println!("설정을 저장하는 동안 오류가 발생했습니다: {}", e);
+            }
+            println!("{} {} 시즌({}개 팀, 포스트시즌 {}개 팀)을 불러왔습니다.", season.league_name(), season.season(), season.teams().len(), season.postseason_teams().len());
+        },
+        Err(e) => println!("{}", e),
+    }
+}
@@ -0,0 +1,68 @@
+use crate::models::DataQuality;
+use crate::records;
+use chrono::{Duration, NaiveDate};
+use std::f64::consts::{LN_10, PI};
+
+// Glicko의 초기 편차이자 최대 편차입니다.
+pub const MAX_DEVIATION: f64 = 350.0;
+// 한 달 동안 대국이 없을 때 늘어나는 편차입니다. 50에서 350까지 약 100개월이 걸립니다.
+const MONTHLY_INFLATION: f64 = 34.6;
+// 오래된 레이팅이나 대체값을 쓸 때 더하는 편차입니다.
+const STALE_DEVIATION: f64 = 60.0;
+// 레이팅 출처 순서의 첫 출처에 없어 뒤의 출처(예: 배태일 랭킹 대신 goratings)의 레이팅을 쓸 때 더하는 편차입니다.
+// 척도를 환산하며 생기는 오차를 반영합니다.
+const FALLBACK_SOURCE_DEVIATION: f64 = 50.0;
+// 90% 신뢰구간의 z값입니다.
+const INTERVAL_Z: f64 = 1.645;
+
+const Q: f64 = LN_10 / 400.0;
+
+// 편차가 클수록 레이팅 차이가 승률에 미치는 영향을 줄이는 Glicko의 g 함수입니다.
+pub fn attenuation(deviation: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * Q * Q * deviation * deviation / (PI * PI)).sqrt()
+}
+
+pub fn combined_deviation(deviation1: f64, deviation2: f64) -> f64 {
+    (deviation1 * deviation1 + deviation2 * deviation2).sqrt()
+}
+
+// 기준일까지 최근 1년 대국 수로 편차를 줄이고, 마지막 대국 이후 지난 기간만큼 다시 늘립니다.
+// 두 기사의 편차는 combined_deviation으로 합치므로 대체 출처의 편차도 승률에 반영됩니다.
+pub fn rating_deviation(name: &str, rating_quality: &DataQuality, from_fallback_source: bool, as_of: NaiveDate) -> f64 {
+    if let DataQuality::Missing(_) = rating_quality {
+        return MAX_DEVIATION;
+    }
+
+    let games = records::player_games_between(name, as_of - Duration::try_days(365).unwrap(), as_of).unwrap_or_default();
+    // 승률이 50%에 가까운 대국 한 판이 주는 정보량(q^2 * 1/4)으로 근사합니다.
+    let information = 1.0 / (MAX_DEVIATION * MAX_DEVIATION) + games.len() as f64 * Q * Q * 0.25;
+    let mut variance = 1.0 / information;
+
+    let months_idle = games
+        .iter()
+        .map(|game| game.date())
+        .max()
+        .map_or(12.0, |last_game| (as_of - last_game).num_days() as f64 / 30.0);
+    variance += MONTHLY_INFLATION * MONTHLY_INFLATION * months_idle;
+
+    if !rating_quality.is_fresh() {
+        variance += STALE_DEVIATION * STALE_DEVIATION;
+    }
+    if from_fallback_source {
+        variance += FALLBACK_SOURCE_DEVIATION * FALLBACK_SOURCE_DEVIATION;
+    }
+
+    variance.sqrt().min(MAX_DEVIATION)
+}
+
+// 편차를 반영한 승률(%)을 레이팅 차이로 되돌린 뒤, 그 차이의 90% 구간에 해당하는 승률(%)을 구합니다.
+pub fn credible_interval(win_probability: f64, deviation: f64) -> (f64, f64) {
+    let probability = (win_probability / 100.0).clamp(1e-6, 1.0 - 1e-6);
+    let elo_diff = 400.0 * (probability / (1.0 - probability)).log10() / attenuation(deviation);
+    let to_probability = |diff: f64| 100.0 / (1.0 + 10.0_f64.powf(-diff / 400.0));
+    (to_probability(elo_diff - INTERVAL_Z * deviation), to_probability(elo_diff + INTERVAL_Z * deviation))
+}
+
+pub fn format_interval(interval: (f64, f64)) -> String {
+    format!("{:.1}~{:.1}%", interval.0, interval.1)
+}
@@ -1,4 +1,21 @@
-use crate::models::{Lineup, MatchResult, Player, PlayerRelativity, Team, TeamRelativity, TiebreakerRelativity, WPAResult, PostWPAResult, PostPlayerRelativity, PostMatchResult, PostLineup, PostRAXResult};
+use crate::cache;
+use crate::config;
+use crate::constraints;
+use crate::conversion;
+use crate::error::DataError;
+use crate::events::{self, TimeControl};
+use crate::fixtures;
+use crate::form;
+use crate::model_params::{self, ModelParams};
+use crate::pipeline;
+use crate::projection;
+use crate::rating_archive;
+use crate::rating_source::{self, BaeteilSource, FederationSource, GoratingsSource, ManualSource, RatingChain, WholeHistorySource};
+use crate::records;
+use crate::registry::{self, Registry};
+use crate::uncertainty;
+use crate::whr;
+use crate::models::{BoardKind, BOARD_KINDS, DataQuality, Stone, Lineup, MatchResult, NowcastGame, Player, PlayerRelativity, Team, TeamRelativity, TiebreakerRelativity, WPAResult, PostWPAResult, PostPlayerRelativity, PostMatchResult, PostLineup, PostRAXResult, RatingNowcast};
 use crossterm::{
     execute,
     terminal::{Clear, ClearType},
@@ -9,8 +26,6 @@ use chrono::{Datelike, Timelike, NaiveDate};
 use fantoccini::{Client, Locator};
 use fantoccini::wd::TimeoutConfiguration;
 use itertools::Itertools;
-use regex::Regex;
-use reqwest;
 use scraper::{Html, Selector};
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
@@ -28,6 +43,84 @@ pub fn calculate_win_probability(player1_elo: f64, player2_elo: f64) -> f64 {
     probability
 }
 
+// 두 기사의 레이팅 편차를 합친 만큼 레이팅 차이를 줄여, 레이팅의 불확실성에 대해 평균한 승률을 구합니다.
+pub fn calculate_win_probability_with_deviation(player1_elo: f64, player2_elo: f64, deviation: f64) -> f64 {
+    let elo_diff = (player2_elo - player1_elo) * uncertainty::attenuation(deviation);
+    1.0 / (1.0 + 10.0_f64.powf(elo_diff / 400.0))
+}
+
+// 판 종류에 관계없이 더하는 레이팅입니다. 전망과 KB리그 성적은 설정에서 켠 경우에만 값이 있습니다.
+pub fn base_rating(player: &Player) -> f64 {
+    player.elo_rating() + player.condition_weight() + player.projection_weight() + player.team_league_weight()
+}
+
+// 판 종류에 따른 가중치와, 흑백을 알면 그 판의 배율을 곱한 흑백 가중치입니다.
+pub fn board_weight(player: &Player, kind: BoardKind, stone: Option<Stone>, params: &ModelParams) -> f64 {
+    let (kind_weight, color_multiplier) = match kind {
+        BoardKind::Rapid => (player.rapid_weight(), params.rapid_color_multiplier),
+        BoardKind::Blitz => (player.blitz_weight(), 1.0),
+        BoardKind::Bullet => (player.bullet_weight(), params.bullet_color_multiplier),
+    };
+    let color_weight = match stone {
+        Some(Stone::Black) => player.black_weight() * color_multiplier,
+        Some(Stone::White) => player.white_weight() * color_multiplier,
+        None => 0.0,
+    };
+    kind_weight + color_weight
+}
+
+pub fn relative_weight(player: &Player, opponent: &Player) -> f64 {
+    *player.relative_weight().get(opponent.korean_name().as_str()).unwrap_or(&0.0)
+}
+
+// 상대 기사와 한 판을 둘 때의 레이팅입니다.
+pub fn effective_rating(player: &Player, opponent: &Player, kind: BoardKind, stone: Option<Stone>, params: &ModelParams) -> f64 {
+    base_rating(player) + board_weight(player, kind, stone, params) + relative_weight(player, opponent)
+}
+
+// player1이 stone을 들었을 때 한 판의 승률(0~1)입니다. 상대전적은 반영하지 않습니다.
+pub fn board_probability(player1: &Player, player2: &Player, kind: BoardKind, stone: Option<Stone>, params: &ModelParams) -> f64 {
+    calculate_win_probability_with_deviation(
+        effective_rating(player1, player2, kind, stone, params),
+        effective_rating(player2, player1, kind, stone.map(|stone| stone.opposite()), params),
+        uncertainty::combined_deviation(player1.rating_deviation(), player2.rating_deviation()),
+    )
+}
+
+fn shrink_head_to_head(win_probability: f64, player1_wins: u32, player2_wins: u32) -> f64 {
+    let config = config::get();
+    if !config.head_to_head_enabled {
+        return win_probability;
+    }
+    shrink_head_to_head_with_prior(win_probability, player1_wins, player2_wins, config.head_to_head_prior_games)
+}
+
+// 상대전적의 승률을 Elo 기대 승률 쪽으로 당겨 반영합니다. 전적이 prior_games판이면 절반만 반영합니다.
+pub fn shrink_head_to_head_with_prior(win_probability: f64, player1_wins: u32, player2_wins: u32, prior_games: f64) -> f64 {
+    let games = (player1_wins + player2_wins) as f64;
+    if games == 0.0 {
+        return win_probability;
+    }
+    let observed_probability = player1_wins as f64 / games;
+    interpolate(observed_probability, prior_games / (games + prior_games), win_probability)
+}
+
+// 상대전적 반영으로 각 판 승률이 평균 몇 %p 바뀌었는지 구합니다.
+fn head_to_head_adjustment(win_probabilities: &[f64], player1_wins: u32, player2_wins: u32) -> f64 {
+    let total: f64 = win_probabilities.iter().map(|&win_probability| shrink_head_to_head(win_probability, player1_wins, player2_wins) - win_probability).sum();
+    total / win_probabilities.len() as f64 * 100.0
+}
+
+fn print_head_to_head_report(pairings: Vec<(&Player, &Player, u32, u32, f64)>) {
+    if !config::get().head_to_head_enabled {
+        return;
+    }
+    println!("\n상대전적 반영:");
+    for (player1, player2, player1_wins, player2_wins, adjustment) in pairings.into_iter().filter(|pairing| pairing.2 + pairing.3 > 0) {
+        println!("{} vs {} ({}-{}): 평균 {:+.2}%p", player1.korean_name(), player2.korean_name(), player1_wins, player2_wins, adjustment);
+    }
+}
+
 fn interpolate(relative_probability: f64, standard_error: f64, base_probability: f64) -> f64 {
     if standard_error <= 0.0 {
         return relative_probability;
@@ -38,16 +131,19 @@ fn interpolate(relative_probability: f64, standard_error: f64, base_probability:
     }
 }
 
-pub fn fetch_player_ratings_on_baeteil(year: &str, month: &str) -> Result<(HashMap<String, f64>, String), Box<dyn std::error::Error>> {
+pub fn fetch_player_ratings_on_baeteil(year: &str, month: &str) -> Result<(HashMap<String, f64>, String), DataError> {
+    let body = cache::post_form_text(&fixtures::baduk_url("/record/rankingPlayer_in.asp"), &[("pageNo", "1"), ("keyColumn", year), ("keyWord", month), ("etcKey1", "1")])?;
+    parse_player_ratings_on_baeteil(&body)
+}
+
+pub fn parse_player_ratings_on_baeteil(body: &str) -> Result<(HashMap<String, f64>, String), DataError> {
     let mut ratings = HashMap::new();
-    let client = reqwest::blocking::Client::new();
-    let res = client.post("http://baduk.or.kr/record/rankingPlayer_in.asp")
-        .form(&[("pageNo", "1"), ("keyColumn", year), ("keyWord", month), ("etcKey1", "1")])
-        .send()?;
-    let body = res.text()?;
-    let document = Html::parse_document(&body);
+    let document = Html::parse_document(body);
     let ranking_month_selector = Selector::parse("button.on").unwrap();
-    let ranking_month_element = document.select(&ranking_month_selector).next().unwrap();
+    let ranking_month_element = document.select(&ranking_month_selector).next().ok_or_else(|| DataError::Parse {
+        site: "배태일 랭킹".to_string(),
+        message: "랭킹 월 표시를 찾을 수 없습니다.".to_string(),
+    })?;
     let ranking_month_text = ranking_month_element.text().collect::<String>();
     let ranking_month = ranking_month_text.chars().filter(|c| c.is_digit(10)).collect::<String>();
 
@@ -70,10 +166,9 @@ pub fn fetch_player_ratings_on_baeteil(year: &str, month: &str) -> Result<(HashM
     Ok((ratings, ranking_month))
 }
 
-pub fn fetch_player_ratings_on_goratings() -> Result<HashMap<String, f64>, Box<dyn std::error::Error>> {
-    let mut ratings = HashMap::new();
-    let body = reqwest::blocking::get("https://www.goratings.org/en/")?.text()?;
-    let document = Html::parse_document(&body);
+pub fn parse_player_ratings_on_goratings(body: &str) -> Vec<(Option<u32>, String, f64)> {
+    let mut ratings = Vec::new();
+    let document = Html::parse_document(body);
     let player_selector = Selector::parse("tr").unwrap();
     let name_selector = Selector::parse("td:nth-child(2) a").unwrap();
     let rating_selector = Selector::parse("td:nth-child(5)").unwrap();
@@ -82,94 +177,34 @@ pub fn fetch_player_ratings_on_goratings() -> Result<HashMap<String, f64>, Box<d
         if let Some(name_element) = player.select(&name_selector).next() {
             if let Some(rating_element) = player.select(&rating_selector).next() {
                 let name = name_element.inner_html();
+                // 기사 페이지 링크(players/1313.html)의 번호가 goratings id입니다.
+                let id = name_element.value().attr("href")
+                    .and_then(|href| href.rsplit('/').next())
+                    .and_then(|file| file.trim_end_matches(".html").parse::<u32>().ok());
                 let rating_str = rating_element.inner_html();
                 if let Ok(rating) = rating_str.parse::<f64>() {
-                    ratings.insert(name, rating);
+                    ratings.push((id, name, rating));
                 }
             }
         }
     }
 
-    Ok(ratings)
+    ratings
 }
 
-pub fn fetch_head_to_head_record(gisa1: &str, gisa2: &str) -> Result<HashMap<String, u32>, Box<dyn Error>> {
-    let syne_day = chrono::Utc::now().date_naive() - chrono::Duration::try_days(1095).unwrap();
+pub fn fetch_head_to_head_record(gisa1: &str, gisa2: &str, as_of: NaiveDate) -> Result<HashMap<String, u32>, Box<dyn Error>> {
+    let syne_day = as_of - chrono::Duration::try_days(1095).unwrap();
     let mut records = HashMap::new();
 
-    let re = Regex::new(r"choice\('[^']+', ?'(\d+)', ?'\d+'\)").unwrap();
-    let choice_selector = Selector::parse("li[onclick]").unwrap();
-
-    let url1 = format!("http://baduk.or.kr/common/search_pro.asp?keyword={}&R_name=player1&R_code=player1_code", gisa1);
-    let body1 = reqwest::blocking::get(&url1)?.text()?;
-    let document1 = Html::parse_document(&body1);
-    let script_texts1 = document1.select(&choice_selector).map(|script| script.value().attr("onclick").unwrap_or_default().to_string()).collect::<Vec<_>>();
-    let script_text1 = script_texts1.first().unwrap(); // 첫번째만 사용하도록 변경
-    let gisa1_code = re.captures(script_text1).and_then(|cap| cap.get(1).map(|match_| match_.as_str().parse::<i32>().ok())).flatten().unwrap_or_default();
-
-    let url2 = format!("http://baduk.or.kr/common/search_pro.asp?keyword={}&R_name=player2&R_code=player2_code", gisa2);
-    let body2 = reqwest::blocking::get(&url2)?.text()?;
-    let document2 = Html::parse_document(&body2);
-    let script_texts2 = document2.select(&choice_selector).map(|script| script.value().attr("onclick").unwrap_or_default().to_string()).collect::<Vec<_>>();
-    let script_text2 = script_texts2.first().unwrap();
-    let gisa2_code = re.captures(script_text2).and_then(|cap| cap.get(1).map(|match_| match_.as_str().parse::<i32>().ok())).flatten().unwrap_or_default();
-
     let mut gisa1_wins = 0;
     let mut gisa2_wins = 0;
 
-    let mut page_no = 1;
-    loop {
-        let url3 = format!("http://baduk.or.kr/record/diary_in.asp?foreignKey=&pageNo={}&keyWord={}&etcKey={}&etc2=1", page_no, gisa1_code, gisa2_code);
-        let body3 = reqwest::blocking::get(&url3)?.text()?;
-        let document3 = Html::parse_document(&body3);
-
-        let match_selector = Selector::parse("tbody>tr").unwrap();
-        let date_selector = Selector::parse("td.no").unwrap();
-
-        let matches: Vec<_> = document3.select(&match_selector).collect();
-        if matches.is_empty() {
-            break;
-        }
-        let mut break_loop = false;
-
-        for selected_match in matches.iter() {
-            if let Some(date_element) = selected_match.select(&date_selector).next() {
-                let date_text = date_element.text().collect::<String>();
-                let match_date = NaiveDate::parse_from_str(&date_text, "%Y-%m-%d").ok();
-
-                if let Some(match_date) = match_date {
-                    if match_date < syne_day {
-                        break_loop = true;
-                        break;
-                    }
-
-                    let player_names = selected_match.select(&Selector::parse("td").unwrap())
-                        .enumerate()
-                        .filter_map(|(index, element)| {
-                            if index == 2 || index == 3 {
-                                Some(element.text().collect::<String>())
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>();
-
-                    if !player_names.is_empty() {
-                        if player_names[0] == gisa1 {
-                            gisa1_wins += 1;
-                        } else {
-                            gisa2_wins += 1;
-                        }
-                    }
-                }
-            }
-        }
-
-        if break_loop {
-            break;
+    for game in records::head_to_head_games_between(gisa1, gisa2, syne_day, as_of)? {
+        if game.winner_name() == gisa1 {
+            gisa1_wins += 1;
+        } else {
+            gisa2_wins += 1;
         }
-
-        page_no += 1;
     }
 
     records.insert(gisa1.to_string(), gisa1_wins);
@@ -179,258 +214,361 @@ pub fn fetch_head_to_head_record(gisa1: &str, gisa2: &str) -> Result<HashMap<Str
 }
 
 pub fn update_team_elo_ratings(selected_teams: &mut Vec<Team>) -> Result<(), Box<dyn Error>> {
-    let (player_ratings_on_baeteil, ranking_month) = fetch_player_ratings_on_baeteil(&chrono::Utc::now().year().to_string(), &chrono::Utc::now().month().to_string())?;
-    let player_ratings_on_goratings = fetch_player_ratings_on_goratings()?;
+    let sync_failures = pipeline::prefetch_team_data(selected_teams)?;
+    let (player_ratings_on_baeteil, baeteil_date, baeteil_quality) = current_baeteil_list();
+    let (player_ratings_on_goratings, goratings_date, goratings_quality) = current_goratings_list();
+    let today = chrono::Utc::now().date_naive();
+
+    let mut registry = Registry::load();
+    let all_players: Vec<Player> = selected_teams.iter().flat_map(|team| team.players().clone()).collect();
+    if registry.learn_goratings_ids(&rating_archive::goratings_players()?, &all_players) && registry.is_writable() {
+        registry.save()?;
+    }
+    registry::apply_aliases(selected_teams);
+
+    let rating_chain = RatingChain::new(vec![
+        Box::new(BaeteilSource::new(player_ratings_on_baeteil.clone(), baeteil_date, baeteil_quality)),
+        Box::new(GoratingsSource::new(player_ratings_on_goratings, goratings_date, goratings_quality)),
+        Box::new(ManualSource::load().unwrap_or_else(|e| {
+            println!("경고: 수동 레이팅 파일을 읽지 못했습니다: {}", e);
+            ManualSource::default()
+        })),
+        Box::new(whole_history_source(&player_ratings_on_baeteil, today)),
+        Box::new(federation_source(today)),
+    ]);
 
     let teams = selected_teams.split_at_mut(1);
     let (team1, team2) = (teams.0.get_mut(0).unwrap(), teams.1.get_mut(0).unwrap());
 
     for player in team1.players_mut().iter_mut() {
-        if let Some(&rating) = player_ratings_on_baeteil.get(player.korean_name()) {
-            match get_recent_record(player.korean_name(), baeteil_to_goratings(rating), &player_ratings_on_baeteil, ranking_month.clone()) {
-                Ok(current_rating) => {
-                    player.set_elo_rating(current_rating);
-                },
-                Err(_) => {
-                    player.set_elo_rating(baeteil_to_goratings(rating));
-                }
-            }
-
-            if let Ok((white_weight, black_weight, relative_weight_list, live_weight, blitz_weight, kb_weight)) = get_relative_and_color_weight(player.korean_name(), player.english_name(), team2) {
-                player.set_white_weight(white_weight);
-                player.set_black_weight(black_weight);
-                player.set_relative_weight(relative_weight_list);
+        rate_player(player, team2, &rating_chain, &player_ratings_on_baeteil, &sync_failures, true, today);
+    }
 
-                player.set_rapid_weight((live_weight + blitz_weight) / 2.0);
-                player.set_blitz_weight(blitz_weight + ((blitz_weight - ((live_weight + blitz_weight) / 2.0)) / 2.0));
-                player.set_bullet_weight(blitz_weight + (blitz_weight - ((live_weight + blitz_weight) / 2.0)));
+    for player in team2.players_mut().iter_mut() {
+        rate_player(player, team1, &rating_chain, &player_ratings_on_baeteil, &sync_failures, false, today);
+    }
 
-                println!("{}: {:.2}, {:.2}, {:.2}", player.korean_name(), player.elo_rating(), kb_weight, goratings_to_baeteil(player.elo_rating() + kb_weight));
-            }
-        } else if let Some(&rating) = player_ratings_on_goratings.get(player.english_name()) {
-            match get_recent_record(player.korean_name(), rating, &player_ratings_on_baeteil, ranking_month.clone()) {
-                Ok(current_rating) => {
-                    player.set_elo_rating(current_rating);
-                },
-                Err(_) => {
-                    player.set_elo_rating(rating);
-                }
-            }
+    print_nowcast_report(selected_teams);
+    print_data_quality_report(selected_teams);
+    Ok(())
+}
 
-            if let Ok((white_weight, black_weight, relative_weight_list, live_weight, blitz_weight, kb_weight)) = get_relative_and_color_weight(player.korean_name(), player.english_name(), team2) {
-                player.set_white_weight(white_weight);
-                player.set_black_weight(black_weight);
-                player.set_relative_weight(relative_weight_list);
+// 보관된 랭킹과 기준일까지의 대국만으로 레이팅과 가중치를 계산합니다. 백테스트에서 경기 전날의 상태를 다시 만들 때 사용하며,
+// 같은 기사를 여러 번 계산하므로 데이터는 호출하는 쪽에서 미리 받아 둡니다.
+pub fn update_team_elo_ratings_as_of(selected_teams: &mut Vec<Team>, as_of: NaiveDate, sync_failures: &HashMap<String, DataError>) -> Result<(), Box<dyn Error>> {
+    let (player_ratings_on_baeteil, ranking_month) = rating_archive::baeteil_list(as_of.year(), as_of.month())?;
+    let player_ratings_on_goratings = rating_archive::goratings_list_as_of(as_of)?;
+    registry::apply_aliases(selected_teams);
+
+    // 수동 입력 레이팅은 현재 시점의 값이므로 사용하지 않습니다.
+    let rating_chain = RatingChain::new(vec![
+        Box::new(BaeteilSource::new(player_ratings_on_baeteil.clone(), ranking_month_start(&ranking_month, as_of), DataQuality::Fresh)),
+        Box::new(GoratingsSource::new(player_ratings_on_goratings, as_of, DataQuality::Fresh)),
+        Box::new(whole_history_source(&player_ratings_on_baeteil, as_of)),
+        Box::new(federation_source(as_of)),
+    ]);
 
-                player.set_rapid_weight((live_weight + blitz_weight) / 2.0);
-                player.set_blitz_weight(blitz_weight);
-                player.set_bullet_weight(blitz_weight + ((blitz_weight - ((live_weight + blitz_weight) / 2.0)) / 2.0));
+    let teams = selected_teams.split_at_mut(1);
+    let (team1, team2) = (teams.0.get_mut(0).unwrap(), teams.1.get_mut(0).unwrap());
 
-                println!("{}: {:.2}, {:.2}, {:.2}", player.korean_name(), player.elo_rating(), kb_weight, goratings_to_baeteil(player.elo_rating() + kb_weight));
-            }
-        }
+    for player in team1.players_mut().iter_mut() {
+        rate_player(player, team2, &rating_chain, &player_ratings_on_baeteil, sync_failures, true, as_of);
     }
 
     for player in team2.players_mut().iter_mut() {
-        if let Some(&rating) = player_ratings_on_baeteil.get(player.korean_name()) {
-            match get_recent_record(player.korean_name(), baeteil_to_goratings(rating), &player_ratings_on_baeteil, ranking_month.clone()) {
-                Ok(current_rating) => {
-                    player.set_elo_rating(current_rating);
-                },
-                Err(_) => {
-                    player.set_elo_rating(baeteil_to_goratings(rating));
-                }
-            }
+        rate_player(player, team1, &rating_chain, &player_ratings_on_baeteil, sync_failures, false, as_of);
+    }
 
-            if let Ok((white_weight, black_weight, relative_weight_list, live_weight, blitz_weight, kb_weight)) = get_relative_and_color_weight(player.korean_name(), player.english_name(), team1) {
-                player.set_white_weight(white_weight);
-                player.set_black_weight(black_weight);
-                player.set_relative_weight(relative_weight_list);
+    Ok(())
+}
 
-                player.set_rapid_weight((live_weight + blitz_weight) / 2.0);
-                player.set_blitz_weight(blitz_weight);
-                player.set_bullet_weight(blitz_weight + ((blitz_weight - ((live_weight + blitz_weight) / 2.0)) / 2.0));
+fn whole_history_source(player_ratings_on_baeteil: &HashMap<String, f64>, as_of: NaiveDate) -> WholeHistorySource {
+    if rating_source::is_in_use("whr") {
+        WholeHistorySource::new(whr::whole_history_ratings_cached(as_of, player_ratings_on_baeteil), as_of)
+    } else {
+        WholeHistorySource::new(HashMap::new(), as_of)
+    }
+}
+
+fn federation_source(as_of: NaiveDate) -> FederationSource {
+    FederationSource::load(as_of).unwrap_or_else(|e| {
+        println!("경고: 다른 기원 레이팅 목록을 읽지 못했습니다: {}", e);
+        FederationSource::default()
+    })
+}
 
-                println!("{}: {:.2}, {:.2}, {:.2}", player.korean_name(), player.elo_rating(), kb_weight, goratings_to_baeteil(player.elo_rating() + kb_weight));
+fn rate_player(player: &mut Player, other_team: &Team, rating_chain: &RatingChain, player_ratings_on_baeteil: &HashMap<String, f64>, sync_failures: &HashMap<String, DataError>, first_team: bool, as_of: NaiveDate) {
+    let sourced = match rating_chain.lookup(player) {
+        Some(sourced) => sourced,
+        None => {
+            if rating_chain.is_empty() {
+                player.set_rating_quality(DataQuality::Missing("레이팅 목록을 모두 받지 못했습니다.".to_string()));
+            } else {
+                println!("경고: {} 기사를 레이팅 출처({})에서 모두 찾지 못했습니다. '기사 식별 정보 관리'에서 별칭이나 레이팅 출처를 지정해주세요.", player.korean_name(), rating_chain.order_for(player).join(", "));
+                player.set_rating_quality(DataQuality::Missing("레이팅 출처에서 모두 찾지 못했습니다.".to_string()));
             }
-        } else if let Some(&rating) = player_ratings_on_goratings.get(player.english_name()) {
-            match get_recent_record(player.korean_name(), rating, &player_ratings_on_baeteil, ranking_month.clone()) {
-                Ok(current_rating) => {
-                    player.set_elo_rating(current_rating);
-                },
-                Err(_) => {
-                    player.set_elo_rating(rating);
-                }
+            player.set_weight_quality(DataQuality::Missing("레이팅이 없어 계산하지 않았습니다.".to_string()));
+            return;
+        }
+    };
+    player.set_rating_source(rating_source::source_label(sourced.source).to_string());
+    player.set_rating_date(Some(sourced.date));
+
+    let nowcast_since = match nowcast_rating(player.korean_name(), sourced.rating, player_ratings_on_baeteil, sourced.date, as_of) {
+        Ok(nowcast) => {
+            player.set_elo_rating(nowcast.rating());
+            player.set_nowcast(nowcast);
+            player.set_rating_quality(if sourced.quality.is_fresh() { sync_quality(player.korean_name(), sync_failures) } else { sourced.quality.clone() });
+            Some(sourced.date)
+        },
+        Err(e) => {
+            player.set_elo_rating(sourced.rating);
+            player.set_nowcast(RatingNowcast::new(sourced.rating));
+            player.set_rating_quality(DataQuality::Fallback(format!("최근 대국을 반영하지 못해 랭킹 레이팅을 그대로 사용합니다 ({})", e)));
+            None
+        }
+    };
+    let from_fallback_source = rating_chain.order_for(player).first().is_some_and(|first| first != sourced.source);
+    player.set_rating_deviation(uncertainty::rating_deviation(player.korean_name(), player.rating_quality(), from_fallback_source, as_of));
+    let form = form::recent_form(player.korean_name(), player.elo_rating(), player_ratings_on_baeteil, nowcast_since, as_of);
+    player.set_condition_weight(form.weight());
+    player.set_form(form);
+    let projection = projection::career_projection(player, as_of);
+    player.set_projection_weight(if config::get().projection_enabled { projection.change() } else { 0.0 });
+    player.set_projection(projection);
+
+    match get_relative_and_color_weight(player.korean_name(), player.english_name(), other_team, as_of) {
+        Ok((white_weight, black_weight, relative_weight_list, live_weight, blitz_weight, kb_weight)) => {
+            player.set_white_weight(white_weight);
+            player.set_black_weight(black_weight);
+            player.set_relative_weight(relative_weight_list);
+
+            player.set_rapid_weight((live_weight + blitz_weight) / 2.0);
+            if first_team && sourced.source == "baeteil" {
+                player.set_blitz_weight(blitz_weight + ((blitz_weight - ((live_weight + blitz_weight) / 2.0)) / 2.0));
+                player.set_bullet_weight(blitz_weight + (blitz_weight - ((live_weight + blitz_weight) / 2.0)));
+            } else {
+                player.set_blitz_weight(blitz_weight);
+                player.set_bullet_weight(blitz_weight + ((blitz_weight - ((live_weight + blitz_weight) / 2.0)) / 2.0));
             }
 
-            if let Ok((white_weight, black_weight, relative_weight_list, live_weight, blitz_weight, kb_weight)) = get_relative_and_color_weight(player.korean_name(), player.english_name(), team1) {
-                player.set_white_weight(white_weight);
-                player.set_black_weight(black_weight);
-                player.set_relative_weight(relative_weight_list);
+            player.set_team_league_performance(kb_weight);
+            player.set_team_league_weight(if config::get().team_league_weight_enabled { kb_weight } else { 0.0 });
 
-                player.set_rapid_weight((live_weight + blitz_weight) / 2.0);
-                player.set_blitz_weight(blitz_weight);
-                player.set_bullet_weight(blitz_weight + ((blitz_weight - ((live_weight + blitz_weight) / 2.0)) / 2.0));
+            player.set_weight_quality(sync_quality(player.korean_name(), sync_failures));
+        },
+        Err(e) => {
+            player.set_weight_quality(DataQuality::Missing(format!("가중치를 계산하지 못했습니다 ({})", e)));
+        }
+    }
+}
+
+// 랭킹 월만 표시되므로 기준일의 월보다 뒤의 월이면 전년도 랭킹으로 봅니다.
+fn ranking_month_start(ranking_month: &str, reference: NaiveDate) -> NaiveDate {
+    let month = ranking_month.parse::<u32>().unwrap_or(reference.month());
+    let year = if month > reference.month() { reference.year() - 1 } else { reference.year() };
+    NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(reference)
+}
 
-                println!("{}: {:.2}, {:.2}, {:.2}", player.korean_name(), player.elo_rating(), kb_weight, goratings_to_baeteil(player.elo_rating() + kb_weight));
+// 이번 달 랭킹을 받지 못하면 보관된 가장 최근 랭킹으로 대신합니다.
+fn current_baeteil_list() -> (HashMap<String, f64>, NaiveDate, DataQuality) {
+    let today = chrono::Utc::now().date_naive();
+    match rating_archive::baeteil_list(today.year(), today.month()) {
+        Ok((ratings, ranking_month)) => (ratings, ranking_month_start(&ranking_month, today), DataQuality::Fresh),
+        Err(e) => match rating_archive::latest_baeteil_list() {
+            Ok(Some((year, month, ratings, ranking_month))) => {
+                println!("경고: 이번 달 배태일 랭킹을 받지 못해 {}년 {}월 랭킹을 사용합니다: {}", year, month, e);
+                (ratings, ranking_month_start(&ranking_month, today), DataQuality::Stale(format!("{}년 {}월 배태일 랭킹 사용", year, month)))
+            },
+            _ => {
+                println!("경고: 배태일 랭킹을 받지 못했습니다: {}", e);
+                (HashMap::new(), today, DataQuality::Missing("배태일 랭킹 없음".to_string()))
+            }
+        },
+    }
+}
+
+fn current_goratings_list() -> (HashMap<String, f64>, NaiveDate, DataQuality) {
+    let today = chrono::Utc::now().date_naive();
+    match rating_archive::snapshot_goratings() {
+        Ok(ratings) => (ratings, today, DataQuality::Fresh),
+        Err(e) => match rating_archive::latest_goratings_snapshot() {
+            Ok(Some((snapshot_date, ratings))) => {
+                println!("경고: goratings를 받지 못해 {} 스냅샷을 사용합니다: {}", snapshot_date, e);
+                let date = NaiveDate::parse_from_str(&snapshot_date, "%Y-%m-%d").unwrap_or(today);
+                (ratings, date, DataQuality::Stale(format!("{} goratings 스냅샷 사용", snapshot_date)))
+            },
+            _ => {
+                println!("경고: goratings를 받지 못했습니다: {}", e);
+                (HashMap::new(), today, DataQuality::Missing("goratings 없음".to_string()))
             }
+        },
+    }
+}
+
+// 기보 동기화에 실패한 기사는 이전에 저장해 둔 기록으로 계산하므로 오래된 데이터로 표시합니다.
+fn sync_quality(name: &str, sync_failures: &HashMap<String, DataError>) -> DataQuality {
+    let error = match sync_failures.get(name) {
+        Some(error) => error,
+        None => return DataQuality::Fresh,
+    };
+    match records::last_synced_date(name) {
+        Ok(Some(date)) => DataQuality::Stale(format!("{}, {}에 동기화한 기보 사용", error, date)),
+        _ => DataQuality::Stale(error.to_string()),
+    }
+}
+
+// 기사마다 기준 레이팅과 그 이후 반영한 대국을 보여줍니다.
+fn print_nowcast_report(teams: &[Team]) {
+    println!("\n최근 대국 반영:");
+    for player in teams.iter().flat_map(|team| team.players()) {
+        println!("{} ({}): {} (배태일 환산 {:.0})", player.korean_name(), player.describe_rating_source(), player.nowcast().describe(), goratings_to_baeteil(player.elo_rating()));
+        for game in player.nowcast().games() {
+            println!("    {}", game.describe());
         }
     }
+}
 
-    Ok(())
+fn print_data_quality_report(teams: &[Team]) {
+    let players: Vec<&Player> = teams
+        .iter()
+        .flat_map(|team| team.players())
+        .filter(|player| !player.rating_quality().is_fresh() || !player.weight_quality().is_fresh())
+        .collect();
+    if players.is_empty() {
+        return;
+    }
+    println!("\n누락되었거나 오래된 입력 데이터:");
+    for player in players {
+        println!("{}{}", player.korean_name(), player.data_quality_note());
+    }
 }
 
-fn baeteil_to_goratings(x: f64) -> f64 {
-    if x < 9250.0 {
-        x - 6050.0
+fn mentions_player(text: &str, player: &Player) -> bool {
+    text.contains(player.chinese_name().as_str()) || player.aliases().iter().any(|alias| text.contains(alias.as_str()))
+}
+
+// 레이팅 환산 곡선을 맞춰 두었으면 그 곡선을, 아니면 모델 상수의 환산식을 사용합니다.
+pub fn baeteil_to_goratings(x: f64) -> f64 {
+    if let Some(y) = conversion::to_goratings("baeteil", x) {
+        return y;
+    }
+    let params = model_params::get();
+    if x < params.baeteil_curve_start {
+        x - params.baeteil_offset
     } else {
         // Parameters for the logarithmic part
-        let a = 740.332659;
-        let b = 0.0946919155;
-        let c = 8456.81141;
+        let a = params.baeteil_curve_a;
+        let b = params.baeteil_curve_b;
+        let c = params.baeteil_curve_c;
 
         a * (b * (x - c)).ln()
     }
 }
 
 fn goratings_to_baeteil(y: f64) -> f64 {
-    if y < 3200.0 {
-        y + 6050.0
+    if let Some(x) = conversion::from_goratings("baeteil", y) {
+        return x;
+    }
+    let params = model_params::get();
+    if y < params.baeteil_curve_start - params.baeteil_offset {
+        y + params.baeteil_offset
     } else {
-        let a = 740.332659;
-        let b = 0.0946919155;
-        let c = 8456.81141;
+        let a = params.baeteil_curve_a;
+        let b = params.baeteil_curve_b;
+        let c = params.baeteil_curve_c;
 
         (y / a).exp() / b + c
     }
 }
 
-pub fn generate_player_relativities(selected_teams: &Vec<Team>, first_rapid_black: bool, first_rapid_none_color: bool) -> Result<Vec<PlayerRelativity>, String> {
+pub fn generate_player_relativities(selected_teams: &Vec<Team>, first_rapid: Option<Stone>) -> Result<Vec<PlayerRelativity>, String> {
+    generate_player_relativities_as_of(selected_teams, first_rapid, chrono::Utc::now().date_naive())
+}
+
+// 좌측팀이 각 판에 드는 돌입니다. 1국의 흑백을 알면 1국부터 흑백이 번갈아 바뀌고, 모르면 흑백 가중치를 쓰지 않습니다.
+fn board_stones(first_rapid: Option<Stone>) -> [Option<Stone>; 5] {
+    match first_rapid {
+        Some(first) => std::array::from_fn(|board| Some(if board % 2 == 0 { first } else { first.opposite() })),
+        None => [None; 5],
+    }
+}
+
+// 상대전적은 기준일까지의 대국만 셉니다.
+pub fn generate_player_relativities_as_of(selected_teams: &Vec<Team>, first_rapid: Option<Stone>, as_of: NaiveDate) -> Result<Vec<PlayerRelativity>, String> {
+    let params = model_params::get();
     let mut all_relative_records: Vec<PlayerRelativity> = Vec::new();
+    let stones = board_stones(first_rapid);
 
     let team1 = &selected_teams[0];
     let team2 = &selected_teams[1];
     for player1 in team1.players() {
         for player2 in team2.players() {
-            let record = fetch_head_to_head_record(&player1.korean_name(), &player2.korean_name())
-                               .map_err(|e| format!("상대전적을 가져오는 중 오류가 발생했습니다: {}", e))?;
+            let record = fetch_head_to_head_record(&player1.korean_name(), &player2.korean_name(), as_of).unwrap_or_else(|e| {
+                println!("경고: {} 기사와 {} 기사의 상대전적을 가져오지 못해 상대전적 없이 계산합니다: {}", player1.korean_name(), player2.korean_name(), e);
+                HashMap::new()
+            });
             let player1_wins = *record.get(player1.korean_name()).unwrap_or(&0);
             let player2_wins = *record.get(player2.korean_name()).unwrap_or(&0);
 
-            if first_rapid_none_color {
-                if first_rapid_black {
-                    let first_rapid_win_probability = calculate_win_probability(
-                        (player1.elo_rating() + player1.condition_weight() + player1.rapid_weight() + (player1.black_weight() / 2.0) + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                        (player2.elo_rating() + player2.condition_weight() + player2.rapid_weight() + (player2.white_weight() / 2.0) + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                    );
-                    let second_blitz_win_probability = calculate_win_probability(
-                        (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + player1.white_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                        (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + player2.black_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                    );
-                    let third_blitz_win_probability = calculate_win_probability(
-                        (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + player1.black_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                        (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + player2.white_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                    );
-                    let forth_blitz_win_probability = calculate_win_probability(
-                        (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + player1.white_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                        (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + player2.black_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                    );
-                    let fifth_bullet_win_probability = calculate_win_probability(
-                        (player1.elo_rating() + player1.condition_weight() + player1.bullet_weight() + (player1.black_weight() * 1.5) + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                        (player2.elo_rating() + player2.condition_weight() + player2.bullet_weight() + (player2.white_weight() * 1.5) + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                    );
-
-                    all_relative_records.push(PlayerRelativity::new(
-                        player1.clone(),
-                        player2.clone(),
-                        player1_wins,
-                        player2_wins,
-                        first_rapid_win_probability * 100.0,
-                        second_blitz_win_probability * 100.0,
-                        third_blitz_win_probability * 100.0,
-                        forth_blitz_win_probability * 100.0,
-                        fifth_bullet_win_probability * 100.0,
-                    ));
-                } else {
-                    let first_rapid_win_probability = calculate_win_probability(
-                        (player1.elo_rating() + player1.condition_weight() + player1.rapid_weight() + (player1.white_weight() / 2.0) + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                        (player2.elo_rating() + player2.condition_weight() + player2.rapid_weight() + (player2.black_weight() / 2.0) + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                    );
-                    let second_blitz_win_probability = calculate_win_probability(
-                        (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + player1.black_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                        (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + player2.white_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                    );
-                    let third_blitz_win_probability = calculate_win_probability(
-                        (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + player1.white_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                        (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + player2.black_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                    );
-                    let forth_blitz_win_probability = calculate_win_probability(
-                        (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + player1.black_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                        (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + player2.white_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                    );
-                    let fifth_bullet_win_probability = calculate_win_probability(
-                        (player1.elo_rating() + player1.condition_weight() + player1.bullet_weight() + (player1.white_weight() * 1.5) + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                        (player2.elo_rating() + player2.condition_weight() + player2.bullet_weight() + (player2.black_weight() * 1.5) + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                    );
-
-                    all_relative_records.push(PlayerRelativity::new(
-                        player1.clone(),
-                        player2.clone(),
-                        player1_wins,
-                        player2_wins,
-                        first_rapid_win_probability * 100.0,
-                        second_blitz_win_probability * 100.0,
-                        third_blitz_win_probability * 100.0,
-                        forth_blitz_win_probability * 100.0,
-                        fifth_bullet_win_probability * 100.0,
-                    ));
-                }
-            } else {
-                let first_rapid_win_probability = calculate_win_probability(
-                    (player1.elo_rating() + player1.condition_weight() + player1.rapid_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                    (player2.elo_rating() + player2.condition_weight() + player2.rapid_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                );
-                let second_blitz_win_probability = calculate_win_probability(
-                    (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                    (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                );
-                let third_blitz_win_probability = calculate_win_probability(
-                    (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                    (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                );
-                let forth_blitz_win_probability = calculate_win_probability(
-                    (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                    (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                );
-                let fifth_bullet_win_probability = calculate_win_probability(
-                    (player1.elo_rating() + player1.condition_weight() + player1.bullet_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                    (player2.elo_rating() + player2.condition_weight() + player2.bullet_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-                );
+            let elo_win_probabilities: [f64; 5] = std::array::from_fn(|board| board_probability(player1, player2, BOARD_KINDS[board], stones[board], &params));
+            let [first_rapid_win_probability, second_blitz_win_probability, third_blitz_win_probability, forth_blitz_win_probability, fifth_bullet_win_probability] = elo_win_probabilities.map(|win_probability| shrink_head_to_head(win_probability, player1_wins, player2_wins));
 
-                all_relative_records.push(PlayerRelativity::new(
-                    player1.clone(),
-                    player2.clone(),
-                    player1_wins,
-                    player2_wins,
-                    first_rapid_win_probability * 100.0,
-                    second_blitz_win_probability * 100.0,
-                    third_blitz_win_probability * 100.0,
-                    forth_blitz_win_probability * 100.0,
-                    fifth_bullet_win_probability * 100.0,
-                ));
-            }
+            let mut relativity = PlayerRelativity::new(
+                player1.clone(),
+                player2.clone(),
+                player1_wins,
+                player2_wins,
+                first_rapid_win_probability * 100.0,
+                second_blitz_win_probability * 100.0,
+                third_blitz_win_probability * 100.0,
+                forth_blitz_win_probability * 100.0,
+                fifth_bullet_win_probability * 100.0,
+            );
+            relativity.set_head_to_head_adjustment(head_to_head_adjustment(&elo_win_probabilities, player1_wins, player2_wins));
+            all_relative_records.push(relativity);
         }
     }
 
+    print_head_to_head_report(all_relative_records.iter().map(|relativity| (relativity.player1(), relativity.player2(), relativity.player1_wins(), relativity.player2_wins(), relativity.head_to_head_adjustment())).collect());
+
     Ok(all_relative_records)
 }
 
+// 각 판의 승률(%)로 4-0, 3-1, 2-2, 1-3, 0-4가 될 확률을 구합니다.
+pub fn board_outcome_probabilities(win_probabilities: &[f64]) -> (f64, f64, f64, f64, f64) {
+    let all_win_probability = win_probabilities.iter().map(|p| p / 100.0).product::<f64>();
+
+    let three_win_one_lose_probability = win_probabilities.iter().enumerate().map(|(i, &win_prob)| {
+        let lose_prob = 1.0 - (win_prob / 100.0);
+        win_probabilities.iter().enumerate().filter(|&(j, _)| i != j).map(|(_, &other_win_prob)| other_win_prob / 100.0).product::<f64>() * lose_prob
+    }).sum::<f64>();
+
+    let two_win_two_lose_probability = win_probabilities.iter().enumerate().combinations(2).map(|win_indices| {
+        let win_prob_product = win_indices.iter().map(|&(i, _)| win_probabilities[i] / 100.0).product::<f64>();
+        let lose_indices = (0..win_probabilities.len()).filter(|i| !win_indices.iter().any(|&(wi, _)| wi == *i)).collect::<Vec<_>>();
+        let lose_prob_product = lose_indices.iter().map(|&i| 1.0 - (win_probabilities[i] / 100.0)).product::<f64>();
+        win_prob_product * lose_prob_product
+    }).sum::<f64>();
+
+    let one_win_three_lose_probability = win_probabilities.iter().enumerate().map(|(i, &win_prob)| {
+        let win_prob = win_prob / 100.0;
+        win_probabilities.iter().enumerate().filter(|&(j, _)| i != j).map(|(_, &other_lose_prob)| 1.0 - (other_lose_prob / 100.0)).product::<f64>() * win_prob
+    }).sum::<f64>();
+
+    let all_lose_probability = win_probabilities.iter().map(|&win_prob| 1.0 - (win_prob / 100.0)).product::<f64>();
+
+    (all_win_probability, three_win_one_lose_probability, two_win_two_lose_probability, one_win_three_lose_probability, all_lose_probability)
+}
+
 pub fn calculate_match_result(team1_lineup: Lineup, team2_lineup: Lineup, player_relativities: Vec<PlayerRelativity>) -> MatchResult {
+    let params = model_params::get();
     let team1_players = vec![team1_lineup.first_rapid(), team1_lineup.second_blitz(), team1_lineup.third_blitz(), team1_lineup.forth_blitz()];
     let team2_players = vec![team2_lineup.first_rapid(), team2_lineup.second_blitz(), team2_lineup.third_blitz(), team2_lineup.forth_blitz()];
 
     let mut win_probabilities = vec![0.0; team1_players.len()];
     let mut bullet_win_probabilities = vec![0.0; team1_players.len()];
+    let mut win_intervals = vec![(0.0, 0.0); team1_players.len()];
 
     for (i, player1) in team1_players.iter().enumerate() {
         if let Some(player2) = team2_players.get(i) {
@@ -443,6 +581,7 @@ pub fn calculate_match_result(team1_lineup: Lineup, team2_lineup: Lineup, player
                     _ => relativity.fifth_bullet_win_probability(),
                 };
                 bullet_win_probabilities[i] = relativity.fifth_bullet_win_probability();
+                win_intervals[i] = relativity.win_interval(win_probabilities[i]);
             }
         }
     }
@@ -453,10 +592,10 @@ pub fn calculate_match_result(team1_lineup: Lineup, team2_lineup: Lineup, player
             let player2_position = team2_players.iter().position(|p| p.korean_name() == relativity.player2().korean_name());
             let player1_penalty = if let Some(pos) = player1_position {
                 match pos {
-                    0 => (1.0 / 1.04) * (1.0 / (1.0 + (0.04 * (1.0 - relativity.first_rapid_win_probability() / 100.0)))),
-                    1 => (1.0 / 1.02) * (1.0 / (1.0 + (0.02 * (1.0 - relativity.second_blitz_win_probability() / 100.0)))),
-                    2 => (1.0 / 1.08) * (1.0 / (1.0 + (0.08 * (1.0 - relativity.third_blitz_win_probability() / 100.0)))),
-                    3 => (1.0 / 1.08) * (1.0 / (1.0 + (0.08 * (1.0 - relativity.forth_blitz_win_probability() / 100.0)))),
+                    0 => (1.0 / (1.0 + params.tiebreaker_penalties[0])) * (1.0 / (1.0 + (params.tiebreaker_penalties[0] * (1.0 - relativity.first_rapid_win_probability() / 100.0)))),
+                    1 => (1.0 / (1.0 + params.tiebreaker_penalties[1])) * (1.0 / (1.0 + (params.tiebreaker_penalties[1] * (1.0 - relativity.second_blitz_win_probability() / 100.0)))),
+                    2 => (1.0 / (1.0 + params.tiebreaker_penalties[2])) * (1.0 / (1.0 + (params.tiebreaker_penalties[2] * (1.0 - relativity.third_blitz_win_probability() / 100.0)))),
+                    3 => (1.0 / (1.0 + params.tiebreaker_penalties[3])) * (1.0 / (1.0 + (params.tiebreaker_penalties[3] * (1.0 - relativity.forth_blitz_win_probability() / 100.0)))),
                     _ => 1.0,
                 }
             } else {
@@ -464,10 +603,10 @@ pub fn calculate_match_result(team1_lineup: Lineup, team2_lineup: Lineup, player
             };
             let player2_penalty = if let Some(pos) = player2_position {
                 match pos {
-                    0 => 1.04 * (1.0 + (0.04 * (1.0 - relativity.first_rapid_win_probability() / 100.0))),
-                    1 => 1.02 * (1.0 + (0.02 * (1.0 - relativity.second_blitz_win_probability() / 100.0))),
-                    2 => 1.08 * (1.0 + (0.08 * (1.0 - relativity.third_blitz_win_probability() / 100.0))),
-                    3 => 1.08 * (1.0 + (0.08 * (1.0 - relativity.forth_blitz_win_probability() / 100.0))),
+                    0 => (1.0 + params.tiebreaker_penalties[0]) * (1.0 + (params.tiebreaker_penalties[0] * (1.0 - relativity.first_rapid_win_probability() / 100.0))),
+                    1 => (1.0 + params.tiebreaker_penalties[1]) * (1.0 + (params.tiebreaker_penalties[1] * (1.0 - relativity.second_blitz_win_probability() / 100.0))),
+                    2 => (1.0 + params.tiebreaker_penalties[2]) * (1.0 + (params.tiebreaker_penalties[2] * (1.0 - relativity.third_blitz_win_probability() / 100.0))),
+                    3 => (1.0 + params.tiebreaker_penalties[3]) * (1.0 + (params.tiebreaker_penalties[3] * (1.0 - relativity.forth_blitz_win_probability() / 100.0))),
                     _ => 1.0,
                 }
             } else {
@@ -509,31 +648,22 @@ pub fn calculate_match_result(team1_lineup: Lineup, team2_lineup: Lineup, player
 
     let tiebreaker_win_probability = (team1_tiebreaker_details.map_or(50.0, |details| details.win_probability()) + team2_tiebreaker_details.map_or(50.0, |details| details.win_probability())) / 2.0;
 
-    let all_win_probability = win_probabilities.iter().map(|p| p / 100.0).product::<f64>();
-
-    let three_win_one_lose_probability = win_probabilities.iter().enumerate().map(|(i, &win_prob)| {
-        let lose_prob = 1.0 - (win_prob / 100.0);
-        win_probabilities.iter().enumerate().filter(|&(j, _)| i != j).map(|(_, &other_win_prob)| other_win_prob / 100.0).product::<f64>() * lose_prob
-    }).sum::<f64>();
-
-    let two_win_two_lose_probability = win_probabilities.iter().enumerate().combinations(2).map(|win_indices| {
-        let win_prob_product = win_indices.iter().map(|&(i, _)| win_probabilities[i] / 100.0).product::<f64>();
-        let lose_indices = (0..win_probabilities.len()).filter(|i| !win_indices.iter().any(|&(wi, _)| wi == *i)).collect::<Vec<_>>();
-        let lose_prob_product = lose_indices.iter().map(|&i| 1.0 - (win_probabilities[i] / 100.0)).product::<f64>();
-        win_prob_product * lose_prob_product
-    }).sum::<f64>();
-
-    let one_win_three_lose_probability = win_probabilities.iter().enumerate().map(|(i, &win_prob)| {
-        let win_prob = win_prob / 100.0;
-        win_probabilities.iter().enumerate().filter(|&(j, _)| i != j).map(|(_, &other_lose_prob)| 1.0 - (other_lose_prob / 100.0)).product::<f64>() * win_prob
-    }).sum::<f64>();
-
-    let all_lose_probability = win_probabilities.iter().map(|&win_prob| 1.0 - (win_prob / 100.0)).product::<f64>();
+    let (all_win_probability, three_win_one_lose_probability, two_win_two_lose_probability, one_win_three_lose_probability, all_lose_probability) = board_outcome_probabilities(&win_probabilities);
 
     let tie_win_probability = two_win_two_lose_probability * (tiebreaker_win_probability / 100.0);
 
     let total_win_probability = tie_win_probability + three_win_one_lose_probability + all_win_probability;
 
+    // 모든 판의 승률이 함께 구간의 하한(상한)일 때의 총 승리확률이므로, 실제보다 조금 넓은 보수적인 구간입니다.
+    let total_win_at = |probabilities: Vec<f64>| {
+        let (all_win, three_win_one_lose, two_win_two_lose, _, _) = board_outcome_probabilities(&probabilities);
+        (all_win + three_win_one_lose + two_win_two_lose * (tiebreaker_win_probability / 100.0)) * 100.0
+    };
+    let total_win_interval = (
+        total_win_at(win_intervals.iter().map(|interval| interval.0).collect()),
+        total_win_at(win_intervals.iter().map(|interval| interval.1).collect()),
+    );
+
     MatchResult::new(
         player_relativities.iter().find(|relativity| relativity.player1().korean_name() == team1_players[0].korean_name() && relativity.player2().korean_name() == team2_players[0].korean_name()).unwrap().clone(),
         player_relativities.iter().find(|relativity| relativity.player1().korean_name() == team1_players[1].korean_name() && relativity.player2().korean_name() == team2_players[1].korean_name()).unwrap().clone(),
@@ -549,6 +679,7 @@ pub fn calculate_match_result(team1_lineup: Lineup, team2_lineup: Lineup, player
         one_win_three_lose_probability * 100.0,
         all_lose_probability * 100.0,
         total_win_probability * 100.0,
+        total_win_interval,
         vec![team1_tiebreaker_details.cloned(), team2_tiebreaker_details.cloned()],
         tiebreaker_win_probability,
     )
@@ -572,16 +703,16 @@ pub fn create_excel_from_relativities(player_relativities: Vec<PlayerRelativity>
 
     let player1s: Vec<_> = player1_set.into_iter().sorted_by(|a, b| {
         let a_score = player_relativities.iter().find(|relativity| relativity.player1().korean_name() == a)
-            .map_or(0.0, |relativity| relativity.player1().elo_rating() + relativity.player1().condition_weight());
+            .map_or(0.0, |relativity| base_rating(relativity.player1()));
         let b_score = player_relativities.iter().find(|relativity| relativity.player1().korean_name() == b)
-            .map_or(0.0, |relativity| relativity.player1().elo_rating() + relativity.player1().condition_weight());
+            .map_or(0.0, |relativity| base_rating(relativity.player1()));
         b_score.partial_cmp(&a_score).unwrap_or(std::cmp::Ordering::Equal)
     }).collect();
     let player2s: Vec<_> = player2_set.into_iter().sorted_by(|a, b| {
         let a_score = player_relativities.iter().find(|relativity| relativity.player2().korean_name() == a)
-            .map_or(0.0, |relativity| relativity.player2().elo_rating() + relativity.player2().condition_weight());
+            .map_or(0.0, |relativity| base_rating(relativity.player2()));
         let b_score = player_relativities.iter().find(|relativity| relativity.player2().korean_name() == b)
-            .map_or(0.0, |relativity| relativity.player2().elo_rating() + relativity.player2().condition_weight());
+            .map_or(0.0, |relativity| base_rating(relativity.player2()));
         b_score.partial_cmp(&a_score).unwrap_or(std::cmp::Ordering::Equal)
     }).collect();
 
@@ -627,6 +758,7 @@ pub fn create_excel_from_relativities(player_relativities: Vec<PlayerRelativity>
     let mut worksheet_three_one = workbook.add_worksheet(Some("팀-에결없이 승리"))?;
     let mut worksheet_four_zero = workbook.add_worksheet(Some("팀-완봉승"))?;
     let mut worksheet_tiebreak = workbook.add_worksheet(Some("팀-에결진출"))?;
+    let mut worksheet_total_win_interval = workbook.add_worksheet(Some("팀-최종승리 신뢰구간"))?;
 
     for (row_index, row) in match_results_matrix.iter().enumerate() {
         if row_index == 0 { 
@@ -640,6 +772,7 @@ pub fn create_excel_from_relativities(player_relativities: Vec<PlayerRelativity>
                 worksheet_three_one.write_string(0, col_index as u16 + 1, &lineup_names, None)?;
                 worksheet_four_zero.write_string(0, col_index as u16 + 1, &lineup_names, None)?;
                 worksheet_tiebreak.write_string(0, col_index as u16 + 1, &lineup_names, None)?;
+                worksheet_total_win_interval.write_string(0, col_index as u16 + 1, &lineup_names, None)?;
             }
         }
         let lineup_names = format!("1국:{}, 2국:{}, 3국:{}, 4국:{}", 
@@ -651,6 +784,7 @@ pub fn create_excel_from_relativities(player_relativities: Vec<PlayerRelativity>
         worksheet_three_one.write_string(row_index as u32 + 1, 0, &lineup_names, None)?;
         worksheet_four_zero.write_string(row_index as u32 + 1, 0, &lineup_names, None)?;
         worksheet_tiebreak.write_string(row_index as u32 + 1, 0, &lineup_names, None)?;
+        worksheet_total_win_interval.write_string(row_index as u32 + 1, 0, &lineup_names, None)?;
 
         for (col_index, match_result) in row.iter().enumerate().take(36) {
             let total_win_format = create_custom_format(match_result.total_win_probability(), 25.0)?;
@@ -662,14 +796,78 @@ pub fn create_excel_from_relativities(player_relativities: Vec<PlayerRelativity>
             worksheet_three_one.write_number(row_index as u32 + 1, col_index as u16 + 1, (match_result.four_zero_probability() + match_result.three_one_probability()) / 100.0, Some(&win_format))?;
             worksheet_four_zero.write_number(row_index as u32 + 1, col_index as u16 + 1, match_result.four_zero_probability() / 100.0, Some(&four_zero_format))?;
             worksheet_tiebreak.write_number(row_index as u32 + 1, col_index as u16 + 1, match_result.two_two_probability() / 100.0, Some(&tiebreaker_format))?;
+            worksheet_total_win_interval.write_string(row_index as u32 + 1, col_index as u16 + 1, &uncertainty::format_interval(match_result.total_win_interval()), None)?;
         }
     }
 
+    let players = player_relativities.iter().flat_map(|relativity| [relativity.player1(), relativity.player2()]).collect();
+    write_player_ratings_worksheet(&workbook, players)?;
+
+    let interval_rows = player_relativities.iter().map(|relativity| (
+        relativity.player1(),
+        relativity.player2(),
+        relativity.rating_deviation(),
+        vec![
+            relativity.win_interval(relativity.first_rapid_win_probability()),
+            relativity.win_interval(relativity.second_blitz_win_probability()),
+            relativity.win_interval(relativity.third_blitz_win_probability()),
+            relativity.win_interval(relativity.forth_blitz_win_probability()),
+            relativity.win_interval(relativity.fifth_bullet_win_probability()),
+        ],
+    )).collect();
+    write_credible_interval_worksheet(&workbook, &["1국 장고", "2국 속기", "3국 속기", "4국 속기", "5국 초속기"], interval_rows)?;
+
     workbook.close()?;
 
     Ok(())
 }
 
+// 각 기사의 레이팅이 어느 출처의 언제 기준 값인지 함께 남깁니다.
+fn write_player_ratings_worksheet(workbook: &Workbook, players: Vec<&Player>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut worksheet = workbook.add_worksheet(Some("기사 레이팅"))?;
+    for (col, header) in ["기사", "ELO", "출처", "기준일", "레이팅 상태", "가중치 상태", "전망", "전망 내역", "KB리그 성적", "기준 레이팅", "반영 대국 수"].iter().enumerate() {
+        worksheet.write_string(0, col as u16, header, None)?;
+    }
+
+    let players: Vec<&Player> = players.into_iter().unique_by(|player| player.korean_name().clone()).collect();
+    for (index, player) in players.iter().enumerate() {
+        let row = (index + 1) as u32;
+        worksheet.write_string(row, 0, player.korean_name(), None)?;
+        worksheet.write_number(row, 1, player.elo_rating(), None)?;
+        worksheet.write_string(row, 2, player.rating_source(), None)?;
+        worksheet.write_string(row, 3, &player.rating_date().map_or(String::new(), |date| date.to_string()), None)?;
+        worksheet.write_string(row, 4, &player.rating_quality().describe(), None)?;
+        worksheet.write_string(row, 5, &player.weight_quality().describe(), None)?;
+        worksheet.write_number(row, 6, player.projection().change(), None)?;
+        worksheet.write_string(row, 7, &player.projection().describe(), None)?;
+        worksheet.write_number(row, 8, player.team_league_performance(), None)?;
+        worksheet.write_number(row, 9, player.nowcast().base_rating(), None)?;
+        worksheet.write_number(row, 10, player.nowcast().games().len() as f64, None)?;
+    }
+
+    Ok(())
+}
+
+// 각 판 승률의 90% 신뢰구간을 기사 조합별로 남깁니다.
+fn write_credible_interval_worksheet(workbook: &Workbook, boards: &[&str], rows: Vec<(&Player, &Player, f64, Vec<(f64, f64)>)>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut worksheet = workbook.add_worksheet(Some("승률 신뢰구간"))?;
+    for (col, header) in ["기사1", "기사2", "레이팅 편차"].iter().chain(boards.iter()).enumerate() {
+        worksheet.write_string(0, col as u16, header, None)?;
+    }
+
+    for (index, (player1, player2, deviation, intervals)) in rows.iter().enumerate() {
+        let row = (index + 1) as u32;
+        worksheet.write_string(row, 0, player1.korean_name(), None)?;
+        worksheet.write_string(row, 1, player2.korean_name(), None)?;
+        worksheet.write_number(row, 2, *deviation, None)?;
+        for (col, interval) in intervals.iter().enumerate() {
+            worksheet.write_string(row, (col + 3) as u16, &uncertainty::format_interval(*interval), None)?;
+        }
+    }
+
+    Ok(())
+}
+
 pub fn create_excel_from_tiebreaker_relativities(outcome_map: HashMap<&str, Vec<PlayerRelativity>>) -> Result<(), Box<dyn std::error::Error>> {
     let workbook = Workbook::new("tiebreaker_relativities.xlsx")?;
 
@@ -687,17 +885,17 @@ pub fn create_excel_from_tiebreaker_relativities(outcome_map: HashMap<&str, Vec<
 
         let player1s: Vec<_> = player1_set.into_iter().sorted_by(|a, b| {
             let a_score = tiebreaker_relativities.iter().find(|relativity| relativity.player1().korean_name() == a)
-                .map_or(0.0, |relativity| relativity.player1().elo_rating() + relativity.player1().condition_weight() + relativity.player1().bullet_weight());
+                .map_or(0.0, |relativity| base_rating(relativity.player1()) + relativity.player1().bullet_weight());
             let b_score = tiebreaker_relativities.iter().find(|relativity| relativity.player1().korean_name() == b)
-                .map_or(0.0, |relativity| relativity.player1().elo_rating() + relativity.player1().condition_weight() + relativity.player1().bullet_weight());
+                .map_or(0.0, |relativity| base_rating(relativity.player1()) + relativity.player1().bullet_weight());
             b_score.partial_cmp(&a_score).unwrap_or(std::cmp::Ordering::Equal)
         }).collect();
 
         let player2s: Vec<_> = player2_set.into_iter().sorted_by(|a, b| {
             let a_score = tiebreaker_relativities.iter().find(|relativity| relativity.player2().korean_name() == a)
-                .map_or(0.0, |relativity| relativity.player2().elo_rating() + relativity.player2().condition_weight() + relativity.player2().bullet_weight());
+                .map_or(0.0, |relativity| base_rating(relativity.player2()) + relativity.player2().bullet_weight());
             let b_score = tiebreaker_relativities.iter().find(|relativity| relativity.player2().korean_name() == b)
-                .map_or(0.0, |relativity| relativity.player2().elo_rating() + relativity.player2().condition_weight() + relativity.player2().bullet_weight());
+                .map_or(0.0, |relativity| base_rating(relativity.player2()) + relativity.player2().bullet_weight());
             b_score.partial_cmp(&a_score).unwrap_or(std::cmp::Ordering::Equal)
         }).collect();
 
@@ -765,7 +963,7 @@ pub fn select_team_combination(team: &Team) -> Vec<&Player> {
     let mut team_combination: Vec<&Player> = Vec::new();
     println!("\n{} 팀의 스쿼드:", team.team_name());
     for (index, player) in team.players().iter().enumerate() {
-        println!("{}. {} (elo: {:.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", index + 1, player.korean_name(), player.elo_rating(), player.elo_rating() + player.condition_weight(), player.elo_rating() + player.rapid_weight(), player.elo_rating() + player.blitz_weight(), player.elo_rating() + player.bullet_weight());
+        println!("{}. {} (elo: {:.2}, 전망: {:+.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", index + 1, player.korean_name(), player.elo_rating(), player.projection().change(), player.elo_rating() + player.condition_weight(), player.elo_rating() + player.rapid_weight(), player.elo_rating() + player.blitz_weight(), player.elo_rating() + player.bullet_weight());
     }
     for i in 0..4 {
         loop {
@@ -790,48 +988,21 @@ pub fn select_team_combination(team: &Team) -> Vec<&Player> {
     team_combination
 }
 
-pub fn filter_team1_lineups(selected_teams: &[Team], team1_all_lineups: &[Lineup]) -> Vec<Lineup> {
-    let unknown_player = Player::new("알 수 없음".to_string(), "unknown".to_string(), "未知".to_string(), NaiveDate::from_ymd_opt(2000, 1, 1).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new());
-
-    let mut team1_combination: Vec<&Player> = Vec::new();
-    println!("\n{} 팀의 스쿼드:", selected_teams[0].team_name());
-    println!("특정 기사에게 고정포지션이 있다면 선택해주세요. 없다면 알 수 없음을 선택해주세요.");
-    let mut last_index = 0;
-    for (index, player) in selected_teams[0].players().iter().enumerate() {
-        println!("{}. {} (elo: {:.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", index + 1, player.korean_name(), player.elo_rating(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight());
-        last_index = index;
-    }
-    println!("{}. 알 수 없음", last_index + 2);
-    for i in 0..4 {
-        loop {
-            let mut input = String::new();
-            println!("\n{} 팀의 {}국 {} 기사 번호를 입력하세요:", selected_teams[0].team_name(), i + 1, if i == 0 { "장고(rapid)" } else { "속기(blitz)" });
-            io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
-            match input.trim().parse::<usize>() {
-                Ok(num) if num > 0 && num <= selected_teams[0].players().len() => {
-                    team1_combination.push(&selected_teams[0].players()[num - 1]);
-                    break;
-                },
-                Ok(num) if num == selected_teams[0].players().len() + 1 => {
-                    team1_combination.push(&unknown_player);
-                    break;
-                },
-                _ => println!("잘못된 입력입니다. 다시 입력해주세요."),
-            }
-        }
-    }
-
-    team1_all_lineups.iter().filter(|lineup| {
-        (team1_combination[0].english_name() == "unknown" || lineup.first_rapid().korean_name() == team1_combination[0].korean_name()) &&
-        (team1_combination[1].english_name() == "unknown" || lineup.second_blitz().korean_name() == team1_combination[1].korean_name()) &&
-        (team1_combination[2].english_name() == "unknown" || lineup.third_blitz().korean_name() == team1_combination[2].korean_name()) &&
-        (team1_combination[3].english_name() == "unknown" || lineup.forth_blitz().korean_name() == team1_combination[3].korean_name())
-    }).cloned().collect()
+// 좌측팀의 라인업 조건을 입력받아 조건을 만족하는 라인업만 남깁니다. first_rapid_black은 좌측팀 1국이 흑번인지이며, 모르면 None입니다.
+pub fn filter_team1_lineups(selected_teams: &[Team], team1_all_lineups: &[Lineup], first_rapid_black: Option<bool>) -> Vec<Lineup> {
+    let constraints = constraints::read_constraints(&selected_teams[0], 4);
+    let filtered: Vec<Lineup> = team1_all_lineups.iter().filter(|lineup| {
+        let boards = [lineup.first_rapid().korean_name().as_str(), lineup.second_blitz().korean_name().as_str(), lineup.third_blitz().korean_name().as_str(), lineup.forth_blitz().korean_name().as_str()];
+        constraints.allows(&boards, first_rapid_black)
+    }).cloned().collect();
+    constraints.report(selected_teams[0].team_name(), filtered.len(), first_rapid_black);
+    filtered
 }
 
 pub async fn live_win_ratings(match_result: MatchResult, player_relativities: Vec<PlayerRelativity>) {
-    let c = Client::new("http://127.0.0.1:4444").await.expect("WebDriver에 연결하지 못했습니다.");
-    c.goto("https://home.yikeweiqi.com/#/live").await.expect("yikeweiqi에 연결하지 못했습니다.");
+    let params = model_params::get();
+    let c = Client::new(&config::get().webdriver_url).await.expect("WebDriver에 연결하지 못했습니다.");
+    c.goto(&config::get().yikeweiqi_live_url).await.expect("yikeweiqi에 연결하지 못했습니다.");
 
     let mut live_match_result = match_result.clone();
     let mut stdout = stdout();
@@ -870,29 +1041,29 @@ pub async fn live_win_ratings(match_result: MatchResult, player_relativities: Ve
 
             if (text.contains("KB") || text.contains("韩国围甲") || text.contains("韩围甲")) && livedtl_datetime < today_20_clock {
                 let mut live_win_probability = 50.0;
-                let (name1, elo1, elo2) = if text.contains(match_result.first_rapid().player1().chinese_name()) && text.contains(match_result.first_rapid().player2().chinese_name()) {
+                let (name1, elo1, elo2) = if mentions_player(&text, match_result.first_rapid().player1()) && mentions_player(&text, match_result.first_rapid().player2()) {
                     (
                         match_result.first_rapid().player1().chinese_name(),
-                        match_result.first_rapid().player1().elo_rating() + match_result.first_rapid().player1().condition_weight() + match_result.first_rapid().player1().rapid_weight(),
-                        match_result.first_rapid().player2().elo_rating() + match_result.first_rapid().player2().condition_weight() + match_result.first_rapid().player2().rapid_weight()
+                        base_rating(match_result.first_rapid().player1()) + match_result.first_rapid().player1().rapid_weight(),
+                        base_rating(match_result.first_rapid().player2()) + match_result.first_rapid().player2().rapid_weight()
                     )
-                } else if text.contains(match_result.second_blitz().player1().chinese_name()) && text.contains(match_result.second_blitz().player2().chinese_name()) {
+                } else if mentions_player(&text, match_result.second_blitz().player1()) && mentions_player(&text, match_result.second_blitz().player2()) {
                     (
                         match_result.second_blitz().player1().chinese_name(),
-                        match_result.second_blitz().player1().elo_rating() + match_result.second_blitz().player1().condition_weight() + match_result.second_blitz().player1().rapid_weight(),
-                        match_result.second_blitz().player2().elo_rating() + match_result.second_blitz().player2().condition_weight() + match_result.second_blitz().player2().rapid_weight()
+                        base_rating(match_result.second_blitz().player1()) + match_result.second_blitz().player1().rapid_weight(),
+                        base_rating(match_result.second_blitz().player2()) + match_result.second_blitz().player2().rapid_weight()
                     )
-                } else if text.contains(match_result.third_blitz().player1().chinese_name()) && text.contains(match_result.third_blitz().player2().chinese_name()) {
+                } else if mentions_player(&text, match_result.third_blitz().player1()) && mentions_player(&text, match_result.third_blitz().player2()) {
                     (
                         match_result.third_blitz().player1().chinese_name(),
-                        match_result.third_blitz().player1().elo_rating() + match_result.third_blitz().player1().condition_weight() + match_result.third_blitz().player1().rapid_weight(),
-                        match_result.third_blitz().player2().elo_rating() + match_result.third_blitz().player2().condition_weight() + match_result.third_blitz().player2().rapid_weight()
+                        base_rating(match_result.third_blitz().player1()) + match_result.third_blitz().player1().rapid_weight(),
+                        base_rating(match_result.third_blitz().player2()) + match_result.third_blitz().player2().rapid_weight()
                     )
-                } else if text.contains(match_result.forth_blitz().player1().chinese_name()) && text.contains(match_result.forth_blitz().player2().chinese_name()) {
+                } else if mentions_player(&text, match_result.forth_blitz().player1()) && mentions_player(&text, match_result.forth_blitz().player2()) {
                     (
                         match_result.forth_blitz().player1().chinese_name(),
-                        match_result.forth_blitz().player1().elo_rating() + match_result.forth_blitz().player1().condition_weight() + match_result.forth_blitz().player1().rapid_weight(),
-                        match_result.forth_blitz().player2().elo_rating() + match_result.forth_blitz().player2().condition_weight() + match_result.forth_blitz().player2().rapid_weight()
+                        base_rating(match_result.forth_blitz().player1()) + match_result.forth_blitz().player1().rapid_weight(),
+                        base_rating(match_result.forth_blitz().player2()) + match_result.forth_blitz().player2().rapid_weight()
                     )
                 } else {
                     (match_result.first_rapid().player1().chinese_name(), 0.0, 0.0)
@@ -922,16 +1093,16 @@ pub async fn live_win_ratings(match_result: MatchResult, player_relativities: Ve
                     } else {
                         50.0
                     };
-                    let current_elo_win_probability = if text.contains(match_result.first_rapid().player1().chinese_name()) && text.contains(match_result.first_rapid().player2().chinese_name()) {
+                    let current_elo_win_probability = if mentions_player(&text, match_result.first_rapid().player1()) && mentions_player(&text, match_result.first_rapid().player2()) {
                         first_rapid_now_sn = now_sn;
                         match_result.first_rapid_win_probability()
-                    } else if text.contains(match_result.second_blitz().player1().chinese_name()) && text.contains(match_result.second_blitz().player2().chinese_name()) {
+                    } else if mentions_player(&text, match_result.second_blitz().player1()) && mentions_player(&text, match_result.second_blitz().player2()) {
                         second_blitz_now_sn = now_sn;
                         match_result.second_blitz_win_probability()
-                    } else if text.contains(match_result.third_blitz().player1().chinese_name()) && text.contains(match_result.third_blitz().player2().chinese_name()) {
+                    } else if mentions_player(&text, match_result.third_blitz().player1()) && mentions_player(&text, match_result.third_blitz().player2()) {
                         third_blitz_now_sn = now_sn;
                         match_result.third_blitz_win_probability()
-                    } else if text.contains(match_result.forth_blitz().player1().chinese_name()) && text.contains(match_result.forth_blitz().player2().chinese_name()) {
+                    } else if mentions_player(&text, match_result.forth_blitz().player1()) && mentions_player(&text, match_result.forth_blitz().player2()) {
                         forth_blitz_now_sn = now_sn;
                         match_result.forth_blitz_win_probability()
                     } else {
@@ -963,16 +1134,16 @@ pub async fn live_win_ratings(match_result: MatchResult, player_relativities: Ve
                         continue;
                     };
                 }
-                if text.contains(match_result.first_rapid().player1().chinese_name()) && text.contains(match_result.first_rapid().player2().chinese_name()) {
+                if mentions_player(&text, match_result.first_rapid().player1()) && mentions_player(&text, match_result.first_rapid().player2()) {
                     first_rapid_now_sn = 200.0;
                     live_match_result.set_first_rapid_win_probability(live_win_probability);
-                } else if text.contains(match_result.second_blitz().player1().chinese_name()) && text.contains(match_result.second_blitz().player2().chinese_name()) {
+                } else if mentions_player(&text, match_result.second_blitz().player1()) && mentions_player(&text, match_result.second_blitz().player2()) {
                     second_blitz_now_sn = 200.0;
                     live_match_result.set_second_blitz_win_probability(live_win_probability);
-                } else if text.contains(match_result.third_blitz().player1().chinese_name()) && text.contains(match_result.third_blitz().player2().chinese_name()) {
+                } else if mentions_player(&text, match_result.third_blitz().player1()) && mentions_player(&text, match_result.third_blitz().player2()) {
                     third_blitz_now_sn = 200.0;
                     live_match_result.set_third_blitz_win_probability(live_win_probability);
-                } else if text.contains(match_result.forth_blitz().player1().chinese_name()) && text.contains(match_result.forth_blitz().player2().chinese_name()) {
+                } else if mentions_player(&text, match_result.forth_blitz().player1()) && mentions_player(&text, match_result.forth_blitz().player2()) {
                     forth_blitz_now_sn = 200.0;
                     live_match_result.set_forth_blitz_win_probability(live_win_probability);
                 }
@@ -1007,10 +1178,10 @@ pub async fn live_win_ratings(match_result: MatchResult, player_relativities: Ve
 
                 let player1_penalty = if let Some(pos) = player1_position {
                     match pos {
-                        0 => (1.0 / 1.04) * (1.0 / (1.0 + (0.04 * (1.0 - live_match_result.first_rapid_win_probability() / 100.0)))),
-                        1 => (1.0 / 1.02) * (1.0 / (1.0 + (0.02 * (1.0 - live_match_result.second_blitz_win_probability() / 100.0)))),
-                        2 => (1.0 / 1.08) * (1.0 / (1.0 + (0.08 * (1.0 - live_match_result.third_blitz_win_probability() / 100.0)))),
-                        3 => (1.0 / 1.08) * (1.0 / (1.0 + (0.08 * (1.0 - live_match_result.forth_blitz_win_probability() / 100.0)))),
+                        0 => (1.0 / (1.0 + params.tiebreaker_penalties[0])) * (1.0 / (1.0 + (params.tiebreaker_penalties[0] * (1.0 - live_match_result.first_rapid_win_probability() / 100.0)))),
+                        1 => (1.0 / (1.0 + params.tiebreaker_penalties[1])) * (1.0 / (1.0 + (params.tiebreaker_penalties[1] * (1.0 - live_match_result.second_blitz_win_probability() / 100.0)))),
+                        2 => (1.0 / (1.0 + params.tiebreaker_penalties[2])) * (1.0 / (1.0 + (params.tiebreaker_penalties[2] * (1.0 - live_match_result.third_blitz_win_probability() / 100.0)))),
+                        3 => (1.0 / (1.0 + params.tiebreaker_penalties[3])) * (1.0 / (1.0 + (params.tiebreaker_penalties[3] * (1.0 - live_match_result.forth_blitz_win_probability() / 100.0)))),
                         _ => 1.0,
                     }
                 } else {
@@ -1018,10 +1189,10 @@ pub async fn live_win_ratings(match_result: MatchResult, player_relativities: Ve
                 };
                 let player2_penalty = if let Some(pos) = player2_position {
                     match pos {
-                        0 => 1.04 * (1.0 + (0.04 * (1.0 - live_match_result.first_rapid_win_probability() / 100.0))),
-                        1 => 1.02 * (1.0 + (0.02 * (1.0 - live_match_result.second_blitz_win_probability() / 100.0))),
-                        2 => 1.08 * (1.0 + (0.08 * (1.0 - live_match_result.third_blitz_win_probability() / 100.0))),
-                        3 => 1.08 * (1.0 + (0.08 * (1.0 - live_match_result.forth_blitz_win_probability() / 100.0))),
+                        0 => (1.0 + params.tiebreaker_penalties[0]) * (1.0 + (params.tiebreaker_penalties[0] * (1.0 - live_match_result.first_rapid_win_probability() / 100.0))),
+                        1 => (1.0 + params.tiebreaker_penalties[1]) * (1.0 + (params.tiebreaker_penalties[1] * (1.0 - live_match_result.second_blitz_win_probability() / 100.0))),
+                        2 => (1.0 + params.tiebreaker_penalties[2]) * (1.0 + (params.tiebreaker_penalties[2] * (1.0 - live_match_result.third_blitz_win_probability() / 100.0))),
+                        3 => (1.0 + params.tiebreaker_penalties[3]) * (1.0 + (params.tiebreaker_penalties[3] * (1.0 - live_match_result.forth_blitz_win_probability() / 100.0))),
                         _ => 1.0,
                     }
                 } else {
@@ -1061,18 +1232,18 @@ pub async fn live_win_ratings(match_result: MatchResult, player_relativities: Ve
                 println!("백돌 플레이어: {}", w_player);
 
                 let relevant_tiebreaker = mapped_tiebreaker_win_probability.iter().find(|tiebreaker| {
-                    (b_player.contains(tiebreaker.player1().chinese_name()) || w_player.contains(tiebreaker.player1().chinese_name())) &&
-                    (b_player.contains(tiebreaker.player2().chinese_name()) || w_player.contains(tiebreaker.player2().chinese_name()))
+                    (mentions_player(&b_player, tiebreaker.player1()) || mentions_player(&w_player, tiebreaker.player1())) &&
+                    (mentions_player(&b_player, tiebreaker.player2()) || mentions_player(&w_player, tiebreaker.player2()))
                 });
                 if let Some(tiebreaker) = relevant_tiebreaker {
                     tiebreaker_name1 = tiebreaker.player1().korean_name().clone();
                     tiebreaker_name2 = tiebreaker.player2().korean_name().clone();
 
-                    let (name1, elo1, elo2) = if text.contains(tiebreaker.player1().chinese_name()) {
+                    let (name1, elo1, elo2) = if mentions_player(&text, tiebreaker.player1()) {
                         (
                             tiebreaker.player1().chinese_name(),
-                            tiebreaker.player1().elo_rating() + tiebreaker.player1().condition_weight() + tiebreaker.player1().bullet_weight(),
-                            tiebreaker.player2().elo_rating() + tiebreaker.player2().condition_weight() + tiebreaker.player2().bullet_weight()
+                            base_rating(tiebreaker.player1()) + tiebreaker.player1().bullet_weight(),
+                            base_rating(tiebreaker.player2()) + tiebreaker.player2().bullet_weight()
                         )
                     } else {
                         (tiebreaker.player1().chinese_name(), 0.0, 0.0)
@@ -1492,68 +1663,28 @@ pub async fn live_win_ratings(match_result: MatchResult, player_relativities: Ve
     c.close().await.expect("WebDriver를 닫는 데 실패했습니다.");
 }
 
-pub fn get_recent_record(gisa1: &str, mut gisa1_rating: f64, rating_list: &HashMap<String, f64>, ranking_month: String) -> Result<f64, Box<dyn Error>> {
-    let current_month = format!("-{:02}-", chrono::Utc::now().month());
-    let current_ranking_month = format!("-{:02}-", ranking_month.parse::<u32>().unwrap());
-
-    let re = Regex::new(r"choice\('[^']+', ?'(\d+)', ?'\d+'\)").unwrap();
-    let choice_selector = Selector::parse("li[onclick]").unwrap();
-
-    let url1 = format!("http://baduk.or.kr/common/search_pro.asp?keyword={}&R_name=player1&R_code=player1_code", gisa1);
-    let body1 = reqwest::blocking::get(&url1)?.text()?;
-    let document1 = Html::parse_document(&body1);
-    let script_texts1 = document1.select(&choice_selector).map(|script| script.value().attr("onclick").unwrap_or_default().to_string()).collect::<Vec<_>>();
-    let script_text1 = script_texts1.first().unwrap(); // 첫번째만 사용하도록 변경
-    let gisa_code = re.captures(script_text1).and_then(|cap| cap.get(1).map(|match_| match_.as_str().parse::<i32>().ok())).flatten().unwrap_or_default();
-
-    let mut matches_to_process = Vec::new();
-
-    for page_no in 1..=3 {
-        let url2 = format!("http://baduk.or.kr/record/diary_in.asp?foreignKey=&pageNo={}&keyWord={}&etcKey=&etc2=1", page_no, gisa_code);
-        let body2 = reqwest::blocking::get(&url2)?.text()?;
-        let document2 = Html::parse_document(&body2);
-
-        let match_selector = Selector::parse("tr").unwrap();
-        let date_selector = Selector::parse("td.no").unwrap();
-
-        let matches: Vec<_> = document2.select(&match_selector).collect();
-
-        for selected_match in matches.iter() {
-            if let Some(date_element) = selected_match.select(&date_selector).next() {
-                let date_text = date_element.text().collect::<String>();
-                if date_text.contains(&current_month) || date_text.contains(&current_ranking_month) {
-                    let player_names = selected_match.select(&Selector::parse("td").unwrap())
-                        .enumerate()
-                        .filter_map(|(index, element)| {
-                            if index == 1 || index == 2 || index == 3 {
-                                Some(element.text().collect::<String>())
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>();
-                    matches_to_process.push(player_names);
-                }
-            }
-        }
-    }
-
-    for players in matches_to_process.iter().rev() {
-        let winner_text = players[1].clone();
-        
-        let gisa2 = if !winner_text.contains(gisa1) { &players[1] } else { &players[2] };
-        if let Some(gisa2_rating) = rating_list.get(gisa2) {
-            let is_win = if winner_text.contains(gisa1) { 1.0 } else { 0.0 };
-            let win_probability = calculate_win_probability(gisa1_rating, baeteil_to_goratings(*gisa2_rating));
-            if players[0].contains("바둑리그") {
-                gisa1_rating += 2.0 * (is_win - win_probability);
-            } else {
-                gisa1_rating += 1.0 * (is_win - win_probability);
-            }
+// 레이팅 기준일부터 until까지의 대국을 반영합니다.
+// 기준 레이팅 이후의 대국을 날짜순으로 모두 반영합니다. 대회 목록에 K 계수가 있으면 그 값을, 없으면 단체전과 개인전의 기본 K 계수를 쓰고 대회 가중치를 곱합니다.
+pub fn nowcast_rating(gisa1: &str, base_rating: f64, rating_list: &HashMap<String, f64>, since: NaiveDate, until: NaiveDate) -> Result<RatingNowcast, Box<dyn Error>> {
+    let params = model_params::get();
+    let mut nowcast = RatingNowcast::new(base_rating);
+    for game in records::player_games_between(gisa1, since, until)? {
+        let gisa2 = game.opponent_of(gisa1);
+        match rating_list.get(gisa2) {
+            Some(gisa2_rating) => {
+                let won = game.winner_name() == gisa1;
+                let win_probability = calculate_win_probability(nowcast.rating(), baeteil_to_goratings(*gisa2_rating));
+                let event = events::catalogue().classify(game.event_name());
+                let base_k = if event.team_league { params.team_league_recent_record_k } else { params.recent_record_k };
+                let k = event.k.unwrap_or(base_k) * event.weight;
+                let change = k * (if won { 1.0 } else { 0.0 } - win_probability);
+                nowcast.push_game(NowcastGame::new(game.date(), game.event_name().clone(), gisa2.clone(), won, win_probability, k, change));
+            },
+            None => nowcast.skip_game(),
         }
     }
 
-    Ok(gisa1_rating)
+    Ok(nowcast)
 }
 
 pub fn create_excel_from_team(team_relativities_matrix: Vec<Vec<TeamRelativity>>) -> Result<(), Box<dyn std::error::Error>> {
@@ -1596,6 +1727,7 @@ pub fn create_excel_from_team(team_relativities_matrix: Vec<Vec<TeamRelativity>>
 }
 
 fn get_total_win_probability(match_result: MatchResult, player_relativities: &Vec<PlayerRelativity>) -> (f64, f64, f64) {
+    let params = model_params::get();
     let win_probabilities = [
         match_result.first_rapid_win_probability(),
         match_result.second_blitz_win_probability(),
@@ -1620,10 +1752,10 @@ fn get_total_win_probability(match_result: MatchResult, player_relativities: &Ve
 
             let player1_penalty = if let Some(pos) = player1_position {
                 match pos {
-                    0 => (1.0 / 1.04) * (1.0 / (1.0 + (0.04 * (1.0 - match_result.first_rapid_win_probability() / 100.0)))),
-                    1 => (1.0 / 1.02) * (1.0 / (1.0 + (0.02 * (1.0 - match_result.second_blitz_win_probability() / 100.0)))),
-                    2 => (1.0 / 1.08) * (1.0 / (1.0 + (0.08 * (1.0 - match_result.third_blitz_win_probability() / 100.0)))),
-                    3 => (1.0 / 1.08) * (1.0 / (1.0 + (0.08 * (1.0 - match_result.forth_blitz_win_probability() / 100.0)))),
+                    0 => (1.0 / (1.0 + params.tiebreaker_penalties[0])) * (1.0 / (1.0 + (params.tiebreaker_penalties[0] * (1.0 - match_result.first_rapid_win_probability() / 100.0)))),
+                    1 => (1.0 / (1.0 + params.tiebreaker_penalties[1])) * (1.0 / (1.0 + (params.tiebreaker_penalties[1] * (1.0 - match_result.second_blitz_win_probability() / 100.0)))),
+                    2 => (1.0 / (1.0 + params.tiebreaker_penalties[2])) * (1.0 / (1.0 + (params.tiebreaker_penalties[2] * (1.0 - match_result.third_blitz_win_probability() / 100.0)))),
+                    3 => (1.0 / (1.0 + params.tiebreaker_penalties[3])) * (1.0 / (1.0 + (params.tiebreaker_penalties[3] * (1.0 - match_result.forth_blitz_win_probability() / 100.0)))),
                     _ => 1.0,
                 }
             } else {
@@ -1631,10 +1763,10 @@ fn get_total_win_probability(match_result: MatchResult, player_relativities: &Ve
             };
             let player2_penalty = if let Some(pos) = player2_position {
                 match pos {
-                    0 => 1.04 * (1.0 + (0.04 * (1.0 - match_result.first_rapid_win_probability() / 100.0))),
-                    1 => 1.02 * (1.0 + (0.02 * (1.0 - match_result.second_blitz_win_probability() / 100.0))),
-                    2 => 1.08 * (1.0 + (0.08 * (1.0 - match_result.third_blitz_win_probability() / 100.0))),
-                    3 => 1.08 * (1.0 + (0.08 * (1.0 - match_result.forth_blitz_win_probability() / 100.0))),
+                    0 => (1.0 + params.tiebreaker_penalties[0]) * (1.0 + (params.tiebreaker_penalties[0] * (1.0 - match_result.first_rapid_win_probability() / 100.0))),
+                    1 => (1.0 + params.tiebreaker_penalties[1]) * (1.0 + (params.tiebreaker_penalties[1] * (1.0 - match_result.second_blitz_win_probability() / 100.0))),
+                    2 => (1.0 + params.tiebreaker_penalties[2]) * (1.0 + (params.tiebreaker_penalties[2] * (1.0 - match_result.third_blitz_win_probability() / 100.0))),
+                    3 => (1.0 + params.tiebreaker_penalties[3]) * (1.0 + (params.tiebreaker_penalties[3] * (1.0 - match_result.forth_blitz_win_probability() / 100.0))),
                     _ => 1.0,
                 }
             } else {
@@ -1719,82 +1851,15 @@ fn redistribute_scores(a: f64, b: f64, c: f64, d: f64) -> (f64, f64, f64, f64) {
     (redistributed_scores[0], redistributed_scores[1], redistributed_scores[2], redistributed_scores[3])
 }
 
-pub fn get_relative_and_color_weight(gisa1: &str, gisa1_eng: &str, other_team: &Team) -> Result<(f64, f64, HashMap<String, f64>, f64, f64, f64), Box<dyn Error>> {
+pub fn get_relative_and_color_weight(gisa1: &str, gisa1_eng: &str, other_team: &Team, as_of: NaiveDate) -> Result<(f64, f64, HashMap<String, f64>, f64, f64, f64), Box<dyn Error>> {
     let other_team_players = other_team.players().clone();
 
-    let current_date = chrono::Utc::now();
-    let three_years_ago = current_date - chrono::Duration::try_days(1095).unwrap();
+    let three_years_ago = as_of - chrono::Duration::try_days(1095).unwrap();
     let three_years_ago_date = NaiveDate::from_ymd_opt(three_years_ago.year(), three_years_ago.month(), 1).unwrap();
 
-    let re = Regex::new(r"choice\('[^']+', ?'(\d+)', ?'\d+'\)").unwrap();
-    let choice_selector = Selector::parse("li[onclick]").unwrap();
-
-    let url1 = format!("http://baduk.or.kr/common/search_pro.asp?keyword={}&R_name=player1&R_code=player1_code", gisa1);
-    let body1 = reqwest::blocking::get(&url1)?.text()?;
-    let document1 = Html::parse_document(&body1);
-    let script_texts1 = document1.select(&choice_selector).map(|script| script.value().attr("onclick").unwrap_or_default().to_string()).collect::<Vec<_>>();
-    let script_text1 = script_texts1.first().unwrap(); // 첫번째만 사용하도록 변경
-    let gisa_code = re.captures(script_text1).and_then(|cap| cap.get(1).map(|match_| match_.as_str().parse::<i32>().ok())).flatten().unwrap_or_default();
-
-    let mut matches_to_process = Vec::new();
-
-    let mut page_no = 1;
-    loop {
-        let url3 = format!("http://baduk.or.kr/record/diary_in.asp?foreignKey=&pageNo={}&keyWord={}&etcKey=&etc2=1", page_no, gisa_code);
-        let body3 = reqwest::blocking::get(&url3)?.text()?;
-        let document3 = Html::parse_document(&body3);
-
-        let match_selector = Selector::parse("tbody>tr").unwrap();
-        let date_selector = Selector::parse("td.no").unwrap();
-
-        let matches: Vec<_> = document3.select(&match_selector).collect();
-        if matches.is_empty() {
-            break;
-        }
-        let mut break_loop = false;
-
-        for selected_match in matches.iter() {
-            if let Some(date_element) = selected_match.select(&date_selector).next() {
-
-                let date_text = date_element.text().collect::<String>();
-
-                if let Ok(date) = NaiveDate::parse_from_str(&date_text, "%Y-%m-%d") {
-                    if date > three_years_ago_date {
-                        let mut match_result = HashMap::new();
-                        let td_texts: Vec<_> = selected_match.select(&Selector::parse("td").unwrap())
-                            .enumerate()
-                            .filter_map(|(index, element)| {
-                                if index == 1 || index == 2 || index == 3 || index == 4 {
-                                    Some(element.text().collect::<String>())
-                                } else {
-                                    None
-                                }
-                            })
-                            .collect();
-                        if td_texts[3].contains("백") {
-                            match_result.insert("winner_color", "백".to_string());
-                        } else {
-                            match_result.insert("winner_color", "흑".to_string());
-                        }
-                        match_result.insert("winner_name", td_texts[1].clone());
-                        match_result.insert("loser_name", td_texts[2].clone());
-                        match_result.insert("date", date_text);
-                        match_result.insert("match_name", td_texts[0].clone());
-                        matches_to_process.push(match_result);
-                    } else {
-                        break_loop = true;
-                        break;
-                    }
-                }
-            }
-        }
-
-        if break_loop {
-            break;
-        }
-
-        page_no += 1;
-    }
+    let matches_to_process = records::player_games_between(gisa1, three_years_ago_date + chrono::Duration::try_days(1).unwrap(), as_of)?;
+    let catalogue = events::catalogue();
+    let params = model_params::get();
 
     let mut last_month = 0;
     let mut rating_list = HashMap::new();
@@ -1820,14 +1885,15 @@ pub fn get_relative_and_color_weight(gisa1: &str, gisa1_eng: &str, other_team: &
     let mut blitz_weight = 0.0;
     let mut kb_weight = 0.0;
 
-    for match_result in matches_to_process.iter().rev() {
-        let winner_text = match_result.get("winner_name").unwrap().clone();
-        let match_name = match_result.get("match_name").unwrap().clone();
-        let match_date = NaiveDate::parse_from_str(match_result.get("date").unwrap(), "%Y-%m-%d").unwrap();
+    for match_result in matches_to_process.iter() {
+        let winner_text = match_result.winner_name().clone();
+        let match_name = match_result.event_name().clone();
+        let event = catalogue.classify(&match_name);
+        let match_date = match_result.date();
         let match_month = match_date.month();
 
         if match_month != last_month {
-            let (new_rating_list, new_white_rating, new_black_rating) = get_color_rating(gisa1, gisa1_eng, &match_date.year().to_string(), &match_month.to_string())?;
+            let (new_rating_list, new_white_rating, new_black_rating) = get_color_rating(gisa1, gisa1_eng, match_date)?;
             rating_list = new_rating_list;
 
             for (player, rating) in &relative_rating_list {
@@ -1857,35 +1923,20 @@ pub fn get_relative_and_color_weight(gisa1: &str, gisa1_eng: &str, other_team: &
             last_month = match_month;
         }
 
+        // 대회 목록의 가중치만큼 경과일 가중치를 조정합니다.
         let elapsed_days = match_date.signed_duration_since(three_years_ago_date).num_days() as f64;
-        let mut color_base_weight = elapsed_days * 0.003;
-        let mut speed_base_weight = elapsed_days * 0.0035;
-        let mut relative_base_weight = elapsed_days * 0.02;
-        let mut speed_color_base_weight = elapsed_days * 0.003;
-
-        if rating_list.contains_key(gisa1) {
-            if match_name.contains("바둑리그") {
-                color_base_weight = elapsed_days * 0.004;
-                relative_base_weight = elapsed_days * 0.025;
-                speed_color_base_weight = elapsed_days * 0.002;
-            } else {
-                color_base_weight = elapsed_days * 0.002;
-                relative_base_weight = elapsed_days * 0.015;
-                speed_color_base_weight = elapsed_days * 0.004;
-            }
+        let weighted_days = elapsed_days * event.weight;
+        let speed_base_weight = weighted_days * params.speed_slope;
+        let (color_base_weight, mut relative_base_weight, speed_color_base_weight) = if event.team_league {
+            (weighted_days * params.team_league_color_slope, weighted_days * params.team_league_relative_slope, weighted_days * params.team_league_speed_color_slope)
         } else {
-            if match_name.contains("바둑리그") {
-                color_base_weight = elapsed_days * 0.004;
-                relative_base_weight = elapsed_days * 0.05;
-                speed_color_base_weight = elapsed_days * 0.002;
-            } else {
-                color_base_weight = elapsed_days * 0.002;
-                relative_base_weight = elapsed_days * 0.03;
-                speed_color_base_weight = elapsed_days * 0.004;
-            }
+            (weighted_days * params.color_slope, weighted_days * params.relative_slope, weighted_days * params.speed_color_slope)
+        };
+        if !rating_list.contains_key(gisa1) {
+            relative_base_weight *= params.unrated_relative_multiplier;
         }
 
-        let gisa2 = if !winner_text.contains(gisa1) { match_result.get("winner_name").unwrap() } else { match_result.get("loser_name").unwrap() };
+        let gisa2 = match_result.opponent_of(gisa1);
         if let Some(gisa2_rating) = rating_list.get(gisa2.as_str()) {
             let is_win = if winner_text.contains(gisa1) { 1.0 } else { 0.0 };
 
@@ -1894,53 +1945,23 @@ pub fn get_relative_and_color_weight(gisa1: &str, gisa1_eng: &str, other_team: &
                 *relative_rating_list.entry(gisa2.clone()).or_insert(0.0) += relative_base_weight * (is_win - win_probability);
             }
 
-            if !match_name.contains("중국") &&
-                !match_name.contains("궁륭산병성") &&
-                !match_name.contains("녜웨이핑") &&
-                !match_name.contains("루양") &&
-                !match_name.contains("몽백합") &&
-                !match_name.contains("바이링") &&
-                !match_name.contains("봉황고성") &&
-                !match_name.contains("오청원") &&
-                !match_name.contains("응씨") &&
-                !match_name.contains("천부") &&
-                !match_name.contains("천태산") &&
-                !match_name.contains("춘란") &&
-                !match_name.contains("취저우") &&
-                !match_name.contains("란커") &&
-                !match_name.contains("황룡사")
-            {
-                if (match_name.contains("2023-2024 KB국민은행 바둑리그")) {
+            if !event.excluded {
+                // 시즌과 관계없이 최근 3년의 KB리그 대국으로 KB리그에서의 성적을 계산합니다.
+                if event.kb_league {
                     let win_probability = calculate_win_probability(kb_rating, baeteil_to_goratings(*gisa2_rating));
-                    kb_rating += 8.0 * (is_win - win_probability);
+                    kb_rating += params.kb_league_k * event.weight * (is_win - win_probability);
                 }
-                if (match_result.get("winner_color").unwrap().contains("백") && is_win == 1.0) || (match_result.get("winner_color").unwrap().contains("흑") && is_win == 0.0) {
+                if (match_result.winner_color().contains("백") && is_win == 1.0) || (match_result.winner_color().contains("흑") && is_win == 0.0) {
                     let win_probability = calculate_win_probability(white_rating, baeteil_to_goratings(*gisa2_rating));
                     white_rating += color_base_weight * (is_win - win_probability);
                     speed_white_rating += speed_color_base_weight * (is_win - win_probability);
-                } else if (match_result.get("winner_color").unwrap().contains("흑") && is_win == 1.0) || (match_result.get("winner_color").unwrap().contains("백") && is_win == 0.0) {
+                } else if (match_result.winner_color().contains("흑") && is_win == 1.0) || (match_result.winner_color().contains("백") && is_win == 0.0) {
                     let win_probability = calculate_win_probability(black_rating, baeteil_to_goratings(*gisa2_rating));
                     black_rating += color_base_weight * (is_win - win_probability);
                     speed_black_rating += speed_color_base_weight * (is_win - win_probability);
                 }
-                if (!match_name.contains("KB국민은행 바둑리그")) {
-                    if (match_name.contains("프로기사협회") ||
-                        match_name.contains("퓨처스") ||
-                        match_name.contains("크라운해태") ||
-                        match_name.contains("용성전") ||
-                        match_name.contains("하찬석국수") ||
-                        match_name.contains("MZ") ||
-                        match_name.contains("백암") ||
-                        match_name.contains("국토정중앙") ||
-                        match_name.contains("루키바둑") ||
-                        match_name.contains("유소년 신예최강") ||
-                        match_name.contains("유소년신예최강") ||
-                        match_name.contains("김인국수") ||
-                        match_name.contains("이붕") ||
-                        match_name.contains("KBS") || 
-                        match_name.contains("대통령") ||
-                        match_name.contains("난설헌")
-                    ) {
+                if !event.kb_league {
+                    if event.time_control() == TimeControl::Blitz {
                         let win_probability = calculate_win_probability(blitz_rating, baeteil_to_goratings(*gisa2_rating));
                         blitz_rating += speed_base_weight * (is_win - win_probability);
                     } else {
@@ -1955,8 +1976,8 @@ pub fn get_relative_and_color_weight(gisa1: &str, gisa1_eng: &str, other_team: &
     Ok((white_weight, black_weight, relative_weight_list, live_weight, blitz_weight, kb_weight))
 }
 
-pub fn get_color_rating(korean_name: &str, english_name: &str, year: &str, month: &str) -> Result<(HashMap<String, f64>, f64, f64), Box<dyn Error>> {
-    let (rating_list, _) = fetch_player_ratings_on_baeteil(year, month)?;
+pub fn get_color_rating(korean_name: &str, english_name: &str, date: NaiveDate) -> Result<(HashMap<String, f64>, f64, f64), Box<dyn Error>> {
+    let (rating_list, _) = rating_archive::baeteil_list(date.year(), date.month())?;
     if rating_list.contains_key(korean_name) {
         Ok((
             rating_list.clone(),
@@ -1964,7 +1985,7 @@ pub fn get_color_rating(korean_name: &str, english_name: &str, year: &str, month
             baeteil_to_goratings(*rating_list.get(korean_name).unwrap_or(&0.0)),
         ))
     } else {
-        let player_ratings_on_goratings = fetch_player_ratings_on_goratings()?;
+        let player_ratings_on_goratings = rating_archive::goratings_list_as_of(date)?;
         Ok((
             rating_list.clone(),
             *player_ratings_on_goratings.get(english_name).unwrap_or(&0.0),
@@ -1974,59 +1995,28 @@ pub fn get_color_rating(korean_name: &str, english_name: &str, year: &str, month
 }
 
 pub fn generate_player_relativities_post(selected_teams: &Vec<Team>) -> Result<Vec<PostPlayerRelativity>, String> {
+    let params = model_params::get();
     let mut all_relative_records: Vec<PostPlayerRelativity> = Vec::new();
 
     let team1 = &selected_teams[0];
     let team2 = &selected_teams[1];
     for player1 in team1.players() {
         for player2 in team2.players() {
-            let record = fetch_head_to_head_record(&player1.korean_name(), &player2.korean_name())
-                               .map_err(|e| format!("상대전적을 가져오는 중 오류가 발생했습니다: {}", e))?;
+            let record = fetch_head_to_head_record(&player1.korean_name(), &player2.korean_name(), chrono::Utc::now().date_naive()).unwrap_or_else(|e| {
+                println!("경고: {} 기사와 {} 기사의 상대전적을 가져오지 못해 상대전적 없이 계산합니다: {}", player1.korean_name(), player2.korean_name(), e);
+                HashMap::new()
+            });
             let player1_wins = *record.get(player1.korean_name()).unwrap_or(&0);
             let player2_wins = *record.get(player2.korean_name()).unwrap_or(&0);
 
-            let first_rapid_white_win_probability = calculate_win_probability(
-                (player1.elo_rating() + player1.condition_weight() + player1.rapid_weight() + (player1.white_weight() / 2.0) + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                (player2.elo_rating() + player2.condition_weight() + player2.rapid_weight() + (player2.black_weight() / 2.0) + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-            );
-            let second_blitz_white_win_probability = calculate_win_probability(
-                (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + player1.white_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + player2.black_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-            );
-            let third_blitz_white_win_probability = calculate_win_probability(
-                (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + player1.white_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + player2.black_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-            );
-            let forth_blitz_white_win_probability = calculate_win_probability(
-                (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + player1.white_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + player2.black_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-            );
-            let fifth_bullet_white_win_probability = calculate_win_probability(
-                (player1.elo_rating() + player1.condition_weight() + player1.bullet_weight() + (player1.white_weight() * 1.5) + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                (player2.elo_rating() + player2.condition_weight() + player2.bullet_weight() + (player2.black_weight() * 1.5) + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-            );
-            let first_rapid_black_win_probability = calculate_win_probability(
-                (player1.elo_rating() + player1.condition_weight() + player1.rapid_weight() + (player1.black_weight() / 2.0) + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                (player2.elo_rating() + player2.condition_weight() + player2.rapid_weight() + (player2.white_weight() / 2.0) + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-            );
-            let second_blitz_black_win_probability = calculate_win_probability(
-                (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + player1.black_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + player2.white_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-            );
-            let third_blitz_black_win_probability = calculate_win_probability(
-                (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + player1.black_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + player2.white_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-            );
-            let forth_blitz_black_win_probability = calculate_win_probability(
-                (player1.elo_rating() + player1.condition_weight() + player1.blitz_weight() + player1.black_weight() + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                (player2.elo_rating() + player2.condition_weight() + player2.blitz_weight() + player2.white_weight() + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-            );
-            let fifth_bullet_black_win_probability = calculate_win_probability(
-                (player1.elo_rating() + player1.condition_weight() + player1.bullet_weight() + (player1.black_weight() * 1.5) + *player1.relative_weight().get(player2.korean_name().as_str()).unwrap_or(&0.0)) as f64,
-                (player2.elo_rating() + player2.condition_weight() + player2.bullet_weight() + (player2.white_weight() * 1.5) + *player2.relative_weight().get(player1.korean_name().as_str()).unwrap_or(&0.0)) as f64
-            );
+            // 앞의 다섯 판은 player1이 백, 뒤의 다섯 판은 흑을 든 경우입니다.
+            let elo_win_probabilities: [f64; 10] = std::array::from_fn(|index| {
+                let stone = if index < 5 { Stone::White } else { Stone::Black };
+                board_probability(player1, player2, BOARD_KINDS[index % 5], Some(stone), &params)
+            });
+            let [first_rapid_white_win_probability, second_blitz_white_win_probability, third_blitz_white_win_probability, forth_blitz_white_win_probability, fifth_bullet_white_win_probability, first_rapid_black_win_probability, second_blitz_black_win_probability, third_blitz_black_win_probability, forth_blitz_black_win_probability, fifth_bullet_black_win_probability] = elo_win_probabilities.map(|win_probability| shrink_head_to_head(win_probability, player1_wins, player2_wins));
 
-            all_relative_records.push(PostPlayerRelativity::new(
+            let mut relativity = PostPlayerRelativity::new(
                 player1.clone(),
                 player2.clone(),
                 player1_wins,
@@ -2041,10 +2031,14 @@ pub fn generate_player_relativities_post(selected_teams: &Vec<Team>) -> Result<V
                 third_blitz_black_win_probability * 100.0,
                 forth_blitz_black_win_probability * 100.0,
                 fifth_bullet_black_win_probability * 100.0,
-            ));
+            );
+            relativity.set_head_to_head_adjustment(head_to_head_adjustment(&elo_win_probabilities, player1_wins, player2_wins));
+            all_relative_records.push(relativity);
         }
     }
 
+    print_head_to_head_report(all_relative_records.iter().map(|relativity| (relativity.player1(), relativity.player2(), relativity.player1_wins(), relativity.player2_wins(), relativity.head_to_head_adjustment())).collect());
+
     Ok(all_relative_records)
 }
 
@@ -2191,16 +2185,16 @@ pub fn create_excel_from_relativities_post(player_relativities: Vec<PostPlayerRe
 
     let player1s: Vec<_> = player1_set.into_iter().sorted_by(|a, b| {
         let a_score = player_relativities.iter().find(|relativity| relativity.player1().korean_name() == a)
-            .map_or(0.0, |relativity| relativity.player1().elo_rating() + relativity.player1().condition_weight());
+            .map_or(0.0, |relativity| base_rating(relativity.player1()));
         let b_score = player_relativities.iter().find(|relativity| relativity.player1().korean_name() == b)
-            .map_or(0.0, |relativity| relativity.player1().elo_rating() + relativity.player1().condition_weight());
+            .map_or(0.0, |relativity| base_rating(relativity.player1()));
         b_score.partial_cmp(&a_score).unwrap_or(std::cmp::Ordering::Equal)
     }).collect();
     let player2s: Vec<_> = player2_set.into_iter().sorted_by(|a, b| {
         let a_score = player_relativities.iter().find(|relativity| relativity.player2().korean_name() == a)
-            .map_or(0.0, |relativity| relativity.player2().elo_rating() + relativity.player2().condition_weight());
+            .map_or(0.0, |relativity| base_rating(relativity.player2()));
         let b_score = player_relativities.iter().find(|relativity| relativity.player2().korean_name() == b)
-            .map_or(0.0, |relativity| relativity.player2().elo_rating() + relativity.player2().condition_weight());
+            .map_or(0.0, |relativity| base_rating(relativity.player2()));
         b_score.partial_cmp(&a_score).unwrap_or(std::cmp::Ordering::Equal)
     }).collect();
 
@@ -2262,44 +2256,33 @@ pub fn create_excel_from_relativities_post(player_relativities: Vec<PostPlayerRe
         worksheet_fifth_bullet_black.write_number(row.try_into().unwrap(), col.try_into().unwrap(), relativity.fifth_bullet_black_win_probability() / 100.0, Some(&fifth_bullet_black_format))?;
     }
 
+    let players = player_relativities.iter().flat_map(|relativity| [relativity.player1(), relativity.player2()]).collect();
+    write_player_ratings_worksheet(&workbook, players)?;
+
+    let interval_rows = player_relativities.iter().map(|relativity| (
+        relativity.player1(),
+        relativity.player2(),
+        relativity.rating_deviation(),
+        vec![
+            relativity.win_interval(relativity.first_rapid_white_win_probability()),
+            relativity.win_interval(relativity.first_rapid_black_win_probability()),
+            relativity.win_interval(relativity.second_blitz_white_win_probability()),
+            relativity.win_interval(relativity.second_blitz_black_win_probability()),
+            relativity.win_interval(relativity.third_blitz_white_win_probability()),
+            relativity.win_interval(relativity.third_blitz_black_win_probability()),
+            relativity.win_interval(relativity.forth_blitz_white_win_probability()),
+            relativity.win_interval(relativity.forth_blitz_black_win_probability()),
+            relativity.win_interval(relativity.fifth_bullet_white_win_probability()),
+            relativity.win_interval(relativity.fifth_bullet_black_win_probability()),
+        ],
+    )).collect();
+    write_credible_interval_worksheet(&workbook, &["1국-백", "1국-흑", "2국-백", "2국-흑", "3국-백", "3국-흑", "4국-백", "4국-흑", "5국-백", "5국-흑"], interval_rows)?;
+
     workbook.close()?;
 
     Ok(())
 }
 
-pub fn get_team_combination_post(selected_team: &Team) -> Vec<Player> {
-    let unknown_player = Player::new("알 수 없음".to_string(), "unknown".to_string(), "未知".to_string(), NaiveDate::from_ymd_opt(2000, 1, 1).expect("Invalid date"), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, HashMap::new());
-
-    let mut team_combination: Vec<Player> = Vec::new();
-    println!("\n{} 팀의 스쿼드:", selected_team.team_name());
-    println!("특정 기사에게 고정포지션이 있다면 선택해주세요. 없다면 알 수 없음을 선택해주세요.");
-    let mut last_index = 0;
-    for (index, player) in selected_team.players().iter().enumerate() {
-        println!("{}. {} (elo: {:.2}, 컨디션: {:.2}, 장고: {:.2}, 속기: {:.2}, 초속기: {:.2})", index + 1, player.korean_name(), player.elo_rating(), player.condition_weight(), player.rapid_weight(), player.blitz_weight(), player.bullet_weight());
-        last_index = index;
-    }
-    println!("{}. 알 수 없음", last_index + 2);
-    for i in 0..5 {
-        loop {
-            let mut input = String::new();
-            println!("\n{} 팀의 {}국 {} 기사 번호를 입력하세요:", selected_team.team_name(), i + 1, if i == 0 { "장고(rapid)" } else if i < 4 { "속기(blitz)" } else { "초속기(bullet)" });
-            io::stdin().read_line(&mut input).expect("입력을 읽는 데 실패했습니다.");
-            match input.trim().parse::<usize>() {
-                Ok(num) if num > 0 && num <= selected_team.players().len() => {
-                    team_combination.push(selected_team.players()[num - 1].clone());
-                    break;
-                },
-                Ok(num) if num == selected_team.players().len() + 1 => {
-                    team_combination.push(unknown_player.clone());
-                    break;
-                },
-                _ => println!("잘못된 입력입니다. 다시 입력해주세요."),
-            }
-        }
-    }
-    team_combination
-}
-
 pub fn get_123_wpa_win_result_post(match_result: &PostMatchResult) -> PostWPAResult {
     let mut match_result_for_first_rapid_wpa = match_result.clone();
     match_result_for_first_rapid_wpa.set_first_rapid_white_win_probability(100.0);
@@ -3042,3 +3025,28 @@ pub fn wpa_to_rax_post(first_wpa: f64, second_wpa: f64, third_wpa: f64, forth_wp
         positive_values[4] * scale_factor
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn board_stones_alternate_only_when_the_first_colour_is_known() {
+        let black = board_stones(Some(Stone::Black));
+        let white = board_stones(Some(Stone::White));
+        assert!(black == [Some(Stone::Black), Some(Stone::White), Some(Stone::Black), Some(Stone::White), Some(Stone::Black)]);
+        assert!(white == [Some(Stone::White), Some(Stone::Black), Some(Stone::White), Some(Stone::Black), Some(Stone::White)]);
+        assert!(board_stones(None) == [None; 5]);
+    }
+
+    #[test]
+    fn colour_weights_apply_only_with_a_known_colour() {
+        let params = ModelParams::default();
+        let strong_with_black = Player::new("갑".to_string(), String::new(), String::new(), NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(), 0.0, 0.0, 0.0, 0.0, 0.0, -40.0, 40.0, HashMap::new());
+        let opponent = Player::named("을");
+        let first_rapid = |stone| board_probability(&strong_with_black, &opponent, BoardKind::Rapid, board_stones(stone)[0], &params);
+        assert!(first_rapid(Some(Stone::Black)) > 0.5);
+        assert!(first_rapid(Some(Stone::White)) < 0.5);
+        assert!((first_rapid(None) - 0.5).abs() < 1e-12);
+    }
+}
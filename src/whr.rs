@@ -0,0 +1,282 @@
+use crate::config;
+use crate::models::GameRecord;
+use crate::rating_archive;
+use crate::records;
+use crate::season;
+use crate::utils::baeteil_to_goratings;
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::error::Error;
+use std::f64::consts::LN_10;
+use std::sync::{Mutex, OnceLock};
+
+// 한 달 동안 실력이 변할 수 있는 정도(표준편차)입니다.
+const MONTHLY_DRIFT: f64 = 25.0;
+// 처음 나온 달의 레이팅에 거는 약한 사전분포의 표준편차입니다. 전승이나 전패인 기사의 레이팅이 끝없이 커지지 않게 합니다.
+const PRIOR_DEVIATION: f64 = 400.0;
+// 이보다 대국이 적은 기사는 결과에 넣지 않습니다.
+const MIN_GAMES: usize = 5;
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 0.01;
+
+const Q: f64 = LN_10 / 400.0;
+
+// 기사의 대국이 있는 달마다 하나씩 두는 레이팅입니다.
+struct Period {
+    rating: f64,
+    // (상대 기간, 이겼는지)
+    games: Vec<(usize, bool)>,
+    previous: Option<(usize, f64)>,
+    next: Option<(usize, f64)>,
+}
+
+pub struct WholeHistoryRating {
+    pub rating: f64,
+    pub games: usize,
+}
+
+fn month_index(date: NaiveDate) -> i32 {
+    date.year() * 12 + date.month0() as i32
+}
+
+// 다른 기간의 레이팅을 고정하고, 한 기간의 로그 사후확률을 가장 크게 하도록 레이팅을 옮길 양입니다.
+fn newton_step(periods: &[Period], index: usize) -> f64 {
+    let rating = periods[index].rating;
+    let mut gradient = 0.0;
+    let mut hessian = 0.0;
+    for &(opponent, won) in &periods[index].games {
+        let probability = 1.0 / (1.0 + 10f64.powf((periods[opponent].rating - rating) / 400.0));
+        gradient += Q * (if won { 1.0 } else { 0.0 } - probability);
+        hessian += Q * Q * probability * (1.0 - probability);
+    }
+    for neighbour in [periods[index].previous, periods[index].next].into_iter().flatten() {
+        let (other, variance) = neighbour;
+        gradient -= (rating - periods[other].rating) / variance;
+        hessian += 1.0 / variance;
+    }
+    if periods[index].previous.is_none() {
+        gradient -= rating / (PRIOR_DEVIATION * PRIOR_DEVIATION);
+        hessian += 1.0 / (PRIOR_DEVIATION * PRIOR_DEVIATION);
+    }
+    gradient / hessian
+}
+
+// 기사마다 월별 레이팅을 두고, 이웃한 달의 레이팅은 지난 개월 수만큼 멀어질 수 있는 사전분포로 잇습니다.
+// 모든 대국의 Bradley-Terry 우도와 사전분포의 곱이 가장 큰 레이팅을 기간별 뉴턴법으로 찾습니다.
+fn fit(games: &[GameRecord]) -> HashMap<String, WholeHistoryRating> {
+    let mut player_index: HashMap<&str, usize> = HashMap::new();
+    let mut player_names: Vec<&str> = Vec::new();
+    let mut game_counts: Vec<usize> = Vec::new();
+    let mut period_index: HashMap<(usize, i32), usize> = HashMap::new();
+    let mut periods: Vec<Period> = Vec::new();
+
+    for game in games {
+        let month = month_index(game.date());
+        let mut sides = [0; 2];
+        for (side, name) in [game.winner_name(), game.loser_name()].into_iter().enumerate() {
+            let player = *player_index.entry(name.as_str()).or_insert_with(|| {
+                player_names.push(name.as_str());
+                game_counts.push(0);
+                player_names.len() - 1
+            });
+            game_counts[player] += 1;
+            sides[side] = *period_index.entry((player, month)).or_insert_with(|| {
+                periods.push(Period { rating: 0.0, games: Vec::new(), previous: None, next: None });
+                periods.len() - 1
+            });
+        }
+        let [winner, loser] = sides;
+        periods[winner].games.push((loser, true));
+        periods[loser].games.push((winner, false));
+    }
+
+    // 기사별로 달 순서대로 이웃한 기간을 잇습니다.
+    let mut by_player: HashMap<usize, Vec<(i32, usize)>> = HashMap::new();
+    for (&(player, month), &period) in &period_index {
+        by_player.entry(player).or_default().push((month, period));
+    }
+    for timeline in by_player.values_mut() {
+        timeline.sort();
+        for window in timeline.windows(2) {
+            let ((month1, period1), (month2, period2)) = (window[0], window[1]);
+            let variance = MONTHLY_DRIFT * MONTHLY_DRIFT * (month2 - month1) as f64;
+            periods[period1].next = Some((period2, variance));
+            periods[period2].previous = Some((period1, variance));
+        }
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut max_change: f64 = 0.0;
+        for index in 0..periods.len() {
+            let change = newton_step(&periods, index);
+            periods[index].rating += change;
+            max_change = max_change.max(change.abs());
+        }
+        if max_change < TOLERANCE {
+            break;
+        }
+    }
+
+    // 마지막으로 대국한 달의 레이팅을 현재 레이팅으로 봅니다.
+    let mut result = HashMap::new();
+    for (player, timeline) in by_player {
+        if game_counts[player] < MIN_GAMES {
+            continue;
+        }
+        if let Some(&(_, period)) = timeline.last() {
+            result.insert(player_names[player].to_string(), WholeHistoryRating { rating: periods[period].rating, games: game_counts[player] });
+        }
+    }
+    result
+}
+
+fn fitted_ratings() -> &'static Mutex<HashMap<NaiveDate, HashMap<String, f64>>> {
+    static FITTED_RATINGS: OnceLock<Mutex<HashMap<NaiveDate, HashMap<String, f64>>>> = OnceLock::new();
+    FITTED_RATINGS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// 기준일까지 저장된 대국으로 레이팅을 계산하고, 배태일 랭킹에도 있는 기사들의 평균이 같아지도록 goratings 척도로 옮깁니다.
+pub fn whole_history_ratings(as_of: NaiveDate, player_ratings_on_baeteil: &HashMap<String, f64>) -> Result<HashMap<String, WholeHistoryRating>, Box<dyn Error>> {
+    let since = as_of - Duration::try_days(config::get().records_history_days as i64).unwrap();
+    let mut ratings = fit(&records::games_between(since, as_of)?);
+
+    let offsets: Vec<f64> = ratings
+        .iter()
+        .filter_map(|(name, whr)| player_ratings_on_baeteil.get(name).map(|rating| baeteil_to_goratings(*rating) - whr.rating))
+        .collect();
+    if offsets.is_empty() {
+        return Err("배태일 랭킹과 겹치는 기사가 없어 대국 기록 레이팅의 척도를 맞출 수 없습니다.".into());
+    }
+    let offset = offsets.iter().sum::<f64>() / offsets.len() as f64;
+    for whr in ratings.values_mut() {
+        whr.rating += offset;
+    }
+    Ok(ratings)
+}
+
+// 백테스트에서는 같은 날짜를 여러 번 계산하므로 날짜별로 한 번만 계산합니다.
+pub fn whole_history_ratings_cached(as_of: NaiveDate, player_ratings_on_baeteil: &HashMap<String, f64>) -> HashMap<String, f64> {
+    if let Some(ratings) = fitted_ratings().lock().unwrap().get(&as_of) {
+        return ratings.clone();
+    }
+    let ratings: HashMap<String, f64> = match whole_history_ratings(as_of, player_ratings_on_baeteil) {
+        Ok(ratings) => ratings.into_iter().map(|(name, whr)| (name, whr.rating)).collect(),
+        Err(e) => {
+            println!("경고: 대국 기록 레이팅을 계산하지 못했습니다: {}", e);
+            HashMap::new()
+        }
+    };
+    fitted_ratings().lock().unwrap().insert(as_of, ratings.clone());
+    ratings
+}
+
+// (기사 수, 평균 절대 차이, 상관계수)
+fn agreement(pairs: &[(f64, f64)]) -> (usize, f64, f64) {
+    let count = pairs.len() as f64;
+    let mean_abs_diff = pairs.iter().map(|(a, b)| (a - b).abs()).sum::<f64>() / count;
+    let mean_a = pairs.iter().map(|(a, _)| a).sum::<f64>() / count;
+    let mean_b = pairs.iter().map(|(_, b)| b).sum::<f64>() / count;
+    let covariance: f64 = pairs.iter().map(|(a, b)| (a - mean_a) * (b - mean_b)).sum();
+    let variance_a: f64 = pairs.iter().map(|(a, _)| (a - mean_a).powi(2)).sum();
+    let variance_b: f64 = pairs.iter().map(|(_, b)| (b - mean_b).powi(2)).sum();
+    (pairs.len(), mean_abs_diff, covariance / (variance_a * variance_b).sqrt())
+}
+
+// 현재 시즌 기사들의 대국 기록 레이팅을 배태일 랭킹, goratings와 나란히 보여주고 얼마나 일치하는지 요약합니다.
+pub fn compare_with_federation_lists() -> Result<(), Box<dyn Error>> {
+    let today = Utc::now().date_naive();
+    let player_ratings_on_baeteil = match rating_archive::baeteil_list(today.year(), today.month()) {
+        Ok((ratings, _)) => ratings,
+        Err(e) => match rating_archive::latest_baeteil_list()? {
+            Some((_, _, ratings, _)) => ratings,
+            None => return Err(e),
+        },
+    };
+    let player_ratings_on_goratings = rating_archive::goratings_list_as_of(today).unwrap_or_default();
+    let ratings = whole_history_ratings(today, &player_ratings_on_baeteil)?;
+    let season = season::load_current_season()?;
+
+    let format_rating = |rating: Option<f64>| rating.map_or("-".to_string(), |rating| format!("{:.0}", rating));
+    let mut baeteil_pairs = Vec::new();
+    let mut goratings_pairs = Vec::new();
+    println!("\n기사: 대국 기록(WHR) / 배태일(goratings 환산) / goratings");
+    for player in season.teams().iter().flat_map(|team| team.players()) {
+        let whr = ratings.get(player.korean_name());
+        let baeteil = player_ratings_on_baeteil.get(player.korean_name()).map(|rating| baeteil_to_goratings(*rating));
+        let goratings = player_ratings_on_goratings.get(player.english_name()).copied();
+        println!(
+            "{}: {}{} / {} / {}",
+            player.korean_name(),
+            format_rating(whr.map(|whr| whr.rating)),
+            whr.map_or(String::new(), |whr| format!(" ({}판)", whr.games)),
+            format_rating(baeteil),
+            format_rating(goratings)
+        );
+        if let Some(whr) = whr {
+            if let Some(baeteil) = baeteil {
+                baeteil_pairs.push((whr.rating, baeteil));
+            }
+            if let Some(goratings) = goratings {
+                goratings_pairs.push((whr.rating, goratings));
+            }
+        }
+    }
+
+    println!("\n대국 기록 레이팅이 있는 기사: {}명 (배태일 랭킹에 없는 기사 {}명)", ratings.len(), ratings.keys().filter(|name| !player_ratings_on_baeteil.contains_key(*name)).count());
+    for (label, pairs) in [("배태일", &baeteil_pairs), ("goratings", &goratings_pairs)] {
+        if pairs.len() < 2 {
+            println!("{}: 비교할 기사가 부족합니다.", label);
+            continue;
+        }
+        let (count, mean_abs_diff, correlation) = agreement(pairs);
+        println!("{}: {}명, 평균 차이 {:.1}, 상관계수 {:.3}", label, count, mean_abs_diff, correlation);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn period(rating: f64, games: Vec<(usize, bool)>) -> Period {
+        Period { rating, games, previous: None, next: None }
+    }
+
+    fn game(month: u32, winner: &str, loser: &str) -> GameRecord {
+        GameRecord::new(NaiveDate::from_ymd_opt(2024, month, 1).unwrap(), String::new(), winner.to_string(), loser.to_string(), String::new())
+    }
+
+    #[test]
+    fn newton_step_moves_a_winner_up_by_gradient_over_hessian() {
+        let periods = vec![period(0.0, vec![(1, true)]), period(0.0, vec![(0, false)])];
+        let prior = 1.0 / (PRIOR_DEVIATION * PRIOR_DEVIATION);
+        let expected = (Q * 0.5) / (Q * Q * 0.25 + prior);
+        assert!((newton_step(&periods, 0) - expected).abs() < 1e-9);
+        assert!((newton_step(&periods, 1) + expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn newton_step_pulls_towards_the_previous_month() {
+        // 대국이 없는 기간은 이전 달의 레이팅으로 바로 옮겨 갑니다.
+        let mut periods = vec![period(100.0, Vec::new()), period(0.0, Vec::new())];
+        periods[1].previous = Some((0, MONTHLY_DRIFT * MONTHLY_DRIFT));
+        assert!((newton_step(&periods, 1) - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fit_rates_the_winner_above_the_loser() {
+        let mut games = Vec::new();
+        for month in 1..=3 {
+            games.push(game(month, "갑", "을"));
+            games.push(game(month, "갑", "을"));
+            games.push(game(month, "을", "갑"));
+        }
+        games.push(game(3, "갑", "병"));
+        let ratings = fit(&games);
+        let (winner, loser) = (ratings["갑"].rating, ratings["을"].rating);
+        assert!(winner > 0.0 && loser < 0.0);
+        assert_eq!(ratings["을"].games, 9);
+        // 대국이 MIN_GAMES보다 적은 기사는 결과에 넣지 않습니다.
+        assert!(!ratings.contains_key("병"));
+    }
+}